@@ -1,5 +1,12 @@
+use std::{env::current_dir, fs};
+
 use cosmwasm_schema::write_api;
-use seda_common::msgs::{ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
+use seda_common::msgs::InstantiateMsg;
+use seda_contract::{
+    contract::MigrateMsg,
+    error::error_codes,
+    msgs::{ExecuteMsg, QueryMsg, SudoMsg},
+};
 
 fn main() {
     write_api! {
@@ -7,5 +14,12 @@ fn main() {
         execute: ExecuteMsg,
         query: QueryMsg,
         sudo: SudoMsg,
+        migrate: MigrateMsg,
     }
+
+    // the code table lets clients decode errors without matching on their messages
+    let out_dir = current_dir().unwrap().join("schema");
+    let error_codes = serde_json::to_string_pretty(&error_codes()).unwrap();
+    fs::write(out_dir.join("error_codes.json"), error_codes).unwrap();
+    println!("Exported error codes to {}", out_dir.join("error_codes.json").display());
 }
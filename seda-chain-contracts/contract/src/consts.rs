@@ -2,6 +2,14 @@ use cosmwasm_std::Uint128;
 
 pub const INITIAL_MINIMUM_STAKE_TO_REGISTER: Uint128 = Uint128::new(1);
 pub const INITIAL_MINIMUM_STAKE_FOR_COMMITTEE_ELIGIBILITY: Uint128 = Uint128::new(1);
+pub const INITIAL_UNBONDING_PERIOD_IN_BLOCKS: u64 = 0;
 
 pub const INITIAL_COMMIT_TIMEOUT_IN_BLOCKS: u64 = 10;
 pub const INITIAL_REVEAL_TIMEOUT_IN_BLOCKS: u64 = 10;
+pub const INITIAL_MAX_EXPIRED_PER_SWEEP: u32 = 100;
+pub const INITIAL_MIN_TIMEOUT_IN_BLOCKS: u64 = 1;
+pub const INITIAL_MAX_TIMEOUT_IN_BLOCKS: u64 = 1000;
+
+pub const INITIAL_DATA_RESULT_RETENTION_IN_BLOCKS: u64 = 100_000;
+pub const INITIAL_MAX_PRUNED_PER_SWEEP: u32 = 100;
+pub const INITIAL_MAX_CALLBACK_GAS_LIMIT: u64 = 1_000_000;
@@ -1,7 +1,8 @@
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Event;
 #[cfg(not(feature = "library"))]
-use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response};
-use cw2::set_contract_version;
+use cosmwasm_std::{entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response};
+use cw2::{get_contract_version, set_contract_version};
 use data_requests::TimeoutConfig;
 use seda_common::msgs::*;
 use staking::StakingConfig;
@@ -9,37 +10,61 @@ use staking::StakingConfig;
 use crate::{
     consts::{
         INITIAL_COMMIT_TIMEOUT_IN_BLOCKS,
+        INITIAL_DATA_RESULT_RETENTION_IN_BLOCKS,
+        INITIAL_MAX_CALLBACK_GAS_LIMIT,
+        INITIAL_MAX_EXPIRED_PER_SWEEP,
+        INITIAL_MAX_PRUNED_PER_SWEEP,
+        INITIAL_MAX_TIMEOUT_IN_BLOCKS,
         INITIAL_MINIMUM_STAKE_FOR_COMMITTEE_ELIGIBILITY,
         INITIAL_MINIMUM_STAKE_TO_REGISTER,
+        INITIAL_MIN_TIMEOUT_IN_BLOCKS,
         INITIAL_REVEAL_TIMEOUT_IN_BLOCKS,
+        INITIAL_UNBONDING_PERIOD_IN_BLOCKS,
     },
-    error::ContractError,
+    error::{CodedError, ContractError},
+    migrations,
     msgs::{
-        data_requests::{execute::dr_events::create_timeout_config_event, state::TIMEOUT_CONFIG},
+        data_requests::{
+            execute::dr_events::{
+                create_data_result_config_event,
+                create_extended_timeout_config_event,
+                create_timeout_config_event,
+            },
+            state::{
+                DataResultConfig,
+                ExtendedTimeoutConfig,
+                DATA_RESULT_CONFIG,
+                EXTENDED_TIMEOUT_CONFIG,
+                TIMEOUT_CONFIG,
+            },
+            sudo::{cw20_payout_reply, result_callback_reply, CW20_PAYOUT_REPLY_ID, RESULT_CALLBACK_REPLY_ID},
+        },
         owner::state::{OWNER, PENDING_OWNER},
         staking::{
-            execute::staking_events::create_staking_config_event,
-            state::{STAKERS, STAKING_CONFIG},
+            execute::staking_events::{create_extended_staking_config_event, create_staking_config_event},
+            state::{CommitteeSelection, ExtendedStakingConfig, EXTENDED_STAKING_CONFIG, STAKERS, STAKING_CONFIG},
         },
         ExecuteHandler,
+        ExecuteMsg,
         QueryHandler,
+        QueryMsg,
         SudoHandler,
+        SudoMsg,
     },
     state::{CHAIN_ID, PAUSED, TOKEN},
 };
 
 // version info for migration info
-const CONTRACT_NAME: &str = "staking";
+pub(crate) const CONTRACT_NAME: &str = "staking";
 pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const GIT_REVISION: &str = env!("GIT_REVISION");
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn instantiate(
-    deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    msg: InstantiateMsg,
-) -> Result<Response, ContractError> {
+pub fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: InstantiateMsg) -> Result<Response, CodedError> {
+    Ok(instantiate_contract(deps, msg)?)
+}
+
+fn instantiate_contract(deps: DepsMut, msg: InstantiateMsg) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     TOKEN.save(deps.storage, &msg.token)?;
     OWNER.save(deps.storage, &deps.api.addr_validate(&msg.owner)?)?;
@@ -63,12 +88,33 @@ pub fn instantiate(
 
     STAKING_CONFIG.save(deps.storage, &init_staking_config)?;
 
+    let init_extended_staking_config = ExtendedStakingConfig {
+        unbonding_period_in_blocks: INITIAL_UNBONDING_PERIOD_IN_BLOCKS,
+        slashing:                   None,
+        committee_selection:        CommitteeSelection::Window,
+    };
+    EXTENDED_STAKING_CONFIG.save(deps.storage, &init_extended_staking_config)?;
+
     let init_timeout_config = msg.timeout_config.unwrap_or(TimeoutConfig {
         commit_timeout_in_blocks: INITIAL_COMMIT_TIMEOUT_IN_BLOCKS,
         reveal_timeout_in_blocks: INITIAL_REVEAL_TIMEOUT_IN_BLOCKS,
     });
     TIMEOUT_CONFIG.save(deps.storage, &init_timeout_config)?;
 
+    let init_extended_timeout_config = ExtendedTimeoutConfig {
+        max_expired_per_sweep: INITIAL_MAX_EXPIRED_PER_SWEEP,
+        min_timeout_in_blocks: INITIAL_MIN_TIMEOUT_IN_BLOCKS,
+        max_timeout_in_blocks: INITIAL_MAX_TIMEOUT_IN_BLOCKS,
+    };
+    EXTENDED_TIMEOUT_CONFIG.save(deps.storage, &init_extended_timeout_config)?;
+
+    let init_data_result_config = DataResultConfig {
+        retention_in_blocks:    INITIAL_DATA_RESULT_RETENTION_IN_BLOCKS,
+        max_pruned_per_sweep:   INITIAL_MAX_PRUNED_PER_SWEEP,
+        max_callback_gas_limit: INITIAL_MAX_CALLBACK_GAS_LIMIT,
+    };
+    DATA_RESULT_CONFIG.save(deps.storage, &init_data_result_config)?;
+
     STAKERS.initialize(deps.storage)?;
     crate::msgs::data_requests::state::init_data_requests(deps.storage)?;
 
@@ -82,21 +128,74 @@ pub fn instantiate(
             ("git_revision", GIT_REVISION.to_string()),
         ]),
         create_staking_config_event(init_staking_config),
+        create_extended_staking_config_event(init_extended_staking_config),
         create_timeout_config_event(init_timeout_config),
+        create_extended_timeout_config_event(init_extended_timeout_config),
+        create_data_result_config_event(init_data_result_config),
     ]))
 }
 
+/// Message passed to the `migrate` entry point.
+///
+/// Migrations are driven entirely by the stored and current contract versions,
+/// so it carries no parameters.
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, CodedError> {
+    Ok(migrate_contract(deps)?)
+}
+
+fn migrate_contract(deps: DepsMut) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::MigrationWrongContract(stored.contract));
+    }
+
+    let old_version = migrations::parse_version(&stored.version)?;
+    let new_version = migrations::parse_version(CONTRACT_VERSION)?;
+    if new_version < old_version {
+        return Err(ContractError::MigrationDowngrade(
+            stored.version,
+            CONTRACT_VERSION.to_string(),
+        ));
+    }
+
+    let applied = migrations::migrate(deps.storage, &old_version, &new_version)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_event(Event::new("seda-contract").add_attributes([
+            ("action", "migrate".to_string()),
+            ("old_version", stored.version),
+            ("version", CONTRACT_VERSION.to_string()),
+            ("migrations", applied.join(",")),
+            ("git_revision", GIT_REVISION.to_string()),
+        ])))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> Result<Response, CodedError> {
+    Ok(msg.execute(deps, env, info)?)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> Result<Response, ContractError> {
-    msg.execute(deps, env, info)
+pub fn sudo(deps: DepsMut, env: Env, sudo: SudoMsg) -> Result<Response, CodedError> {
+    Ok(sudo.sudo(deps, env)?)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn sudo(deps: DepsMut, env: Env, sudo: SudoMsg) -> Result<Response, ContractError> {
-    sudo.sudo(deps, env)
+pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, CodedError> {
+    match reply.id {
+        RESULT_CALLBACK_REPLY_ID => Ok(result_callback_reply(deps, env, reply)?),
+        CW20_PAYOUT_REPLY_ID => Ok(cw20_payout_reply(deps, env, reply)?),
+        id => Err(ContractError::UnknownReplyId(id).into()),
+    }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
-    msg.query(deps, env)
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, CodedError> {
+    Ok(msg.query(deps, env)?)
 }
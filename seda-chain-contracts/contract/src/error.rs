@@ -1,19 +1,27 @@
-use cosmwasm_std::{StdError, Uint128};
+use std::fmt;
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, StdError, Uint128};
 use hex::FromHexError;
+use seda_common::msgs::data_requests::DataRequestStatus;
 use thiserror::Error;
 
+/// Errors returned by the contract, each rendering as `<Name>: <message>`.
+///
+/// They reach clients as a [`CodedError`], prefixed with their [`ContractError::code`]
+/// and [`ContractError::category`].
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[cfg(not(test))]
-    #[error(transparent)]
+    #[error("Std: {0}")]
     Std(#[from] StdError),
 
     #[cfg(test)]
-    #[error("{0}")]
+    #[error("Std: {0}")]
     Std(String),
 
     #[cfg(test)]
-    #[error("{0}")]
+    #[error("Dbg: {0}")]
     Dbg(String),
 
     // staking contract errors
@@ -55,31 +63,240 @@ pub enum ContractError {
     NotEnoughReveals,
     #[error("DataRequestExpired: Data request expired at block height {0} during {1} stage")]
     DataRequestExpired(u64, &'static str),
+    #[error("MissingVrfProof: A VRF proof is required to commit with the current committee selection")]
+    MissingVrfProof,
+    #[error("InvalidVrfProof: VRF proof could not be verified against the executor's public key")]
+    InvalidVrfProof,
+    #[error("NotInCommittee: Executor was not selected for the committee of this data request")]
+    NotInCommittee,
+    #[error("InvalidCursor: Invalid pagination cursor `{0}`")]
+    InvalidCursor(String),
+    #[error("UnknownReplyId: No handler for replies with id {0}")]
+    UnknownReplyId(u64),
 
     #[error("FromHex: Invalid hexadecimal input: {0}")]
     FromHex(#[from] FromHexError),
 
-    #[error(transparent)]
+    #[error("Payment: {0}")]
     Payment(#[from] cw_utils::PaymentError),
 
-    #[error(transparent)]
+    #[error("Common: {0}")]
     Common(#[from] seda_common::error::Error),
 
-    #[error(transparent)]
+    #[error("Overflow: {0}")]
     Overflow(#[from] cosmwasm_std::OverflowError),
 
-    #[error("Invalid hash length `{0}` expected 32 bytes")]
+    #[error("InvalidHashLength: Invalid hash length `{0}` expected 32 bytes")]
     InvalidHashLength(usize),
-    #[error("Invalid public key length `{0}` expected 33 bytes")]
+    #[error("InvalidPublicKeyLength: Invalid public key length `{0}` expected 33 bytes")]
     InvalidPublicKeyLength(usize),
-    #[error("Contract paused: cannot perform operation `{0}`")]
+    #[error("ContractPaused: Contract paused, cannot perform operation `{0}`")]
     ContractPaused(String),
-    #[error("Contract not paused: cannot unpause")]
+    #[error("ContractNotPaused: Contract not paused, cannot unpause")]
     ContractNotPaused,
     #[error("ZeroMinimumStakeToRegister: Minimum stake to register cannot be zero")]
     ZeroMinimumStakeToRegister,
     #[error("ZeroMinimumStakeForCommitteeEligibility: Minimum stake for committee eligibility cannot be zero")]
     ZeroMinimumStakeForCommitteeEligibility,
+    #[error("InvalidSlashingFraction: Slashing fraction cannot be greater than 1, got {0}")]
+    InvalidSlashingFraction(Decimal),
+    #[error("ZeroStakeCap: Stake cap of the stake weighted committee selection cannot be zero")]
+    ZeroStakeCap,
+    #[error("StakeWeightedTargetOutOfRange: Stake weighted committee selection drew a target beyond the total weight")]
+    StakeWeightedTargetOutOfRange,
+    #[error("ZeroMaxExpiredPerSweep: Maximum number of data requests expired per sweep cannot be zero")]
+    ZeroMaxExpiredPerSweep,
+    #[error("InvalidStatusTransition: Cannot move a data request from {from:?} to {to:?} (timeout: {timeout})")]
+    InvalidStatusTransition {
+        from:    DataRequestStatus,
+        to:      DataRequestStatus,
+        timeout: bool,
+    },
+    #[error("NotRemovableStatus: Cannot remove a data request that is {0:?}, it must be tallying")]
+    NotRemovableStatus(DataRequestStatus),
+    #[error("NotDataRequestPoster: Only the poster of the data request can do this")]
+    NotDataRequestPoster,
+    #[error("DataRequestNotCancellable: Only data requests that are committing without any commits can be cancelled")]
+    DataRequestNotCancellable,
+    #[error("DataRequestNotCommitting: The data request is no longer committing")]
+    DataRequestNotCommitting,
+    #[error("FeeBumpLowersFee: The gas price and gas limits of a data request can only be raised")]
+    FeeBumpLowersFee,
+    #[error("InvalidTimeoutBounds: Timeout bounds must be non-zero with min <= max, got min {0} and max {1}")]
+    InvalidTimeoutBounds(u64, u64),
+    #[error("ZeroMaxPrunedPerSweep: Maximum number of data results pruned per sweep cannot be zero")]
+    ZeroMaxPrunedPerSweep,
+    #[error("Cw20FeeTokenNotAllowed: Tokens of CW20 contract `{0}` are not accepted to pay for data requests")]
+    Cw20FeeTokenNotAllowed(String),
+    #[error("Cw20EscrowFeeBump: The fee of a data request paid in CW20 tokens cannot be bumped")]
+    Cw20EscrowFeeBump,
+    #[error("CallbackGasLimitTooHigh: Result callback gas limit {0} exceeds the maximum of {1}")]
+    CallbackGasLimitTooHigh(u64, u64),
+    #[error("ZeroCw20FeeTokenRate: Rate of a CW20 fee token cannot be zero")]
+    ZeroCw20FeeTokenRate,
+    #[error("NoFailedCw20Payouts: No failed payouts of CW20 contract {0} to claim")]
+    NoFailedCw20Payouts(String),
+    #[error("EmptyDataRequestBatch: A batch of data requests must post at least one")]
+    EmptyDataRequestBatch,
+
+    // migration errors
+    #[error("InvalidContractVersion: Invalid contract version `{0}`: {1}")]
+    InvalidContractVersion(String, String),
+    #[error("MigrationWrongContract: Cannot migrate from contract `{0}`")]
+    MigrationWrongContract(String),
+    #[error("MigrationDowngrade: Cannot migrate from version {0} to older version {1}")]
+    MigrationDowngrade(String, String),
+}
+
+/// The group of operations an error belongs to.
+#[cw_serde]
+#[derive(Copy)]
+pub enum ErrorCategory {
+    General,
+    Owner,
+    Staking,
+    DataRequest,
+    Migration,
+}
+
+impl ErrorCategory {
+    /// The name of the category, the same as its JSON representation.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCategory::General => "general",
+            ErrorCategory::Owner => "owner",
+            ErrorCategory::Staking => "staking",
+            ErrorCategory::DataRequest => "data_request",
+            ErrorCategory::Migration => "migration",
+        }
+    }
+}
+
+/// A [`ContractError`] as returned by the entry points, rendering as
+/// `[code=<code> category=<category>] <Name>: <message>` so clients can decode it without matching on the message.
+#[derive(Debug, PartialEq)]
+pub struct CodedError(pub ContractError);
+
+impl fmt::Display for CodedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[code={} category={}] {}",
+            self.0.code(),
+            self.0.category().as_str(),
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for CodedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+impl From<ContractError> for CodedError {
+    fn from(err: ContractError) -> Self {
+        CodedError(err)
+    }
+}
+
+/// An entry of the error code table.
+#[cw_serde]
+pub struct ErrorCodeInfo {
+    pub code:     u32,
+    pub category: ErrorCategory,
+    pub name:     String,
+}
+
+macro_rules! error_codes {
+    ($($variant:ident => ($code:literal, $category:ident),)*) => {
+        impl ContractError {
+            /// Stable numeric code of the error.
+            /// Codes are never changed or reused, new errors get a new code in their category range.
+            pub fn code(&self) -> u32 {
+                match self {
+                    $(ContractError::$variant { .. } => $code,)*
+                    #[cfg(test)]
+                    ContractError::Dbg(_) => 1001,
+                }
+            }
+
+            pub fn category(&self) -> ErrorCategory {
+                match self {
+                    $(ContractError::$variant { .. } => ErrorCategory::$category,)*
+                    #[cfg(test)]
+                    ContractError::Dbg(_) => ErrorCategory::General,
+                }
+            }
+        }
+
+        /// The code and category of every error, ordered by code.
+        pub fn error_codes() -> Vec<ErrorCodeInfo> {
+            vec![$(ErrorCodeInfo {
+                code:     $code,
+                category: ErrorCategory::$category,
+                name:     stringify!($variant).to_string(),
+            },)*]
+        }
+    };
+}
+
+error_codes! {
+    Std => (1000, General),
+    FromHex => (1002, General),
+    Payment => (1003, General),
+    Common => (1004, General),
+    Overflow => (1005, General),
+    InvalidHashLength => (1006, General),
+    InvalidPublicKeyLength => (1007, General),
+    InvalidCursor => (1008, General),
+    UnknownReplyId => (1009, General),
+    NotOwner => (2000, Owner),
+    NotPendingOwner => (2001, Owner),
+    NoPendingOwnerFound => (2002, Owner),
+    NotOnAllowlist => (2003, Owner),
+    ContractPaused => (2004, Owner),
+    ContractNotPaused => (2005, Owner),
+    NoFunds => (3000, Staking),
+    ZeroMinimumStakeToRegister => (3001, Staking),
+    ZeroMinimumStakeForCommitteeEligibility => (3002, Staking),
+    InvalidSlashingFraction => (3003, Staking),
+    ZeroStakeCap => (3004, Staking),
+    StakeWeightedTargetOutOfRange => (3005, Staking),
+    InsufficientFunds => (4000, DataRequest),
+    DataRequestAlreadyExists => (4001, DataRequest),
+    DataRequestReplicationFactorZero => (4002, DataRequest),
+    DataRequestReplicationFactorTooHigh => (4003, DataRequest),
+    AlreadyCommitted => (4004, DataRequest),
+    RevealNotStarted => (4005, DataRequest),
+    RevealStarted => (4006, DataRequest),
+    NotCommitted => (4007, DataRequest),
+    AlreadyRevealed => (4008, DataRequest),
+    RevealMismatch => (4009, DataRequest),
+    NotEnoughReveals => (4010, DataRequest),
+    DataRequestExpired => (4011, DataRequest),
+    MissingVrfProof => (4012, DataRequest),
+    InvalidVrfProof => (4013, DataRequest),
+    NotInCommittee => (4014, DataRequest),
+    InvalidStatusTransition => (4015, DataRequest),
+    NotRemovableStatus => (4016, DataRequest),
+    NotDataRequestPoster => (4017, DataRequest),
+    DataRequestNotCancellable => (4018, DataRequest),
+    DataRequestNotCommitting => (4019, DataRequest),
+    FeeBumpLowersFee => (4020, DataRequest),
+    ZeroMaxExpiredPerSweep => (4021, DataRequest),
+    InvalidTimeoutBounds => (4022, DataRequest),
+    ZeroMaxPrunedPerSweep => (4023, DataRequest),
+    Cw20FeeTokenNotAllowed => (4024, DataRequest),
+    Cw20EscrowFeeBump => (4025, DataRequest),
+    CallbackGasLimitTooHigh => (4026, DataRequest),
+    ZeroCw20FeeTokenRate => (4027, DataRequest),
+    NoFailedCw20Payouts => (4028, DataRequest),
+    EmptyDataRequestBatch => (4029, DataRequest),
+    InvalidContractVersion => (5000, Migration),
+    MigrationWrongContract => (5001, Migration),
+    MigrationDowngrade => (5002, Migration),
 }
 
 #[cfg(test)]
@@ -91,6 +308,9 @@ impl From<StdError> for ContractError {
 
 impl From<ContractError> for StdError {
     fn from(err: ContractError) -> StdError {
-        StdError::generic_err(err.to_string())
+        StdError::generic_err(CodedError(err).to_string())
     }
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+
+use super::*;
+
+#[test]
+fn error_codes_are_unique() {
+    let codes = error_codes();
+    let unique = codes.iter().map(|info| info.code).collect::<HashSet<_>>();
+    assert_eq!(codes.len(), unique.len());
+
+    let mut sorted = codes.clone();
+    sorted.sort_by_key(|info| info.code);
+    assert_eq!(codes, sorted);
+}
+
+#[test]
+fn errors_render_their_code_and_category() {
+    let errors = [
+        ContractError::Std("not found".to_string()),
+        ContractError::FromHex(FromHexError::OddLength),
+        ContractError::Payment(cw_utils::PaymentError::NoFunds {}),
+        ContractError::Overflow(cosmwasm_std::OverflowError::new(cosmwasm_std::OverflowOperation::Add)),
+        ContractError::InvalidHashLength(31),
+        ContractError::InvalidPublicKeyLength(32),
+        ContractError::InvalidCursor("cursor".to_string()),
+        ContractError::UnknownReplyId(7),
+        ContractError::NotOwner,
+        ContractError::NotPendingOwner,
+        ContractError::NoPendingOwnerFound,
+        ContractError::NotOnAllowlist,
+        ContractError::ContractPaused("commit".to_string()),
+        ContractError::ContractNotPaused,
+        ContractError::NoFunds,
+        ContractError::ZeroMinimumStakeToRegister,
+        ContractError::ZeroMinimumStakeForCommitteeEligibility,
+        ContractError::InvalidSlashingFraction(Decimal::percent(200)),
+        ContractError::ZeroStakeCap,
+        ContractError::StakeWeightedTargetOutOfRange,
+        ContractError::InsufficientFunds(Uint128::new(2), Uint128::new(1)),
+        ContractError::DataRequestAlreadyExists,
+        ContractError::DataRequestReplicationFactorZero,
+        ContractError::DataRequestReplicationFactorTooHigh(1),
+        ContractError::AlreadyCommitted,
+        ContractError::RevealNotStarted,
+        ContractError::RevealStarted,
+        ContractError::NotCommitted,
+        ContractError::AlreadyRevealed,
+        ContractError::RevealMismatch,
+        ContractError::NotEnoughReveals,
+        ContractError::DataRequestExpired(10, "commit"),
+        ContractError::MissingVrfProof,
+        ContractError::InvalidVrfProof,
+        ContractError::NotInCommittee,
+        ContractError::InvalidStatusTransition {
+            from:    DataRequestStatus::Revealing,
+            to:      DataRequestStatus::Committing,
+            timeout: false,
+        },
+        ContractError::NotRemovableStatus(DataRequestStatus::Committing),
+        ContractError::NotDataRequestPoster,
+        ContractError::DataRequestNotCancellable,
+        ContractError::DataRequestNotCommitting,
+        ContractError::FeeBumpLowersFee,
+        ContractError::ZeroMaxExpiredPerSweep,
+        ContractError::InvalidTimeoutBounds(2, 1),
+        ContractError::ZeroMaxPrunedPerSweep,
+        ContractError::Cw20FeeTokenNotAllowed("cw20".to_string()),
+        ContractError::Cw20EscrowFeeBump,
+        ContractError::CallbackGasLimitTooHigh(2, 1),
+        ContractError::ZeroCw20FeeTokenRate,
+        ContractError::NoFailedCw20Payouts("cw20".to_string()),
+        ContractError::EmptyDataRequestBatch,
+        ContractError::InvalidContractVersion("x".to_string(), "invalid".to_string()),
+        ContractError::MigrationWrongContract("other".to_string()),
+        ContractError::MigrationDowngrade("2.0.0".to_string(), "1.0.0".to_string()),
+    ];
+
+    let codes = error_codes();
+    for err in errors {
+        assert!(codes
+            .iter()
+            .any(|info| info.code == err.code() && info.category == err.category()));
+        let message = err.to_string();
+        let (name, _) = message.split_once(": ").unwrap();
+        assert!(
+            name.chars().all(char::is_alphanumeric),
+            "{message} should start with its name"
+        );
+
+        let category = serde_json::to_value(err.category()).unwrap();
+        assert_eq!(category.as_str().unwrap(), err.category().as_str());
+        let prefix = format!("[code={} category={}] {name}: ", err.code(), err.category().as_str());
+        let coded = CodedError(err).to_string();
+        assert!(coded.starts_with(&prefix), "{coded} should start with {prefix}");
+    }
+}
+
+#[test]
+fn std_error_keeps_the_code() {
+    let err: StdError = ContractError::RevealMismatch.into();
+    assert!(err
+        .to_string()
+        .contains("[code=4009 category=data_request] RevealMismatch:"));
+}
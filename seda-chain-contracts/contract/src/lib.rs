@@ -1,6 +1,7 @@
 pub mod consts;
 pub mod contract;
-mod error;
+pub mod error;
+mod migrations;
 pub mod msgs;
 pub mod state;
 mod types;
@@ -0,0 +1,92 @@
+use cosmwasm_std::Storage;
+use semver::Version;
+
+use crate::error::ContractError;
+
+mod v0_6_0;
+
+/// A storage migration introduced by a given contract version.
+pub struct Migration {
+    /// The contract version that introduced this migration.
+    /// It runs when migrating from a version below it to a version at or above it.
+    pub version: &'static str,
+    /// Short identifier emitted in the `migrate` event.
+    pub name:    &'static str,
+    pub migrate: fn(&mut dyn Storage) -> Result<(), ContractError>,
+}
+
+/// Every storage migration, ordered by version.
+/// New steps must be appended so that they run after the ones they build on.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: "0.6.0",
+        name:    "init_extended_staking_config",
+        migrate: v0_6_0::init_extended_staking_config,
+    },
+    Migration {
+        version: "0.6.0",
+        name:    "init_eligible_executors",
+        migrate: v0_6_0::init_eligible_executors,
+    },
+    Migration {
+        version: "0.6.0",
+        name:    "init_data_request_status_index",
+        migrate: v0_6_0::init_data_request_status_index,
+    },
+    Migration {
+        version: "0.6.0",
+        name:    "init_extended_timeout_config",
+        migrate: v0_6_0::init_extended_timeout_config,
+    },
+    Migration {
+        version: "0.6.0",
+        name:    "move_data_request_results",
+        migrate: v0_6_0::move_data_request_results,
+    },
+    Migration {
+        version: "0.6.0",
+        name:    "init_data_request_poster_index",
+        migrate: v0_6_0::init_data_request_poster_index,
+    },
+    Migration {
+        version: "0.6.0",
+        name:    "init_data_request_program_index",
+        migrate: v0_6_0::init_data_request_program_index,
+    },
+    Migration {
+        version: "0.6.0",
+        name:    "init_data_result_config",
+        migrate: v0_6_0::init_data_result_config,
+    },
+];
+
+pub fn parse_version(version: &str) -> Result<Version, ContractError> {
+    Version::parse(version).map_err(|e| ContractError::InvalidContractVersion(version.to_string(), e.to_string()))
+}
+
+/// Runs, in order, every migration introduced after `from` and up to and including `to`.
+/// Returns the names of the migrations that were applied.
+pub fn migrate(store: &mut dyn Storage, from: &Version, to: &Version) -> Result<Vec<&'static str>, ContractError> {
+    run_migrations(MIGRATIONS, store, from, to)
+}
+
+fn run_migrations(
+    migrations: &[Migration],
+    store: &mut dyn Storage,
+    from: &Version,
+    to: &Version,
+) -> Result<Vec<&'static str>, ContractError> {
+    let mut applied = Vec::new();
+    for migration in migrations {
+        let version = parse_version(migration.version)?;
+        if from < &version && &version <= to {
+            (migration.migrate)(store)?;
+            applied.push(migration.name);
+        }
+    }
+
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests;
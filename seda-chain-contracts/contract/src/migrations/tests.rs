@@ -0,0 +1,201 @@
+use cosmwasm_std::testing::MockStorage;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Item;
+use seda_common::types::Hash;
+
+use super::*;
+use crate::{
+    contract::{CONTRACT_NAME, CONTRACT_VERSION},
+    msgs::{
+        data_requests::{state::DR_BY_POSTER, test::test_helpers::calculate_dr_id_and_args},
+        staking::state::{EXTENDED_STAKING_CONFIG, STAKERS},
+    },
+    types::FromHexStr,
+    TestInfo,
+};
+
+const STEPS: Item<Vec<String>> = Item::new("test_migration_steps");
+
+fn record(store: &mut dyn Storage, step: &str) -> Result<(), ContractError> {
+    let mut steps = STEPS.may_load(store)?.unwrap_or_default();
+    steps.push(step.to_string());
+    STEPS.save(store, &steps)?;
+    Ok(())
+}
+
+const TEST_MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: "0.2.0",
+        name:    "first",
+        migrate: |store| record(store, "first"),
+    },
+    Migration {
+        version: "0.3.0",
+        name:    "second",
+        migrate: |store| record(store, "second"),
+    },
+    Migration {
+        version: "0.3.0",
+        name:    "third",
+        migrate: |store| record(store, "third"),
+    },
+];
+
+fn version(v: &str) -> Version {
+    parse_version(v).unwrap()
+}
+
+#[test]
+fn registered_migrations_are_ordered() {
+    let versions = MIGRATIONS
+        .iter()
+        .map(|m| parse_version(m.version).unwrap())
+        .collect::<Vec<_>>();
+    assert!(versions.windows(2).all(|w| w[0] <= w[1]));
+    assert!(versions.iter().all(|v| v <= &version(CONTRACT_VERSION)));
+}
+
+#[test]
+fn runs_only_migrations_in_range_in_order() {
+    let mut store = MockStorage::new();
+
+    let applied = run_migrations(TEST_MIGRATIONS, &mut store, &version("0.2.0"), &version("0.3.1")).unwrap();
+    assert_eq!(vec!["second", "third"], applied);
+    assert_eq!(vec!["second", "third"], STEPS.load(&store).unwrap());
+}
+
+#[test]
+fn runs_full_chain() {
+    let mut store = MockStorage::new();
+
+    let applied = run_migrations(TEST_MIGRATIONS, &mut store, &version("0.1.0"), &version("0.3.0")).unwrap();
+    assert_eq!(vec!["first", "second", "third"], applied);
+}
+
+#[test]
+fn same_version_runs_nothing() {
+    let mut store = MockStorage::new();
+
+    let applied = run_migrations(TEST_MIGRATIONS, &mut store, &version("0.3.0"), &version("0.3.0")).unwrap();
+    assert!(applied.is_empty());
+    assert_eq!(None, STEPS.may_load(&store).unwrap());
+}
+
+#[test]
+fn migrate_to_same_version() {
+    let mut test_info = TestInfo::init();
+    test_info.migrate().unwrap();
+
+    let version = get_contract_version(&*test_info.app().contract_storage(&test_info.contract_addr())).unwrap();
+    assert_eq!(CONTRACT_NAME, version.contract);
+    assert_eq!(CONTRACT_VERSION, version.version);
+}
+
+#[test]
+fn migrate_from_older_version() {
+    let mut test_info = TestInfo::init();
+    let contract_addr = test_info.contract_addr();
+    set_contract_version(
+        &mut *test_info.app_mut().contract_storage_mut(&contract_addr),
+        CONTRACT_NAME,
+        "0.0.1",
+    )
+    .unwrap();
+
+    test_info.migrate().unwrap();
+
+    let version = get_contract_version(&*test_info.app().contract_storage(&contract_addr)).unwrap();
+    assert_eq!(CONTRACT_VERSION, version.version);
+}
+
+#[test]
+fn cannot_downgrade() {
+    let mut test_info = TestInfo::init();
+    let contract_addr = test_info.contract_addr();
+    set_contract_version(
+        &mut *test_info.app_mut().contract_storage_mut(&contract_addr),
+        CONTRACT_NAME,
+        "999.0.0",
+    )
+    .unwrap();
+
+    let res = test_info.migrate();
+    assert!(
+        res.is_err_and(|x| x == ContractError::MigrationDowngrade("999.0.0".to_string(), CONTRACT_VERSION.to_string()))
+    );
+}
+
+#[test]
+fn cannot_migrate_other_contract() {
+    let mut test_info = TestInfo::init();
+    let contract_addr = test_info.contract_addr();
+    set_contract_version(
+        &mut *test_info.app_mut().contract_storage_mut(&contract_addr),
+        "crates.io:cw20-base",
+        "0.0.1",
+    )
+    .unwrap();
+
+    let res = test_info.migrate();
+    assert!(res.is_err_and(|x| x == ContractError::MigrationWrongContract("crates.io:cw20-base".to_string())));
+}
+
+#[test]
+fn migrate_from_0_5_initializes_extended_staking_config() {
+    let mut test_info = TestInfo::init();
+    let contract_addr = test_info.contract_addr();
+    {
+        let mut storage = test_info.app_mut().contract_storage_mut(&contract_addr);
+        set_contract_version(&mut *storage, CONTRACT_NAME, "0.5.9").unwrap();
+        EXTENDED_STAKING_CONFIG.remove(&mut *storage);
+    }
+
+    test_info.migrate().unwrap();
+
+    let config = test_info.get_extended_staking_config();
+    assert_eq!(0, config.unbonding_period_in_blocks);
+}
+
+#[test]
+fn migrate_from_0_5_builds_eligible_executors() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 10).unwrap();
+
+    let contract_addr = test_info.contract_addr();
+    {
+        let mut storage = test_info.app_mut().contract_storage_mut(&contract_addr);
+        set_contract_version(&mut *storage, CONTRACT_NAME, "0.5.9").unwrap();
+        STAKERS.eligible.remove(&mut *storage, alice.pub_key()).unwrap();
+        STAKERS.eligible.len.remove(&mut *storage);
+    }
+
+    test_info.migrate().unwrap();
+
+    let storage = test_info.app().contract_storage(&contract_addr);
+    assert_eq!(1, STAKERS.eligible_len(&*storage).unwrap());
+    assert_eq!(Some(0), STAKERS.eligible_index(&*storage, alice.pub_key()).unwrap());
+}
+
+#[test]
+fn migrate_from_0_5_indexes_data_requests_by_poster() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(42));
+    let dr = calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    let contract_addr = test_info.contract_addr();
+    {
+        let mut storage = test_info.app_mut().contract_storage_mut(&contract_addr);
+        set_contract_version(&mut *storage, CONTRACT_NAME, "0.5.9").unwrap();
+        DR_BY_POSTER.remove(&mut *storage, (&alice.addr(), &Hash::from_hex_str(&dr_id).unwrap()));
+    }
+    assert!(test_info.get_data_requests_by_poster(&alice, None, 10).is_empty());
+
+    test_info.migrate().unwrap();
+
+    let requests = test_info.get_data_requests_by_poster(&alice, None, 10);
+    assert_eq!(vec![dr_id], requests.into_iter().map(|dr| dr.dr_id).collect::<Vec<_>>());
+}
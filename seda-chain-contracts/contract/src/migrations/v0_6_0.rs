@@ -0,0 +1,113 @@
+use cosmwasm_std::Storage;
+
+use crate::{
+    consts::{
+        INITIAL_DATA_RESULT_RETENTION_IN_BLOCKS,
+        INITIAL_MAX_CALLBACK_GAS_LIMIT,
+        INITIAL_MAX_EXPIRED_PER_SWEEP,
+        INITIAL_MAX_PRUNED_PER_SWEEP,
+        INITIAL_MAX_TIMEOUT_IN_BLOCKS,
+        INITIAL_MIN_TIMEOUT_IN_BLOCKS,
+        INITIAL_UNBONDING_PERIOD_IN_BLOCKS,
+    },
+    error::ContractError,
+    msgs::{
+        data_requests::state::{
+            backfill_program_index,
+            backfill_status_index,
+            index_escrows_by_poster,
+            move_results_out_of_requests,
+            DataResultConfig,
+            ExtendedTimeoutConfig,
+            DATA_RESULT_CONFIG,
+            EXTENDED_TIMEOUT_CONFIG,
+        },
+        staking::state::{CommitteeSelection, ExtendedStakingConfig, EXTENDED_STAKING_CONFIG, STAKERS},
+    },
+};
+
+/// Saves the default extended staking config.
+/// Tokens that were already pending withdrawal have no unbonding entries, so they stay withdrawable.
+pub fn init_extended_staking_config(store: &mut dyn Storage) -> Result<(), ContractError> {
+    if EXTENDED_STAKING_CONFIG.may_load(store)?.is_none() {
+        EXTENDED_STAKING_CONFIG.save(
+            store,
+            &ExtendedStakingConfig {
+                unbonding_period_in_blocks: INITIAL_UNBONDING_PERIOD_IN_BLOCKS,
+                slashing:                   None,
+                committee_selection:        CommitteeSelection::Window,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Builds the eligible executor index from the existing stakers.
+pub fn init_eligible_executors(store: &mut dyn Storage) -> Result<(), ContractError> {
+    if STAKERS.eligible.len.may_load(store)?.is_none() {
+        STAKERS.eligible.initialize(store)?;
+    }
+    STAKERS.sync_all_eligibility(store)?;
+
+    Ok(())
+}
+
+/// Builds the index of data requests ordered by posting height used for cursor pagination.
+pub fn init_data_request_status_index(store: &mut dyn Storage) -> Result<(), ContractError> {
+    backfill_status_index(store)?;
+
+    Ok(())
+}
+
+/// Saves the default extended timeout config.
+pub fn init_extended_timeout_config(store: &mut dyn Storage) -> Result<(), ContractError> {
+    if EXTENDED_TIMEOUT_CONFIG.may_load(store)?.is_none() {
+        EXTENDED_TIMEOUT_CONFIG.save(
+            store,
+            &ExtendedTimeoutConfig {
+                max_expired_per_sweep: INITIAL_MAX_EXPIRED_PER_SWEEP,
+                min_timeout_in_blocks: INITIAL_MIN_TIMEOUT_IN_BLOCKS,
+                max_timeout_in_blocks: INITIAL_MAX_TIMEOUT_IN_BLOCKS,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Moves the commits and reveals of the pending data requests to their own maps.
+pub fn move_data_request_results(store: &mut dyn Storage) -> Result<(), ContractError> {
+    move_results_out_of_requests(store)?;
+
+    Ok(())
+}
+
+/// Indexes the pending data requests by poster.
+pub fn init_data_request_poster_index(store: &mut dyn Storage) -> Result<(), ContractError> {
+    index_escrows_by_poster(store)?;
+
+    Ok(())
+}
+
+/// Indexes the pending data requests by exec and tally program.
+pub fn init_data_request_program_index(store: &mut dyn Storage) -> Result<(), ContractError> {
+    backfill_program_index(store)?;
+
+    Ok(())
+}
+
+pub fn init_data_result_config(store: &mut dyn Storage) -> Result<(), ContractError> {
+    if DATA_RESULT_CONFIG.may_load(store)?.is_none() {
+        DATA_RESULT_CONFIG.save(
+            store,
+            &DataResultConfig {
+                retention_in_blocks:    INITIAL_DATA_RESULT_RETENTION_IN_BLOCKS,
+                max_pruned_per_sweep:   INITIAL_MAX_PRUNED_PER_SWEEP,
+                max_callback_gas_limit: INITIAL_MAX_CALLBACK_GAS_LIMIT,
+            },
+        )?;
+    }
+
+    Ok(())
+}
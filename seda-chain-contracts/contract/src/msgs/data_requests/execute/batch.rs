@@ -0,0 +1,96 @@
+use commit_result::{store_commit, CommitContext};
+use reveal_result::store_reveal;
+
+use super::*;
+use crate::state::CHAIN_ID;
+
+impl BatchItemResult {
+    fn new(dr_id: String, outcome: Result<(), ContractError>) -> Self {
+        match outcome {
+            Ok(()) => BatchItemResult {
+                dr_id,
+                success: true,
+                error_code: None,
+                error: None,
+            },
+            Err(err) => BatchItemResult {
+                dr_id,
+                success: false,
+                error_code: Some(err.code()),
+                error: Some(err.to_string()),
+            },
+        }
+    }
+}
+
+fn batch_response(action: &str, events: Vec<Event>, results: &[BatchItemResult]) -> Result<Response, ContractError> {
+    let succeeded = results.iter().filter(|result| result.success).count();
+
+    Ok(Response::new()
+        .add_attribute("action", action)
+        .add_attribute("succeeded", succeeded.to_string())
+        .add_attribute("failed", (results.len() - succeeded).to_string())
+        .add_events(events)
+        .set_data(to_json_binary(results)?))
+}
+
+/// Commits data results of one executor, each verified and stored on its own.
+/// An item only writes to storage once all its checks pass, so a failed item leaves no trace.
+/// The state shared by all the items, such as the executor's stake, is loaded once for the batch.
+pub(in crate::msgs::data_requests) fn batch_commit_results(
+    mut deps: DepsMut,
+    env: Env,
+    public_key: String,
+    commits: Vec<BatchCommit>,
+) -> Result<Response, ContractError> {
+    let context = CommitContext::load(deps.storage, &public_key)?;
+    let mut events = Vec::with_capacity(commits.len());
+    let mut results = Vec::with_capacity(commits.len());
+    for item in commits {
+        let dr_id = item.dr_id.clone();
+        let commit = execute::commit_result::Execute {
+            dr_id:      item.dr_id,
+            commitment: item.commitment,
+            public_key: public_key.clone(),
+            proof:      item.proof,
+        };
+        let outcome = item
+            .vrf_proof
+            .map(hex::decode)
+            .transpose()
+            .map_err(ContractError::from)
+            .and_then(|vrf_proof| store_commit(deps.branch(), &env, &context, commit, vrf_proof.as_deref()))
+            .map(|event| events.push(event));
+        results.push(BatchItemResult::new(dr_id, outcome));
+    }
+
+    batch_response("batch_commit_data_results", events, &results)
+}
+
+/// Reveals data results of one executor, each verified and stored on its own.
+/// An item only writes to storage once all its checks pass, so a failed item leaves no trace.
+pub(in crate::msgs::data_requests) fn batch_reveal_results(
+    mut deps: DepsMut,
+    env: Env,
+    public_key: String,
+    reveals: Vec<BatchReveal>,
+) -> Result<Response, ContractError> {
+    let chain_id = CHAIN_ID.load(deps.storage)?;
+    let mut events = Vec::with_capacity(reveals.len());
+    let mut results = Vec::with_capacity(reveals.len());
+    for item in reveals {
+        let dr_id = item.dr_id.clone();
+        let reveal = execute::reveal_result::Execute {
+            dr_id:       item.dr_id,
+            reveal_body: item.reveal_body,
+            public_key:  public_key.clone(),
+            proof:       item.proof,
+            stderr:      item.stderr,
+            stdout:      item.stdout,
+        };
+        let outcome = store_reveal(deps.branch(), &env, &chain_id, reveal).map(|event| events.push(event));
+        results.push(BatchItemResult::new(dr_id, outcome));
+    }
+
+    batch_response("batch_reveal_data_results", events, &results)
+}
@@ -0,0 +1,75 @@
+use post_request::{callback_funds, required_funds};
+use state::{DR_ESCROW, DR_RESULT_CALLBACKS};
+
+use super::*;
+use crate::state::TOKEN;
+
+/// Adds funds to the escrow of a data request that is still committing
+/// and raises its gas price and gas limits.
+///
+/// The data request keeps its id and its arguments as posted, which still hash to that id.
+/// The bumped values are stored next to it and replace the posted ones whenever it is loaded,
+/// so the tally pays executors at the bumped gas price.
+pub(in crate::msgs::data_requests) fn bump_fee(
+    deps: DepsMut,
+    info: MessageInfo,
+    dr_id: String,
+    gas_price: Uint128,
+    exec_gas_limit: u64,
+    tally_gas_limit: u64,
+) -> Result<Response, ContractError> {
+    let dr_id_hash = Hash::from_hex_str(&dr_id)?;
+    let mut escrow = DR_ESCROW.load(deps.storage, &dr_id_hash)?;
+
+    // only the poster can bump the fee, as they are the one refunded
+    if info.sender != escrow.poster {
+        return Err(ContractError::NotDataRequestPoster);
+    }
+
+    // the gas price and limits can only go up
+    let dr = state::load_request(deps.storage, &dr_id_hash)?;
+    if gas_price < dr.gas_price || exec_gas_limit < dr.exec_gas_limit || tally_gas_limit < dr.tally_gas_limit {
+        return Err(ContractError::FeeBumpLowersFee);
+    }
+
+    // native funds cannot be added to an escrow of CW20 tokens
+    if escrow.cw20_contract.is_some() {
+        return Err(ContractError::Cw20EscrowFeeBump);
+    }
+
+    // the escrow must cover the new values, same as when posting
+    let token = TOKEN.load(deps.storage)?;
+    let funds = cw_utils::may_pay(&info, &token)?;
+    let escrowed = escrow.amount + funds;
+    // the result callback, if any, is paid for at the new gas price as well
+    let callback_gas_limit = DR_RESULT_CALLBACKS
+        .may_load(deps.storage, &dr_id_hash)?
+        .map_or(0, |callback| callback.gas_limit);
+    let required = required_funds(exec_gas_limit, tally_gas_limit, gas_price)?
+        .checked_add(callback_funds(callback_gas_limit, gas_price)?)?;
+    if escrowed < required {
+        return Err(ContractError::InsufficientFunds(required, escrowed));
+    }
+
+    let bump = state::FeeBump {
+        gas_price,
+        exec_gas_limit,
+        tally_gas_limit,
+    };
+    state::bump_request_fee(deps.storage, dr_id_hash, &bump)?;
+
+    escrow.amount = escrowed;
+    state::save_escrow(deps.storage, &dr_id_hash, &escrow)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "bump_data_request_fee")
+        .add_event(Event::new("seda-data-request-fee-bump").add_attributes([
+            ("dr_id", dr_id),
+            ("gas_price", gas_price.to_string()),
+            ("exec_gas_limit", exec_gas_limit.to_string()),
+            ("tally_gas_limit", tally_gas_limit.to_string()),
+            ("added_funds", funds.to_string()),
+            ("escrow", escrowed.to_string()),
+            ("version", CONTRACT_VERSION.to_string()),
+        ])))
+}
@@ -0,0 +1,39 @@
+use state::DR_ESCROW;
+
+use super::*;
+use crate::state::TOKEN;
+
+/// Cancels a data request that no executor committed on yet,
+/// removing it from the pool and refunding the escrow to its poster.
+pub(in crate::msgs::data_requests) fn cancel_request(
+    deps: DepsMut,
+    info: MessageInfo,
+    dr_id: String,
+) -> Result<Response, ContractError> {
+    let dr_id_hash = Hash::from_hex_str(&dr_id)?;
+    let escrow = DR_ESCROW.load(deps.storage, &dr_id_hash)?;
+
+    // only the poster can cancel their data request
+    if info.sender != escrow.poster {
+        return Err(ContractError::NotDataRequestPoster);
+    }
+
+    state::cancel_request(deps.storage, dr_id_hash)?;
+    state::remove_escrow(deps.storage, &dr_id_hash)?;
+
+    let mut response = Response::new()
+        .add_attribute("action", "cancel_data_request")
+        .add_event(Event::new("seda-data-request-cancelled").add_attributes([
+            ("dr_id", dr_id),
+            ("dr_poster", escrow.poster.to_string()),
+            ("refund", escrow.amount.to_string()),
+            ("version", CONTRACT_VERSION.to_string()),
+        ]));
+
+    if !escrow.amount.is_zero() {
+        let token = TOKEN.load(deps.storage)?;
+        response = response.add_message(escrow.transfer_msg(&token, escrow.poster.to_string(), escrow.amount)?);
+    }
+
+    Ok(response)
+}
@@ -1,4 +1,11 @@
-use staking::state::{STAKERS, STAKING_CONFIG};
+use owner::state::ALLOWLIST;
+use staking::state::{
+    is_eligible_for_dr::is_eligible_for_dr,
+    CommitteeSelection,
+    EXTENDED_STAKING_CONFIG,
+    STAKERS,
+    STAKING_CONFIG,
+};
 
 use super::*;
 use crate::state::CHAIN_ID;
@@ -6,66 +13,154 @@ use crate::state::CHAIN_ID;
 impl ExecuteHandler for execute::commit_result::Execute {
     /// Posts a data result of a data request with an attached hash of the answer and salt.
     fn execute(self, deps: DepsMut, env: Env, _info: MessageInfo) -> Result<Response, ContractError> {
-        // find the data request from the pool (if it exists, otherwise error)
-        let dr_id = Hash::from_hex_str(&self.dr_id)?;
-        let mut dr = state::load_request(deps.storage, &dr_id)?;
-
-        verify_commit(deps.as_ref(), &env, &self, &dr)?;
-
-        // add the commitment to the data request
-        let commitment = Hash::from_hex_str(&self.commitment)?;
-        dr.commits.insert(self.public_key.clone(), commitment);
-
-        let resp = Response::new().add_attribute("action", "commit_data_result").add_event(
-            Event::new("seda-commitment").add_attributes([
-                ("dr_id", self.dr_id),
-                ("posted_dr_height", dr.height.to_string()),
-                ("commitment", self.commitment),
-                ("executor", self.public_key),
-                ("version", CONTRACT_VERSION.to_string()),
-            ]),
-        );
-        state::commit(deps.storage, env.block.height, dr_id, dr)?;
-        Ok(resp)
+        commit_result(deps, env, self, None)
     }
 }
 
+/// Posts a data result, with the executor's committee selection VRF proof if there is one.
+pub(in crate::msgs::data_requests) fn commit_result(
+    deps: DepsMut,
+    env: Env,
+    commit: execute::commit_result::Execute,
+    vrf_proof: Option<&[u8]>,
+) -> Result<Response, ContractError> {
+    let context = CommitContext::load(deps.storage, &commit.public_key)?;
+    let event = store_commit(deps, &env, &context, commit, vrf_proof)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "commit_data_result")
+        .add_event(event))
+}
+
+/// The state the commits of an executor are verified against, whatever their data request.
+/// Loading it once lets a batch verify several commits without reloading it for each of them.
+pub struct CommitContext {
+    public_key:          PublicKey,
+    staker:              Option<Staker>,
+    staking_config:      StakingConfig,
+    committee_selection: CommitteeSelection,
+    chain_id:            String,
+}
+
+impl CommitContext {
+    pub fn load(store: &dyn Storage, public_key: &str) -> Result<Self, ContractError> {
+        let public_key = PublicKey::from_hex_str(public_key)?;
+        Ok(CommitContext {
+            staker: STAKERS.may_get_staker(store, &public_key)?,
+            public_key,
+            staking_config: STAKING_CONFIG.load(store)?,
+            committee_selection: EXTENDED_STAKING_CONFIG.load(store)?.committee_selection,
+            chain_id: CHAIN_ID.load(store)?,
+        })
+    }
+}
+
+/// Verifies and stores a commitment, returning its event.
+/// Nothing is written until every check passed, so a failed commit leaves no trace.
+pub(in crate::msgs::data_requests) fn store_commit(
+    deps: DepsMut,
+    env: &Env,
+    context: &CommitContext,
+    commit: execute::commit_result::Execute,
+    vrf_proof: Option<&[u8]>,
+) -> Result<Event, ContractError> {
+    // find the data request from the pool (if it exists, otherwise error)
+    let dr_id = Hash::from_hex_str(&commit.dr_id)?;
+    let dr = state::load_request(deps.storage, &dr_id)?;
+
+    verify_commit(deps.as_ref(), env, context, &commit, &dr, vrf_proof)?;
+
+    let commitment = Hash::from_hex_str(&commit.commitment)?;
+
+    // add the commitment to the data request
+    state::commit(
+        deps.storage,
+        env.block.height,
+        dr_id,
+        dr.replication_factor,
+        &commit.public_key,
+        &commitment,
+    )?;
+
+    Ok(Event::new("seda-commitment").add_attributes([
+        ("dr_id", commit.dr_id),
+        ("posted_dr_height", dr.height.to_string()),
+        ("commitment", commit.commitment),
+        ("executor", commit.public_key),
+        ("version", CONTRACT_VERSION.to_string()),
+    ]))
+}
+
+/// Checks the commit can be stored, `context` has to be loaded for the executor of the commit.
 pub fn verify_commit(
     deps: Deps,
     env: &Env,
+    context: &CommitContext,
     commit: &execute::commit_result::Execute,
     dr: &DataRequest,
+    vrf_proof: Option<&[u8]>,
 ) -> Result<(), ContractError> {
+    let dr_id = Hash::from_hex_str(&dr.id)?;
+
     // error if the user has already committed
-    if dr.has_committer(commit.public_key.as_str()) {
+    if state::load_commitment(deps.storage, &dr_id, commit.public_key.as_str())?.is_some() {
         return Err(ContractError::AlreadyCommitted);
     }
 
     // error if reveal stage has started (replication factor reached)
-    if dr.reveal_started() {
+    if state::reveal_started(deps.storage, &dr_id, dr.replication_factor)? {
         return Err(ContractError::RevealStarted);
     }
 
     // error if the data request has expired
-    let expires_at = state::get_dr_expiration_height(deps.storage, &Hash::from_hex_str(&dr.id)?)?;
+    let expires_at = state::get_dr_expiration_height(deps.storage, &dr_id)?;
     if expires_at <= env.block.height {
         return Err(ContractError::DataRequestExpired(expires_at, "commit"));
     }
 
-    let public_key = PublicKey::from_hex_str(commit.public_key.as_str())?;
+    let public_key = context.public_key.clone();
 
     // Check if the staker has enough funds staked to commit
-    let staker = STAKERS.get_staker(deps.storage, &public_key)?;
-    let minimum_stake = STAKING_CONFIG
-        .load(deps.storage)?
-        .minimum_stake_for_committee_eligibility;
+    let tokens_staked = match &context.staker {
+        Some(staker) => staker.tokens_staked,
+        // a missing staker fails the same way as loading it
+        None => STAKERS.get_staker(deps.storage, &public_key)?.tokens_staked,
+    };
+    let minimum_stake = context.staking_config.minimum_stake_for_committee_eligibility;
 
-    if staker.tokens_staked < minimum_stake {
-        return Err(ContractError::InsufficientFunds(minimum_stake, staker.tokens_staked));
+    if tokens_staked < minimum_stake {
+        return Err(ContractError::InsufficientFunds(minimum_stake, tokens_staked));
+    }
+
+    // error if the staker was dropped from the allowlist after staking
+    if context.staking_config.allowlist_enabled && !ALLOWLIST.has(deps.storage, &public_key) {
+        return Err(ContractError::NotOnAllowlist);
     }
 
     // verify the proof
-    let chain_id = CHAIN_ID.load(deps.storage)?;
-    commit.verify(public_key.as_ref(), &chain_id, env.contract.address.as_str(), dr.height)?;
+    commit.verify(
+        public_key.as_ref(),
+        &context.chain_id,
+        env.contract.address.as_str(),
+        dr.height,
+    )?;
+
+    // error if the executor was not selected for the committee
+    match context.committee_selection {
+        CommitteeSelection::Window => {}
+        CommitteeSelection::Vrf => {
+            // the executor has to prove they were selected
+            let vrf_proof = vrf_proof.ok_or(ContractError::MissingVrfProof)?;
+            if !is_eligible_for_dr(deps, env, dr_id, public_key, vrf_proof)? {
+                return Err(ContractError::NotInCommittee);
+            }
+        }
+        CommitteeSelection::StakeWeighted { .. } => {
+            if !is_eligible_for_dr(deps, env, dr_id, public_key, &[])? {
+                return Err(ContractError::NotInCommittee);
+            }
+        }
+    }
+
     Ok(())
 }
@@ -0,0 +1,133 @@
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use post_request::TimeoutOverrides;
+use state::{CW20_FEE_TOKENS, FAILED_CW20_PAYOUTS};
+
+use super::*;
+use crate::msgs::owner::state::OWNER;
+
+/// Handles the `Receive` hook of a CW20 contract, paying for the embedded message with the sent tokens.
+pub(in crate::msgs::data_requests) fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    receive: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // the hook is called by the CW20 contract itself, on behalf of the sender of the tokens
+    let Some(rate) = CW20_FEE_TOKENS.may_load(deps.storage, &info.sender)? else {
+        return Err(ContractError::Cw20FeeTokenNotAllowed(info.sender.into_string()));
+    };
+    let poster = deps.api.addr_validate(&receive.sender)?;
+
+    match from_json(&receive.msg)? {
+        Cw20HookMsg::PostDataRequest(post) => post_request::post_request_with_cw20(
+            deps,
+            env,
+            info.sender,
+            rate,
+            poster,
+            receive.amount,
+            post,
+            TimeoutOverrides::default(),
+        ),
+        Cw20HookMsg::PostDataRequestWithTimeouts {
+            post,
+            commit_timeout_in_blocks,
+            reveal_timeout_in_blocks,
+        } => post_request::post_request_with_cw20(
+            deps,
+            env,
+            info.sender,
+            rate,
+            poster,
+            receive.amount,
+            post,
+            TimeoutOverrides {
+                commit_timeout_in_blocks,
+                reveal_timeout_in_blocks,
+            },
+        ),
+    }
+}
+
+/// Accepts the tokens of a CW20 contract to pay for data requests, at `rate` tokens per unit of the native token.
+/// Only the owner can add them, adding them again updates their rate for new data requests.
+pub(in crate::msgs::data_requests) fn add_cw20_fee_token(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_contract: String,
+    rate: Decimal,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::NotOwner);
+    }
+
+    if rate.is_zero() {
+        return Err(ContractError::ZeroCw20FeeTokenRate);
+    }
+
+    let cw20_contract = deps.api.addr_validate(&cw20_contract)?;
+    CW20_FEE_TOKENS.save(deps.storage, &cw20_contract, &rate)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add-cw20-fee-token")
+        .add_event(create_cw20_fee_token_event("add", cw20_contract).add_attribute("rate", rate.to_string())))
+}
+
+/// Stops accepting the tokens of a CW20 contract for new data requests. Only the owner can remove them.
+/// Data requests already escrowed in these tokens are still paid out in them.
+pub(in crate::msgs::data_requests) fn remove_cw20_fee_token(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_contract: String,
+) -> Result<Response, ContractError> {
+    if info.sender != OWNER.load(deps.storage)? {
+        return Err(ContractError::NotOwner);
+    }
+
+    let cw20_contract = deps.api.addr_validate(&cw20_contract)?;
+    CW20_FEE_TOKENS.remove(deps.storage, &cw20_contract);
+
+    Ok(Response::new()
+        .add_attribute("action", "remove-cw20-fee-token")
+        .add_event(create_cw20_fee_token_event("remove", cw20_contract)))
+}
+
+/// Sends the sender all the tokens of a CW20 contract recorded as claimable by it
+/// after the CW20 contract rejected their payout on removal.
+pub(in crate::msgs::data_requests) fn claim_failed_cw20_payouts(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_contract: String,
+) -> Result<Response, ContractError> {
+    let cw20_contract = deps.api.addr_validate(&cw20_contract)?;
+    let Some(amount) = FAILED_CW20_PAYOUTS.may_load(deps.storage, (&cw20_contract, &info.sender))? else {
+        return Err(ContractError::NoFailedCw20Payouts(cw20_contract.into_string()));
+    };
+    FAILED_CW20_PAYOUTS.remove(deps.storage, (&cw20_contract, &info.sender));
+
+    let transfer = WasmMsg::Execute {
+        contract_addr: cw20_contract.to_string(),
+        msg:           to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount,
+        })?,
+        funds:         vec![],
+    };
+
+    Ok(Response::new()
+        .add_attribute("action", "claim-failed-cw20-payouts")
+        .add_message(transfer)
+        .add_event(
+            create_cw20_fee_token_event("claim", cw20_contract)
+                .add_attribute("claimant", info.sender.into_string())
+                .add_attribute("amount", amount.to_string()),
+        ))
+}
+
+fn create_cw20_fee_token_event(action: &str, cw20_contract: Addr) -> Event {
+    Event::new("seda-cw20-fee-token").add_attributes([
+        ("version", CONTRACT_VERSION.to_string()),
+        ("action", action.to_string()),
+        ("cw20_contract", cw20_contract.into_string()),
+    ])
+}
@@ -1,7 +1,10 @@
 use cosmwasm_std::Event;
 use seda_common::msgs::data_requests::TimeoutConfig;
 
-use super::CONTRACT_VERSION;
+use super::{
+    state::{DataResultConfig, ExtendedTimeoutConfig},
+    CONTRACT_VERSION,
+};
 
 pub fn create_timeout_config_event(config: TimeoutConfig) -> Event {
     Event::new("seda-timeout-config").add_attributes([
@@ -10,3 +13,21 @@ pub fn create_timeout_config_event(config: TimeoutConfig) -> Event {
         ("reveal_timeout_in_blocks", config.reveal_timeout_in_blocks.to_string()),
     ])
 }
+
+pub fn create_extended_timeout_config_event(config: ExtendedTimeoutConfig) -> Event {
+    Event::new("seda-extended-timeout-config").add_attributes([
+        ("version", CONTRACT_VERSION.to_string()),
+        ("max_expired_per_sweep", config.max_expired_per_sweep.to_string()),
+        ("min_timeout_in_blocks", config.min_timeout_in_blocks.to_string()),
+        ("max_timeout_in_blocks", config.max_timeout_in_blocks.to_string()),
+    ])
+}
+
+pub fn create_data_result_config_event(config: DataResultConfig) -> Event {
+    Event::new("seda-data-result-config").add_attributes([
+        ("version", CONTRACT_VERSION.to_string()),
+        ("retention_in_blocks", config.retention_in_blocks.to_string()),
+        ("max_pruned_per_sweep", config.max_pruned_per_sweep.to_string()),
+        ("max_callback_gas_limit", config.max_callback_gas_limit.to_string()),
+    ])
+}
@@ -1,13 +1,22 @@
+use cosmwasm_schema::cw_serde;
+use post_request::TimeoutOverrides;
+
 use super::{
     msgs::data_requests::execute::{self, ExecuteMsg},
     *,
 };
 use crate::state::PAUSED;
 
+pub(in crate::msgs::data_requests) mod batch;
+pub(in crate::msgs::data_requests) mod bump_fee;
+pub(in crate::msgs::data_requests) mod cancel_request;
 pub(in crate::msgs::data_requests) mod commit_result;
+pub(in crate::msgs::data_requests) mod cw20_fees;
 pub(crate) mod dr_events;
 pub(in crate::msgs::data_requests) mod post_request;
 pub(in crate::msgs::data_requests) mod reveal_result;
+pub(in crate::msgs::data_requests) mod set_data_result_config;
+pub(in crate::msgs::data_requests) mod set_extended_timeout_config;
 pub(in crate::msgs::data_requests) mod set_timeout_config;
 
 impl ExecuteHandler for ExecuteMsg {
@@ -27,3 +36,251 @@ impl ExecuteHandler for ExecuteMsg {
         }
     }
 }
+
+/// Data request execute messages specific to this contract.
+#[cw_serde]
+pub enum ContractExecuteMsg {
+    /// Commits a data result like `CommitDataResult`, along with the executor's VRF proof (hex encoded)
+    /// that they were selected for the committee of the data request.
+    CommitDataResultWithVrfProof {
+        dr_id:      String,
+        commitment: String,
+        public_key: String,
+        proof:      String,
+        vrf_proof:  String,
+    },
+    /// Posts a data request with its own commit and reveal timeouts,
+    /// clamped to the bounds of the extended timeout config.
+    /// The global timeout config is used for the ones left out.
+    PostDataRequestWithTimeouts {
+        post:                     execute::post_request::Execute,
+        commit_timeout_in_blocks: Option<u64>,
+        reveal_timeout_in_blocks: Option<u64>,
+    },
+    /// Posts a data request and registers a contract to send its final result to once it resolves.
+    /// The callback runs with at most `callback_gas_limit` gas, and its failure never blocks the removal.
+    /// The timeouts are chosen like in `PostDataRequestWithTimeouts`.
+    PostDataRequestWithCallback {
+        post:                     execute::post_request::Execute,
+        callback_contract:        String,
+        callback_gas_limit:       u64,
+        commit_timeout_in_blocks: Option<u64>,
+        reveal_timeout_in_blocks: Option<u64>,
+    },
+    /// Posts several data requests at once, all with the timeouts chosen like in `PostDataRequestWithTimeouts`.
+    /// The batch cannot be empty. The response data lists the payload of each posted request.
+    ///
+    /// The attached funds must cover what all of them require. Unlike a single post, which escrows all the
+    /// attached funds and refunds the rest when the request is removed, each request of the batch escrows
+    /// exactly what it requires and the surplus is refunded right away: there is no single request to hold it.
+    PostDataRequests {
+        posts:                    Vec<execute::post_request::Execute>,
+        commit_timeout_in_blocks: Option<u64>,
+        reveal_timeout_in_blocks: Option<u64>,
+    },
+    /// Cancels a data request that is still committing without any commits
+    /// and refunds its escrow to the poster. Only the poster can cancel.
+    CancelDataRequest { dr_id: String },
+    /// Raises the gas price and gas limits of a data request that is still committing,
+    /// adding the attached funds to its escrow. Only the poster can bump the fee.
+    /// The id and the arguments it is the hash of do not change, the bumped values are stored next to them.
+    BumpDataRequestFee {
+        dr_id:           String,
+        gas_price:       Uint128,
+        exec_gas_limit:  u64,
+        tally_gas_limit: u64,
+    },
+    /// Commits data results to several data requests as the executor with `public_key`.
+    /// Each commit succeeds or fails on its own, the response data lists the outcome of each.
+    BatchCommitDataResults {
+        public_key: String,
+        commits:    Vec<BatchCommit>,
+    },
+    /// Reveals data results of several data requests as the executor with `public_key`.
+    /// Each reveal succeeds or fails on its own, the response data lists the outcome of each.
+    BatchRevealDataResults {
+        public_key: String,
+        reveals:    Vec<BatchReveal>,
+    },
+    /// Sets the timeout parameters specific to this contract.
+    SetExtendedTimeoutConfig(state::ExtendedTimeoutConfig),
+    /// Sets the retention parameters of the data result archive.
+    SetDataResultConfig(state::DataResultConfig),
+    /// Accepts the tokens of a CW20 contract to pay for data requests, at `rate` tokens per unit of the native token.
+    /// Gas is priced in the native token, so the funds required and the distributions are converted at this rate.
+    /// Only the owner can add them, adding them again updates their rate for new data requests.
+    AddCw20FeeToken {
+        cw20_contract: String,
+        rate:          Decimal,
+    },
+    /// Stops accepting the tokens of a CW20 contract to pay for data requests.
+    /// Only the owner can remove them.
+    RemoveCw20FeeToken { cw20_contract: String },
+    /// Sends the sender the tokens of a CW20 contract that it rejected when paying them out on removal.
+    /// Tokens that failed to burn are claimed by the owner.
+    ClaimFailedCw20Payouts { cw20_contract: String },
+    /// Hook called by allowlisted CW20 contracts when tokens are sent to this contract.
+    /// The embedded message is a [`Cw20HookMsg`].
+    Receive(cw20::Cw20ReceiveMsg),
+}
+
+/// Message embedded in the `Send` of an allowlisted CW20 contract,
+/// paid for with the sent tokens.
+#[cw_serde]
+pub enum Cw20HookMsg {
+    PostDataRequest(execute::post_request::Execute),
+    /// Posts a data request with its own commit and reveal timeouts,
+    /// chosen like in [`ContractExecuteMsg::PostDataRequestWithTimeouts`].
+    PostDataRequestWithTimeouts {
+        post:                     execute::post_request::Execute,
+        commit_timeout_in_blocks: Option<u64>,
+        reveal_timeout_in_blocks: Option<u64>,
+    },
+}
+
+/// A commit of a [`ContractExecuteMsg::BatchCommitDataResults`],
+/// with the fields of a `CommitDataResult` other than the executor's public key.
+#[cw_serde]
+pub struct BatchCommit {
+    pub dr_id:      String,
+    pub commitment: String,
+    pub proof:      String,
+    /// Hex encoded VRF proof, required with VRF committee selection.
+    pub vrf_proof:  Option<String>,
+}
+
+/// A reveal of a [`ContractExecuteMsg::BatchRevealDataResults`],
+/// with the fields of a `RevealDataResult` other than the executor's public key.
+#[cw_serde]
+pub struct BatchReveal {
+    pub dr_id:       String,
+    pub reveal_body: RevealBody,
+    pub proof:       String,
+    pub stderr:      Vec<String>,
+    pub stdout:      Vec<String>,
+}
+
+/// Outcome of one item of a batch commit or reveal.
+#[cw_serde]
+pub struct BatchItemResult {
+    pub dr_id:      String,
+    pub success:    bool,
+    /// Code of the error the item failed with, see [`ContractError::code`].
+    pub error_code: Option<u32>,
+    pub error:      Option<String>,
+}
+
+impl ExecuteHandler for ContractExecuteMsg {
+    fn execute(self, deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        // setting the configs is an owner operation and should not be paused
+        if PAUSED.load(deps.storage)?
+            && !matches!(
+                self,
+                ContractExecuteMsg::SetExtendedTimeoutConfig(_)
+                    | ContractExecuteMsg::SetDataResultConfig(_)
+                    | ContractExecuteMsg::AddCw20FeeToken { .. }
+                    | ContractExecuteMsg::RemoveCw20FeeToken { .. }
+            )
+        {
+            return Err(ContractError::ContractPaused(
+                "data request execute messages".to_string(),
+            ));
+        }
+
+        match self {
+            ContractExecuteMsg::CommitDataResultWithVrfProof {
+                dr_id,
+                commitment,
+                public_key,
+                proof,
+                vrf_proof,
+            } => {
+                let commit = execute::commit_result::Execute {
+                    dr_id,
+                    commitment,
+                    public_key,
+                    proof,
+                };
+                let vrf_proof = hex::decode(vrf_proof)?;
+                commit_result::commit_result(deps, env, commit, Some(&vrf_proof))
+            }
+            ContractExecuteMsg::PostDataRequestWithTimeouts {
+                post,
+                commit_timeout_in_blocks,
+                reveal_timeout_in_blocks,
+            } => post_request::post_request(
+                deps,
+                env,
+                info,
+                post,
+                TimeoutOverrides {
+                    commit_timeout_in_blocks,
+                    reveal_timeout_in_blocks,
+                },
+            ),
+            ContractExecuteMsg::CancelDataRequest { dr_id } => cancel_request::cancel_request(deps, info, dr_id),
+            ContractExecuteMsg::BumpDataRequestFee {
+                dr_id,
+                gas_price,
+                exec_gas_limit,
+                tally_gas_limit,
+            } => bump_fee::bump_fee(deps, info, dr_id, gas_price, exec_gas_limit, tally_gas_limit),
+            ContractExecuteMsg::PostDataRequestWithCallback {
+                post,
+                callback_contract,
+                callback_gas_limit,
+                commit_timeout_in_blocks,
+                reveal_timeout_in_blocks,
+            } => post_request::post_request_with_callback(
+                deps,
+                env,
+                info,
+                post,
+                callback_contract,
+                callback_gas_limit,
+                TimeoutOverrides {
+                    commit_timeout_in_blocks,
+                    reveal_timeout_in_blocks,
+                },
+            ),
+            ContractExecuteMsg::PostDataRequests {
+                posts,
+                commit_timeout_in_blocks,
+                reveal_timeout_in_blocks,
+            } => post_request::post_requests(
+                deps,
+                env,
+                info,
+                posts,
+                TimeoutOverrides {
+                    commit_timeout_in_blocks,
+                    reveal_timeout_in_blocks,
+                },
+            ),
+            ContractExecuteMsg::BatchCommitDataResults { public_key, commits } => {
+                batch::batch_commit_results(deps, env, public_key, commits)
+            }
+            ContractExecuteMsg::BatchRevealDataResults { public_key, reveals } => {
+                batch::batch_reveal_results(deps, env, public_key, reveals)
+            }
+            ContractExecuteMsg::SetExtendedTimeoutConfig(msg) => ExecuteHandler::execute(msg, deps, env, info),
+            ContractExecuteMsg::SetDataResultConfig(msg) => ExecuteHandler::execute(msg, deps, env, info),
+            ContractExecuteMsg::AddCw20FeeToken { cw20_contract, rate } => {
+                cw20_fees::add_cw20_fee_token(deps, info, cw20_contract, rate)
+            }
+            ContractExecuteMsg::RemoveCw20FeeToken { cw20_contract } => {
+                cw20_fees::remove_cw20_fee_token(deps, info, cw20_contract)
+            }
+            ContractExecuteMsg::ClaimFailedCw20Payouts { cw20_contract } => {
+                cw20_fees::claim_failed_cw20_payouts(deps, info, cw20_contract)
+            }
+            ContractExecuteMsg::Receive(msg) => cw20_fees::receive_cw20(deps, env, info, msg),
+        }
+    }
+}
+
+impl From<ContractExecuteMsg> for crate::msgs::ExecuteMsg {
+    fn from(msg: ContractExecuteMsg) -> Self {
+        crate::msgs::ContractExecuteMsg::DataRequest(msg).into()
+    }
+}
@@ -1,5 +1,15 @@
-use staking::state::STAKERS;
-use state::{Escrow, DR_ESCROW};
+use cosmwasm_std::{coins, BankMsg};
+use staking::state::{stake_weighted::draw_committee, CommitteeSelection, EXTENDED_STAKING_CONFIG, STAKERS};
+use state::{
+    DataRequestTimeouts,
+    Escrow,
+    ResultCallback,
+    DATA_RESULT_CONFIG,
+    DR_COMMITTEES,
+    DR_RESULT_CALLBACKS,
+    EXTENDED_TIMEOUT_CONFIG,
+    TIMEOUT_CONFIG,
+};
 
 use super::*;
 use crate::{state::TOKEN, utils::get_attached_funds};
@@ -7,97 +17,359 @@ use crate::{state::TOKEN, utils::get_attached_funds};
 impl ExecuteHandler for execute::post_request::Execute {
     /// Posts a data request to the pool
     fn execute(self, deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
-        // require the replication to be non-zero
-        if self.posted_dr.replication_factor == 0 {
-            return Err(ContractError::DataRequestReplicationFactorZero);
-        }
-
-        // require the data request replication factor to be bigger than amount of stakers
-        let stakers_length = STAKERS.len(deps.storage)?;
-        if self.posted_dr.replication_factor as u32 > stakers_length {
-            return Err(ContractError::DataRequestReplicationFactorTooHigh(stakers_length));
-        }
-
-        // hash the inputs to get the data request id
-        let dr_id = self.posted_dr.try_hash()?;
-
-        // require the data request id to be unique
-        if state::data_request_exists(deps.as_ref(), dr_id) {
-            return Err(ContractError::DataRequestAlreadyExists);
-        }
-
-        // Take the funds from the user
-        let token = TOKEN.load(deps.storage)?;
-        let funds = cw_utils::must_pay(&info, &token)?;
-        let required = (Uint128::from(self.posted_dr.exec_gas_limit) + Uint128::from(self.posted_dr.tally_gas_limit))
-            .checked_mul(self.posted_dr.gas_price)?;
-        if funds < required {
-            return Err(ContractError::InsufficientFunds(
-                required,
-                get_attached_funds(&info.funds, &token)?,
-            ));
-        };
+        post_request(deps, env, info, self, TimeoutOverrides::default())
+    }
+}
+
+/// Commit and reveal timeouts chosen by the poster, the global ones are used for those left out.
+#[derive(Default)]
+pub(in crate::msgs::data_requests) struct TimeoutOverrides {
+    pub commit_timeout_in_blocks: Option<u64>,
+    pub reveal_timeout_in_blocks: Option<u64>,
+}
+
+/// Posts a data request to the pool, with the commit and reveal timeouts chosen by the poster if any.
+pub(in crate::msgs::data_requests) fn post_request(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    post: execute::post_request::Execute,
+    timeouts: TimeoutOverrides,
+) -> Result<Response, ContractError> {
+    post_request_paying_callback(deps, env, info, post, timeouts, 0)
+}
 
-        let dr_poster = info.sender.to_string();
-        DR_ESCROW.save(
-            deps.storage,
-            &dr_id,
-            &Escrow {
-                amount: funds,
-                poster: info.sender,
-            },
-        )?;
-
-        // TODO: verify the payback non seda address...
-        let hex_dr_id = dr_id.to_hex();
-        let res = Response::new()
-            .add_attribute("action", "post_data_request")
-            .set_data(to_json_binary(&PostRequestResponsePayload {
-                dr_id:  hex_dr_id.clone(),
-                height: env.block.height,
-            })?)
-            .add_event(Event::new("seda-data-request").add_attributes([
-                ("dr_id", hex_dr_id.clone()),
-                ("dr_poster", dr_poster),
-                ("exec_program_id", self.posted_dr.exec_program_id.clone()),
-                ("exec_inputs", self.posted_dr.exec_inputs.to_base64()),
-                ("exec_gas_limit", self.posted_dr.exec_gas_limit.to_string()),
-                ("tally_program_id", self.posted_dr.tally_program_id.clone()),
-                ("tally_inputs", self.posted_dr.tally_inputs.to_base64()),
-                ("tally_gas_limit", self.posted_dr.tally_gas_limit.to_string()),
-                ("replication_factor", self.posted_dr.replication_factor.to_string()),
-                ("consensus_filter", self.posted_dr.consensus_filter.to_base64()),
-                ("gas_price", self.posted_dr.gas_price.to_string()),
-                ("memo", self.posted_dr.memo.to_base64()),
-                ("seda_payload", self.seda_payload.to_base64()),
-                ("payback_address", self.payback_address.to_base64()),
-                ("version", self.posted_dr.version.to_string()),
-            ]));
-
-        // save the data request
-        let dr = DataRequest {
-            id:                 hex_dr_id,
-            version:            self.posted_dr.version,
-            exec_program_id:    self.posted_dr.exec_program_id,
-            exec_inputs:        self.posted_dr.exec_inputs,
-            exec_gas_limit:     self.posted_dr.exec_gas_limit,
-            tally_program_id:   self.posted_dr.tally_program_id,
-            tally_inputs:       self.posted_dr.tally_inputs,
-            tally_gas_limit:    self.posted_dr.tally_gas_limit,
-            replication_factor: self.posted_dr.replication_factor,
-            consensus_filter:   self.posted_dr.consensus_filter,
-            gas_price:          self.posted_dr.gas_price,
-            memo:               self.posted_dr.memo,
-
-            payback_address: self.payback_address,
-            seda_payload:    self.seda_payload,
-            commits:         Default::default(),
-            reveals:         Default::default(),
-
-            height: env.block.height,
+/// Posts a data request to the pool, escrowing the funds for `callback_gas_limit` result callback gas
+/// on top of its execution and tally gas.
+fn post_request_paying_callback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    post: execute::post_request::Execute,
+    timeouts: TimeoutOverrides,
+    callback_gas_limit: u64,
+) -> Result<Response, ContractError> {
+    let dr_id = validate_request(deps.as_ref(), &post.posted_dr)?;
+    let timeouts = resolve_timeouts(deps.storage, timeouts)?;
+
+    // Take the funds from the user
+    let token = TOKEN.load(deps.storage)?;
+    let funds = cw_utils::must_pay(&info, &token)?;
+    let required = required_funds(
+        post.posted_dr.exec_gas_limit,
+        post.posted_dr.tally_gas_limit,
+        post.posted_dr.gas_price,
+    )?
+    .checked_add(callback_funds(callback_gas_limit, post.posted_dr.gas_price)?)?;
+    if funds < required {
+        return Err(ContractError::InsufficientFunds(
+            required,
+            get_attached_funds(&info.funds, &token)?,
+        ));
+    };
+
+    let escrow = Escrow {
+        amount:        funds,
+        poster:        info.sender,
+        cw20_contract: None,
+        cw20_rate:     None,
+    };
+    let (payload, event) = store_request(deps.storage, &env, dr_id, escrow, post, &timeouts)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "post_data_request")
+        .set_data(to_json_binary(&payload)?)
+        .add_event(event))
+}
+
+/// Posts a data request to the pool, registering the contract its final result is sent to.
+/// The poster pays for the callback gas at the gas price of the data request, and it is burned once the callback is
+/// sent.
+pub(in crate::msgs::data_requests) fn post_request_with_callback(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    post: execute::post_request::Execute,
+    callback_contract: String,
+    callback_gas_limit: u64,
+    timeouts: TimeoutOverrides,
+) -> Result<Response, ContractError> {
+    let contract = deps.api.addr_validate(&callback_contract)?;
+    let dr_id = post.posted_dr.try_hash()?;
+
+    let max_callback_gas_limit = DATA_RESULT_CONFIG.load(deps.storage)?.max_callback_gas_limit;
+    if callback_gas_limit > max_callback_gas_limit {
+        return Err(ContractError::CallbackGasLimitTooHigh(
+            callback_gas_limit,
+            max_callback_gas_limit,
+        ));
+    }
+
+    let response = post_request_paying_callback(deps.branch(), env, info, post, timeouts, callback_gas_limit)?;
+    DR_RESULT_CALLBACKS.save(
+        deps.storage,
+        &dr_id,
+        &ResultCallback {
+            contract,
+            gas_limit: callback_gas_limit,
+        },
+    )?;
+
+    Ok(
+        response.add_event(Event::new("seda-result-callback-registered").add_attributes([
+            ("dr_id", dr_id.to_hex()),
+            ("callback_contract", callback_contract),
+            ("callback_gas_limit", callback_gas_limit.to_string()),
+            ("version", CONTRACT_VERSION.to_string()),
+        ])),
+    )
+}
+
+/// Posts several data requests to the pool, paid for by a single payment covering all of them.
+/// Each request escrows exactly the funds it requires, any surplus is sent back to the poster.
+/// The timeouts chosen by the poster apply to all of them.
+pub(in crate::msgs::data_requests) fn post_requests(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    posts: Vec<execute::post_request::Execute>,
+    timeouts: TimeoutOverrides,
+) -> Result<Response, ContractError> {
+    if posts.is_empty() {
+        return Err(ContractError::EmptyDataRequestBatch);
+    }
+    let timeouts = resolve_timeouts(deps.storage, timeouts)?;
+
+    // Take the funds for all the data requests from the user at once
+    let token = TOKEN.load(deps.storage)?;
+    let funds = cw_utils::must_pay(&info, &token)?;
+    let required = posts
+        .iter()
+        .map(|post| {
+            required_funds(
+                post.posted_dr.exec_gas_limit,
+                post.posted_dr.tally_gas_limit,
+                post.posted_dr.gas_price,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let total_required = required
+        .iter()
+        .try_fold(Uint128::zero(), |total, amount| total.checked_add(*amount))?;
+    if funds < total_required {
+        return Err(ContractError::InsufficientFunds(
+            total_required,
+            get_attached_funds(&info.funds, &token)?,
+        ));
+    };
+
+    let mut payloads = Vec::with_capacity(posts.len());
+    let mut events = Vec::with_capacity(posts.len());
+    for (post, required) in posts.into_iter().zip(required) {
+        // requests earlier in the batch are already stored, so duplicates within the batch are caught too
+        let dr_id = validate_request(deps.as_ref(), &post.posted_dr)?;
+        let escrow = Escrow {
+            amount:        required,
+            poster:        info.sender.clone(),
+            cw20_contract: None,
+            cw20_rate:     None,
         };
-        state::post_request(deps.storage, env.block.height, dr_id, dr)?;
+        let (payload, event) = store_request(deps.storage, &env, dr_id, escrow, post, &timeouts)?;
+        payloads.push(payload);
+        events.push(event);
+    }
 
-        Ok(res)
+    let mut response = Response::new()
+        .add_attribute("action", "post_data_requests")
+        .set_data(to_json_binary(&payloads)?)
+        .add_events(events);
+    let surplus = funds - total_required;
+    if !surplus.is_zero() {
+        response = response.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount:     coins(surplus.u128(), token),
+        });
     }
+
+    Ok(response)
+}
+
+/// Posts a data request to the pool, paid for with the CW20 tokens `poster` sent along with it.
+/// The gas price is in the native token, so the funds it requires are converted at `rate` CW20 tokens per native token.
+#[allow(clippy::too_many_arguments)]
+pub(in crate::msgs::data_requests) fn post_request_with_cw20(
+    deps: DepsMut,
+    env: Env,
+    cw20_contract: Addr,
+    rate: Decimal,
+    poster: Addr,
+    amount: Uint128,
+    post: execute::post_request::Execute,
+    timeouts: TimeoutOverrides,
+) -> Result<Response, ContractError> {
+    let dr_id = validate_request(deps.as_ref(), &post.posted_dr)?;
+    let timeouts = resolve_timeouts(deps.storage, timeouts)?;
+
+    let required = required_funds(
+        post.posted_dr.exec_gas_limit,
+        post.posted_dr.tally_gas_limit,
+        post.posted_dr.gas_price,
+    )?
+    .checked_mul_ceil(rate)
+    .map_err(|_| OverflowError::new(OverflowOperation::Mul))?;
+    if amount < required {
+        return Err(ContractError::InsufficientFunds(required, amount));
+    };
+
+    let escrow = Escrow {
+        amount,
+        poster,
+        cw20_contract: Some(cw20_contract.clone()),
+        cw20_rate: Some(rate),
+    };
+    let (payload, event) = store_request(deps.storage, &env, dr_id, escrow, post, &timeouts)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "post_data_request")
+        .add_attribute("cw20_contract", cw20_contract)
+        .set_data(to_json_binary(&payload)?)
+        .add_event(event))
+}
+
+/// Checks the data request can be posted, returning its id.
+fn validate_request(deps: Deps, posted_dr: &PostDataRequestArgs) -> Result<Hash, ContractError> {
+    // require the replication to be non-zero
+    if posted_dr.replication_factor == 0 {
+        return Err(ContractError::DataRequestReplicationFactorZero);
+    }
+
+    // require the data request replication factor to not exceed the amount of eligible executors
+    let eligible_length = STAKERS.eligible_len(deps.storage)?;
+    if posted_dr.replication_factor as u32 > eligible_length {
+        return Err(ContractError::DataRequestReplicationFactorTooHigh(eligible_length));
+    }
+
+    // hash the inputs to get the data request id
+    let dr_id = posted_dr.try_hash()?;
+
+    // require the data request id to be unique
+    if state::data_request_exists(deps, dr_id) {
+        return Err(ContractError::DataRequestAlreadyExists);
+    }
+
+    Ok(dr_id)
+}
+
+/// Escrows the funds of a validated data request and saves it to the pool,
+/// returning the response payload and event for it.
+fn store_request(
+    store: &mut dyn Storage,
+    env: &Env,
+    dr_id: Hash,
+    escrow: Escrow,
+    post: execute::post_request::Execute,
+    timeouts: &DataRequestTimeouts,
+) -> Result<(PostRequestResponsePayload, Event), ContractError> {
+    let dr_poster = escrow.poster.to_string();
+    state::save_escrow(store, &dr_id, &escrow)?;
+
+    // TODO: verify the payback non seda address...
+    let hex_dr_id = dr_id.to_hex();
+    let payload = PostRequestResponsePayload {
+        dr_id:  hex_dr_id.clone(),
+        height: env.block.height,
+    };
+    let event = Event::new("seda-data-request").add_attributes([
+        ("dr_id", hex_dr_id.clone()),
+        ("dr_poster", dr_poster),
+        ("exec_program_id", post.posted_dr.exec_program_id.clone()),
+        ("exec_inputs", post.posted_dr.exec_inputs.to_base64()),
+        ("exec_gas_limit", post.posted_dr.exec_gas_limit.to_string()),
+        ("tally_program_id", post.posted_dr.tally_program_id.clone()),
+        ("tally_inputs", post.posted_dr.tally_inputs.to_base64()),
+        ("tally_gas_limit", post.posted_dr.tally_gas_limit.to_string()),
+        ("replication_factor", post.posted_dr.replication_factor.to_string()),
+        ("consensus_filter", post.posted_dr.consensus_filter.to_base64()),
+        ("gas_price", post.posted_dr.gas_price.to_string()),
+        ("memo", post.posted_dr.memo.to_base64()),
+        ("seda_payload", post.seda_payload.to_base64()),
+        ("payback_address", post.payback_address.to_base64()),
+        (
+            "commit_timeout_in_blocks",
+            timeouts.commit_timeout_in_blocks.to_string(),
+        ),
+        (
+            "reveal_timeout_in_blocks",
+            timeouts.reveal_timeout_in_blocks.to_string(),
+        ),
+        ("version", post.posted_dr.version.to_string()),
+    ]);
+
+    // save the data request
+    let dr = DataRequest {
+        id:                 hex_dr_id,
+        version:            post.posted_dr.version,
+        exec_program_id:    post.posted_dr.exec_program_id,
+        exec_inputs:        post.posted_dr.exec_inputs,
+        exec_gas_limit:     post.posted_dr.exec_gas_limit,
+        tally_program_id:   post.posted_dr.tally_program_id,
+        tally_inputs:       post.posted_dr.tally_inputs,
+        tally_gas_limit:    post.posted_dr.tally_gas_limit,
+        replication_factor: post.posted_dr.replication_factor,
+        consensus_filter:   post.posted_dr.consensus_filter,
+        gas_price:          post.posted_dr.gas_price,
+        memo:               post.posted_dr.memo,
+
+        payback_address: post.payback_address,
+        seda_payload:    post.seda_payload,
+        commits:         Default::default(),
+        reveals:         Default::default(),
+
+        height: env.block.height,
+    };
+    let replication_factor = dr.replication_factor;
+    state::post_request(store, env.block.height, dr_id, dr, timeouts)?;
+
+    if let CommitteeSelection::StakeWeighted { stake_cap } = EXTENDED_STAKING_CONFIG.load(store)?.committee_selection {
+        let committee = draw_committee(store, &dr_id, replication_factor, stake_cap)?;
+        DR_COMMITTEES.save(store, &dr_id, &committee)?;
+    }
+
+    Ok((payload, event))
+}
+
+/// Uses the global [`TimeoutConfig`] for the timeouts not chosen by the poster,
+/// and clamps the chosen ones to the governance bounds.
+fn resolve_timeouts(store: &dyn Storage, overrides: TimeoutOverrides) -> Result<DataRequestTimeouts, ContractError> {
+    let timeout_config = TIMEOUT_CONFIG.load(store)?;
+    let bounds = EXTENDED_TIMEOUT_CONFIG.load(store)?;
+    let clamp = |timeout: u64| timeout.clamp(bounds.min_timeout_in_blocks, bounds.max_timeout_in_blocks);
+
+    Ok(DataRequestTimeouts {
+        commit_timeout_in_blocks: overrides
+            .commit_timeout_in_blocks
+            .map(clamp)
+            .unwrap_or(timeout_config.commit_timeout_in_blocks),
+        reveal_timeout_in_blocks: overrides
+            .reveal_timeout_in_blocks
+            .map(clamp)
+            .unwrap_or(timeout_config.reveal_timeout_in_blocks),
+    })
+}
+
+/// The funds a data request needs escrowed to pay for its execution and tally gas.
+pub(in crate::msgs::data_requests) fn required_funds(
+    exec_gas_limit: u64,
+    tally_gas_limit: u64,
+    gas_price: Uint128,
+) -> Result<Uint128, ContractError> {
+    Ok((Uint128::from(exec_gas_limit) + Uint128::from(tally_gas_limit)).checked_mul(gas_price)?)
+}
+
+/// The funds a data request needs escrowed to pay for the gas of its result callback.
+pub(in crate::msgs::data_requests) fn callback_funds(
+    callback_gas_limit: u64,
+    gas_price: Uint128,
+) -> Result<Uint128, ContractError> {
+    Ok(Uint128::from(callback_gas_limit).checked_mul(gas_price)?)
 }
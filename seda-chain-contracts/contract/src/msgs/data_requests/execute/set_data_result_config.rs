@@ -0,0 +1,35 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+
+use super::{
+    dr_events::create_data_result_config_event,
+    owner::state::OWNER,
+    state::{DataResultConfig, DATA_RESULT_CONFIG},
+    ContractError,
+    ContractExecuteMsg,
+    ExecuteHandler,
+};
+
+impl ExecuteHandler for DataResultConfig {
+    /// Set data result config
+    fn execute(self, deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        if info.sender != OWNER.load(deps.storage)? {
+            return Err(ContractError::NotOwner);
+        }
+
+        if self.max_pruned_per_sweep == 0 {
+            return Err(ContractError::ZeroMaxPrunedPerSweep);
+        }
+
+        DATA_RESULT_CONFIG.save(deps.storage, &self)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set-data-result-config")
+            .add_event(create_data_result_config_event(self)))
+    }
+}
+
+impl From<DataResultConfig> for crate::msgs::ExecuteMsg {
+    fn from(config: DataResultConfig) -> Self {
+        ContractExecuteMsg::SetDataResultConfig(config).into()
+    }
+}
@@ -0,0 +1,42 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+
+use super::{
+    dr_events::create_extended_timeout_config_event,
+    owner::state::OWNER,
+    state::{ExtendedTimeoutConfig, EXTENDED_TIMEOUT_CONFIG},
+    ContractError,
+    ContractExecuteMsg,
+    ExecuteHandler,
+};
+
+impl ExecuteHandler for ExtendedTimeoutConfig {
+    /// Set extended timeout config
+    fn execute(self, deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        if info.sender != OWNER.load(deps.storage)? {
+            return Err(ContractError::NotOwner);
+        }
+
+        if self.max_expired_per_sweep == 0 {
+            return Err(ContractError::ZeroMaxExpiredPerSweep);
+        }
+
+        if self.min_timeout_in_blocks == 0 || self.min_timeout_in_blocks > self.max_timeout_in_blocks {
+            return Err(ContractError::InvalidTimeoutBounds(
+                self.min_timeout_in_blocks,
+                self.max_timeout_in_blocks,
+            ));
+        }
+
+        EXTENDED_TIMEOUT_CONFIG.save(deps.storage, &self)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set-extended-timeout-config")
+            .add_event(create_extended_timeout_config_event(self)))
+    }
+}
+
+impl From<ExtendedTimeoutConfig> for crate::msgs::ExecuteMsg {
+    fn from(config: ExtendedTimeoutConfig) -> Self {
+        ContractExecuteMsg::SetExtendedTimeoutConfig(config).into()
+    }
+}
@@ -1,4 +1,12 @@
-use execute::commit_result::verify_commit;
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use execute::commit_result::{verify_commit, CommitContext};
+use staking::state::{
+    is_eligible_for_dr::is_eligible_for_dr,
+    CommitteeSelection,
+    EXTENDED_STAKING_CONFIG,
+    STAKERS,
+    STAKING_CONFIG,
+};
 
 use super::{
     msgs::data_requests::{execute::commit_result, query::QueryMsg},
@@ -17,41 +25,46 @@ impl QueryHandler for QueryMsg {
                 commitment,
                 proof,
             } => {
-                let dr = state::may_load_request(deps.storage, &Hash::from_hex_str(&dr_id)?)?;
-                let valid = dr.is_some_and(|dr| {
-                    let commit_msg = commit_result::Execute {
-                        dr_id,
-                        commitment,
-                        public_key,
-                        proof,
-                    };
-                    verify_commit(deps, &env, &commit_msg, &dr).is_ok()
-                });
-                to_json_binary(&valid)?
+                let commit = commit_result::Execute {
+                    dr_id,
+                    commitment,
+                    public_key,
+                    proof,
+                };
+                to_json_binary(&can_executor_commit(deps, &env, commit, None)?)?
             }
             QueryMsg::CanExecutorReveal { dr_id, public_key } => {
-                let dr = state::may_load_request(deps.storage, &Hash::from_hex_str(&dr_id)?)?;
-                let can_reveal = dr.map(|dr| dr.reveal_started() && dr.get_commitment(&public_key).is_some());
-                to_json_binary(&can_reveal.unwrap_or(false))?
-            }
-            QueryMsg::GetDataRequest { dr_id } => {
-                to_json_binary(&state::may_load_request(deps.storage, &Hash::from_hex_str(&dr_id)?)?)?
-            }
-            QueryMsg::GetDataRequestCommitment { dr_id, public_key } => {
-                let dr = state::may_load_request(deps.storage, &Hash::from_hex_str(&dr_id)?)?;
-                to_json_binary(&dr.as_ref().map(|dr| dr.get_commitment(&public_key)))?
+                let dr_id = Hash::from_hex_str(&dr_id)?;
+                let can_reveal = match state::may_load_request(deps.storage, &dr_id)? {
+                    Some(dr) => {
+                        state::reveal_started(deps.storage, &dr_id, dr.replication_factor)?
+                            && state::load_commitment(deps.storage, &dr_id, &public_key)?.is_some()
+                    }
+                    None => false,
+                };
+                to_json_binary(&can_reveal)?
             }
+            QueryMsg::GetDataRequest { dr_id } => to_json_binary(&state::may_load_request_with_results(
+                deps.storage,
+                &Hash::from_hex_str(&dr_id)?,
+            )?)?,
+            QueryMsg::GetDataRequestCommitment { dr_id, public_key } => to_json_binary(&state::load_commitment(
+                deps.storage,
+                &Hash::from_hex_str(&dr_id)?,
+                &public_key,
+            )?)?,
             QueryMsg::GetDataRequestCommitments { dr_id } => {
-                let dr = state::may_load_request(deps.storage, &Hash::from_hex_str(&dr_id)?)?;
+                let dr = state::may_load_request_with_results(deps.storage, &Hash::from_hex_str(&dr_id)?)?;
                 let commitments = dr.map(|dr| dr.commits).unwrap_or_default();
                 to_json_binary(&commitments)?
             }
-            QueryMsg::GetDataRequestReveal { dr_id, public_key } => {
-                let dr = state::may_load_request(deps.storage, &Hash::from_hex_str(&dr_id)?)?;
-                to_json_binary(&dr.as_ref().map(|dr| dr.get_reveal(&public_key)))?
-            }
+            QueryMsg::GetDataRequestReveal { dr_id, public_key } => to_json_binary(&state::load_reveal(
+                deps.storage,
+                &Hash::from_hex_str(&dr_id)?,
+                &public_key,
+            )?)?,
             QueryMsg::GetDataRequestReveals { dr_id } => {
-                let dr = state::may_load_request(deps.storage, &Hash::from_hex_str(&dr_id)?)?;
+                let dr = state::may_load_request_with_results(deps.storage, &Hash::from_hex_str(&dr_id)?)?;
                 let reveals = dr.map(|dr| dr.reveals).unwrap_or_default();
                 to_json_binary(&reveals)?
             }
@@ -67,3 +80,676 @@ impl QueryHandler for QueryMsg {
         Ok(binary)
     }
 }
+
+/// Data request query messages specific to this contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum ContractQueryMsg {
+    /// Pages through the data requests of a status ordered by posting height then id.
+    /// Pass the `next_cursor` of the previous page to resume after it.
+    #[returns(GetDataRequestsByStatusCursorResponse)]
+    GetDataRequestsByStatusCursor {
+        status: DataRequestStatus,
+        cursor: Option<String>,
+        limit:  u32,
+    },
+    /// Like `GetDataRequestsByStatusCursor`, but only returns a summary of each data request.
+    #[returns(GetDataRequestSummariesResponse)]
+    GetDataRequestSummariesByStatus {
+        status: DataRequestStatus,
+        cursor: Option<String>,
+        limit:  u32,
+    },
+    #[returns(state::ExtendedTimeoutConfig)]
+    GetExtendedTimeoutConfig {},
+    #[returns(state::DataResultConfig)]
+    GetDataResultConfig {},
+    /// Returns the CW20 contracts whose tokens are accepted to pay for data requests, with their rates.
+    #[returns(Vec<Cw20FeeToken>)]
+    GetCw20FeeTokens {},
+    /// Returns the tokens of a CW20 contract that `claimant` can claim after their payout failed.
+    #[returns(Uint128)]
+    GetFailedCw20Payouts {
+        cw20_contract: String,
+        claimant:      String,
+    },
+    /// Returns the final result of a removed data request, if it is still within the retention window.
+    #[returns(Option<state::DataResult>)]
+    GetDataResult { dr_id: String },
+    /// Returns a data request like `GetDataRequest`, along with its timeouts.
+    /// `GetDataRequest` answers with the `seda-common` data request, which has no room for them.
+    #[returns(Option<DataRequestWithTimeouts>)]
+    GetDataRequestWithTimeouts { dr_id: String },
+    /// Returns where a data request stands in its lifecycle and when its current stage times out.
+    #[returns(Option<DataRequestTimeline>)]
+    GetDataRequestTimeline { dr_id: String },
+    /// Lists up to `limit` data requests whose current stage times out
+    /// from `from_height` to `to_height` inclusive, ordered by timeout height.
+    #[returns(Vec<ExpiringDataRequest>)]
+    GetDataRequestsExpiringBetween {
+        from_height: u64,
+        to_height:   u64,
+        limit:       u32,
+    },
+    /// Pages through the pending data requests of an exec or tally program, ordered by id.
+    ///
+    /// With a `status` only the requests of that status are returned out of each page of `limit` requests,
+    /// so a page can hold fewer requests than `limit` while `next_start_after` is still set.
+    #[returns(GetDataRequestsByProgramResponse)]
+    GetDataRequestsByProgram {
+        kind:        state::ProgramKind,
+        program_id:  String,
+        status:      Option<DataRequestStatus>,
+        start_after: Option<String>,
+        limit:       u32,
+    },
+    /// Returns the number of pending data requests of an exec or tally program.
+    #[returns(u32)]
+    GetDataRequestCountByProgram {
+        kind:       state::ProgramKind,
+        program_id: String,
+    },
+    /// Pages through the data requests of a poster that are still pending, ordered by id.
+    /// Index entries whose data request is gone are left out, so a page may hold fewer than `limit` of them.
+    #[returns(Vec<PosterDataRequest>)]
+    GetDataRequestsByPoster {
+        poster:      String,
+        start_after: Option<String>,
+        limit:       u32,
+    },
+    /// Like `CanExecutorCommit`, for a `CommitDataResultWithVrfProof` carrying
+    /// the executor's VRF proof (hex encoded) under VRF committee selection.
+    #[returns(bool)]
+    CanExecutorCommitWithVrfProof {
+        dr_id:      String,
+        public_key: String,
+        commitment: String,
+        proof:      String,
+        vrf_proof:  String,
+    },
+    /// Like `CanExecutorCommit`, but explains why the executor cannot commit.
+    /// The VRF proof (hex encoded) is only needed under VRF committee selection.
+    #[returns(ExecutorActionCheck)]
+    ExplainCanExecutorCommit {
+        dr_id:      String,
+        public_key: String,
+        commitment: String,
+        proof:      String,
+        vrf_proof:  Option<String>,
+    },
+    /// Like `CanExecutorReveal`, but explains why the executor cannot reveal.
+    #[returns(ExecutorActionCheck)]
+    ExplainCanExecutorReveal { dr_id: String, public_key: String },
+    /// Lists the data requests an executor has to act on.
+    ///
+    /// Each list pages through at most `limit` requests of its status per call,
+    /// so a page can hold fewer items than `limit` while its cursor is still set.
+    #[returns(ExecutorWorkQueue)]
+    GetExecutorWorkQueue {
+        public_key:        String,
+        committing_cursor: Option<String>,
+        revealing_cursor:  Option<String>,
+        limit:             u32,
+    },
+}
+
+#[cw_serde]
+pub struct GetDataRequestsByProgramResponse {
+    pub data_requests:    Vec<DataRequest>,
+    /// Id to pass as `start_after` to fetch the next page, `None` once there are no more requests.
+    pub next_start_after: Option<String>,
+}
+
+#[cw_serde]
+pub struct Cw20FeeToken {
+    pub cw20_contract: String,
+    /// Amount of the tokens worth one unit of the native token.
+    pub rate:          Decimal,
+}
+
+#[cw_serde]
+pub struct PosterDataRequest {
+    pub dr_id:         String,
+    pub status:        DataRequestStatus,
+    pub posted_height: u64,
+    /// Funds still held for the data request.
+    pub escrowed:      Uint128,
+    /// The CW20 contract of the escrowed tokens, or `None` when they are the native token.
+    pub cw20_contract: Option<String>,
+}
+
+#[cw_serde]
+pub struct ExecutorActionCheck {
+    pub allowed: bool,
+    /// Why the executor cannot act, `None` when it can.
+    pub reason:  Option<ExecutorRejection>,
+}
+
+impl From<Option<ExecutorRejection>> for ExecutorActionCheck {
+    fn from(reason: Option<ExecutorRejection>) -> Self {
+        ExecutorActionCheck {
+            allowed: reason.is_none(),
+            reason,
+        }
+    }
+}
+
+/// Why an executor cannot commit to or reveal for a data request.
+#[cw_serde]
+pub enum ExecutorRejection {
+    NotFound,
+    AlreadyCommitted,
+    RevealStarted,
+    RevealNotStarted,
+    /// The current stage timed out at this height.
+    Expired {
+        height: u64,
+    },
+    /// The executor is not a staker or has less than the minimum stake for committee eligibility.
+    InsufficientStake {
+        required: Uint128,
+        actual:   Uint128,
+    },
+    /// The proof does not verify for the commit.
+    InvalidProof,
+    /// The commitment or the proof is not valid hex, so the proof could not be checked.
+    InvalidInput,
+    /// VRF committee selection is enabled but no VRF proof was given.
+    MissingVrfProof,
+    /// The VRF proof is not the executor's proof for the data request.
+    InvalidVrfProof,
+    NotOnAllowlist,
+    NotInCommittee,
+    NotCommitted,
+    AlreadyRevealed,
+}
+
+#[cw_serde]
+pub struct ExecutorWorkQueue {
+    /// Committing requests the executor is eligible for and has not committed to, before their deadline.
+    ///
+    /// With VRF committee selection only the executor can prove it was selected,
+    /// so every such request is listed as long as the executor is eligible for committees.
+    pub committing:             Vec<WorkQueueItem>,
+    /// Revealing requests the executor committed to and has not revealed yet, before their deadline.
+    pub revealing:              Vec<WorkQueueItem>,
+    /// Cursor to fetch the next page of committing requests, `None` once there are no more.
+    pub next_committing_cursor: Option<String>,
+    /// Cursor to fetch the next page of revealing requests, `None` once there are no more.
+    pub next_revealing_cursor:  Option<String>,
+}
+
+#[cw_serde]
+pub struct WorkQueueItem {
+    pub dr_id:    String,
+    /// Height at which the current stage times out, the commit or reveal has to land below it.
+    pub deadline: u64,
+}
+
+#[cw_serde]
+pub struct DataRequestTimeline {
+    pub status:           DataRequestStatus,
+    pub posted_height:    u64,
+    /// Height at which the current stage times out, `None` once the request is tallying.
+    /// Commits and reveals only land below this height.
+    pub stage_deadline:   Option<u64>,
+    /// Blocks left between the current height and the stage deadline.
+    pub blocks_remaining: Option<u64>,
+}
+
+#[cw_serde]
+pub struct ExpiringDataRequest {
+    pub dr_id:             String,
+    pub expiration_height: u64,
+}
+
+#[cw_serde]
+pub struct DataRequestWithTimeouts {
+    pub data_request:             DataRequest,
+    pub commit_timeout_in_blocks: u64,
+    pub reveal_timeout_in_blocks: u64,
+    /// Height at which the current stage times out, `None` once the request is tallying.
+    pub expiration_height:        Option<u64>,
+}
+
+/// The fields of a data request needed to decide whether to act on it,
+/// without its inputs, payload and results.
+#[cw_serde]
+pub struct DataRequestSummary {
+    pub dr_id:              String,
+    pub height:             u64,
+    pub status:             DataRequestStatus,
+    pub replication_factor: u16,
+    pub commits:            u16,
+    pub reveals:            u16,
+    pub gas_price:          Uint128,
+    /// Height at which the current stage times out, `None` once the request is tallying.
+    pub expiration_height:  Option<u64>,
+}
+
+#[cw_serde]
+pub struct GetDataRequestSummariesResponse {
+    pub is_paused:   bool,
+    pub summaries:   Vec<DataRequestSummary>,
+    /// Cursor to fetch the next page, `None` once there are no more requests.
+    pub next_cursor: Option<String>,
+}
+
+#[cw_serde]
+pub struct GetDataRequestsByStatusCursorResponse {
+    pub is_paused:     bool,
+    pub data_requests: Vec<DataRequest>,
+    /// Cursor to fetch the next page, `None` once there are no more requests.
+    pub next_cursor:   Option<String>,
+}
+
+fn encode_cursor((height, dr_id): state::StatusCursor) -> String {
+    hex::encode([height.to_be_bytes().as_slice(), dr_id.as_slice()].concat())
+}
+
+fn decode_cursor(cursor: &str) -> Result<state::StatusCursor, ContractError> {
+    let bytes = hex::decode(cursor).map_err(|_| ContractError::InvalidCursor(cursor.to_string()))?;
+    if bytes.len() != 40 {
+        return Err(ContractError::InvalidCursor(cursor.to_string()));
+    }
+
+    let (height, dr_id) = bytes.split_at(8);
+    Ok((
+        u64::from_be_bytes(height.try_into().expect("split at 8 bytes")),
+        dr_id.try_into().expect("remaining 32 bytes"),
+    ))
+}
+
+fn can_executor_commit(
+    deps: Deps,
+    env: &Env,
+    commit: commit_result::Execute,
+    vrf_proof: Option<&[u8]>,
+) -> Result<bool, ContractError> {
+    let dr = state::may_load_request(deps.storage, &Hash::from_hex_str(&commit.dr_id)?)?;
+    let Ok(context) = CommitContext::load(deps.storage, &commit.public_key) else {
+        return Ok(false);
+    };
+    Ok(dr.is_some_and(|dr| verify_commit(deps, env, &context, &commit, &dr, vrf_proof).is_ok()))
+}
+
+fn explain_commit(
+    deps: Deps,
+    env: &Env,
+    commit: commit_result::Execute,
+    vrf_proof: Option<&[u8]>,
+) -> Result<Option<ExecutorRejection>, ContractError> {
+    let Some(dr) = state::may_load_request(deps.storage, &Hash::from_hex_str(&commit.dr_id)?)? else {
+        return Ok(Some(ExecutorRejection::NotFound));
+    };
+
+    let context = CommitContext::load(deps.storage, &commit.public_key)?;
+    let reason = match verify_commit(deps, env, &context, &commit, &dr, vrf_proof) {
+        Ok(()) => return Ok(None),
+        Err(ContractError::AlreadyCommitted) => ExecutorRejection::AlreadyCommitted,
+        Err(ContractError::RevealStarted) => ExecutorRejection::RevealStarted,
+        Err(ContractError::DataRequestExpired(height, _)) => ExecutorRejection::Expired { height },
+        Err(ContractError::InsufficientFunds(required, actual)) => {
+            ExecutorRejection::InsufficientStake { required, actual }
+        }
+        Err(ContractError::NotOnAllowlist) => ExecutorRejection::NotOnAllowlist,
+        Err(ContractError::NotInCommittee) => ExecutorRejection::NotInCommittee,
+        // the proof check also fails on fields it cannot decode, which are not a proof that does not verify
+        Err(ContractError::Common(_))
+            if hex::decode(&commit.proof).is_err() || Hash::from_hex_str(&commit.commitment).is_err() =>
+        {
+            ExecutorRejection::InvalidInput
+        }
+        Err(ContractError::Common(_)) => ExecutorRejection::InvalidProof,
+        Err(ContractError::MissingVrfProof) => ExecutorRejection::MissingVrfProof,
+        Err(ContractError::InvalidVrfProof) => ExecutorRejection::InvalidVrfProof,
+        Err(err) => {
+            // committing loads the staker before checking its stake
+            let public_key = PublicKey::from_hex_str(&commit.public_key)?;
+            if STAKERS.may_get_staker(deps.storage, &public_key)?.is_some() {
+                return Err(err);
+            }
+            ExecutorRejection::InsufficientStake {
+                required: STAKING_CONFIG
+                    .load(deps.storage)?
+                    .minimum_stake_for_committee_eligibility,
+                actual:   Uint128::zero(),
+            }
+        }
+    };
+
+    Ok(Some(reason))
+}
+
+fn explain_reveal(
+    deps: Deps,
+    env: &Env,
+    dr_id: &str,
+    public_key: &str,
+) -> Result<Option<ExecutorRejection>, ContractError> {
+    let dr_id = Hash::from_hex_str(dr_id)?;
+    let Some(dr) = state::may_load_request(deps.storage, &dr_id)? else {
+        return Ok(Some(ExecutorRejection::NotFound));
+    };
+
+    if !state::reveal_started(deps.storage, &dr_id, dr.replication_factor)? {
+        return Ok(Some(ExecutorRejection::RevealNotStarted));
+    }
+    if let Some(height) = state::may_get_dr_expiration_height(deps.storage, &dr_id)? {
+        if height <= env.block.height {
+            return Ok(Some(ExecutorRejection::Expired { height }));
+        }
+    }
+    if state::load_commitment(deps.storage, &dr_id, public_key)?.is_none() {
+        return Ok(Some(ExecutorRejection::NotCommitted));
+    }
+    if state::has_revealed(deps.storage, &dr_id, public_key) {
+        return Ok(Some(ExecutorRejection::AlreadyRevealed));
+    }
+
+    Ok(None)
+}
+
+/// Committing requests an executor is eligible for and has not committed to, out of a page of them.
+/// Requests past their commit deadline are left out even if they have not been expired yet.
+fn committing_work(
+    deps: Deps,
+    env: &Env,
+    public_key: &str,
+    cursor: Option<String>,
+    limit: u32,
+) -> Result<(Vec<WorkQueueItem>, Option<String>), ContractError> {
+    let after = cursor.as_deref().map(decode_cursor).transpose()?;
+    let (keys, next) = state::keys_by_status_after(deps.storage, &DataRequestStatus::Committing, after, limit)?;
+
+    let executor = PublicKey::from_hex_str(public_key)?;
+    if !STAKERS.is_staker_executor(deps.storage, &executor)? {
+        return Ok((vec![], next.map(encode_cursor)));
+    }
+    let committee_selection = EXTENDED_STAKING_CONFIG.load(deps.storage)?.committee_selection;
+
+    let mut items = Vec::new();
+    for (_, dr_id) in keys {
+        let deadline = state::get_dr_expiration_height(deps.storage, &dr_id)?;
+        if deadline <= env.block.height || state::load_commitment(deps.storage, &dr_id, public_key)?.is_some() {
+            continue;
+        }
+
+        let eligible = match committee_selection {
+            CommitteeSelection::Vrf => STAKERS.eligible_index(deps.storage, executor.clone())?.is_some(),
+            _ => is_eligible_for_dr(deps, env, dr_id, executor.clone(), &[])?,
+        };
+        if eligible {
+            items.push(WorkQueueItem {
+                dr_id: dr_id.to_hex(),
+                deadline,
+            });
+        }
+    }
+
+    Ok((items, next.map(encode_cursor)))
+}
+
+/// Revealing requests an executor committed to and has not revealed, out of a page of them.
+/// Requests past their reveal deadline are left out even if they have not been expired yet.
+fn revealing_work(
+    deps: Deps,
+    env: &Env,
+    public_key: &str,
+    cursor: Option<String>,
+    limit: u32,
+) -> Result<(Vec<WorkQueueItem>, Option<String>), ContractError> {
+    let after = cursor.as_deref().map(decode_cursor).transpose()?;
+    let (keys, next) = state::keys_by_status_after(deps.storage, &DataRequestStatus::Revealing, after, limit)?;
+
+    let mut items = Vec::new();
+    for (_, dr_id) in keys {
+        let deadline = state::get_dr_expiration_height(deps.storage, &dr_id)?;
+        if deadline > env.block.height
+            && state::load_commitment(deps.storage, &dr_id, public_key)?.is_some()
+            && !state::has_revealed(deps.storage, &dr_id, public_key)
+        {
+            items.push(WorkQueueItem {
+                dr_id: dr_id.to_hex(),
+                deadline,
+            });
+        }
+    }
+
+    Ok((items, next.map(encode_cursor)))
+}
+
+impl QueryHandler for ContractQueryMsg {
+    fn query(self, deps: Deps, env: Env) -> Result<Binary, ContractError> {
+        let binary = match self {
+            ContractQueryMsg::GetDataRequestsByStatusCursor { status, cursor, limit } => {
+                let after = cursor.as_deref().map(decode_cursor).transpose()?;
+                let (data_requests, next) = state::requests_by_status_after(deps.storage, &status, after, limit)?;
+                to_json_binary(&GetDataRequestsByStatusCursorResponse {
+                    is_paused: PAUSED.load(deps.storage)?,
+                    data_requests,
+                    next_cursor: next.map(encode_cursor),
+                })?
+            }
+            ContractQueryMsg::GetDataRequestSummariesByStatus { status, cursor, limit } => {
+                let after = cursor.as_deref().map(decode_cursor).transpose()?;
+                let (keys, next) = state::keys_by_status_after(deps.storage, &status, after, limit)?;
+                let summaries = keys
+                    .into_iter()
+                    .map(|(height, dr_id)| {
+                        let dr = state::load_request(deps.storage, &dr_id)?;
+                        Ok(DataRequestSummary {
+                            dr_id: dr.id,
+                            height,
+                            status: status.clone(),
+                            replication_factor: dr.replication_factor,
+                            commits: state::commit_count(deps.storage, &dr_id)?,
+                            reveals: state::reveal_count(deps.storage, &dr_id)?,
+                            gas_price: dr.gas_price,
+                            expiration_height: state::may_get_dr_expiration_height(deps.storage, &dr_id)?,
+                        })
+                    })
+                    .collect::<StdResult<Vec<_>>>()?;
+                to_json_binary(&GetDataRequestSummariesResponse {
+                    is_paused: PAUSED.load(deps.storage)?,
+                    summaries,
+                    next_cursor: next.map(encode_cursor),
+                })?
+            }
+            ContractQueryMsg::GetExtendedTimeoutConfig {} => {
+                to_json_binary(&state::EXTENDED_TIMEOUT_CONFIG.load(deps.storage)?)?
+            }
+            ContractQueryMsg::GetDataResultConfig {} => to_json_binary(&state::DATA_RESULT_CONFIG.load(deps.storage)?)?,
+            ContractQueryMsg::GetCw20FeeTokens {} => {
+                let tokens = state::CW20_FEE_TOKENS
+                    .range(deps.storage, None, None, Order::Ascending)
+                    .map(|token| {
+                        token.map(|(cw20_contract, rate)| Cw20FeeToken {
+                            cw20_contract: cw20_contract.into_string(),
+                            rate,
+                        })
+                    })
+                    .collect::<StdResult<Vec<_>>>()?;
+                to_json_binary(&tokens)?
+            }
+            ContractQueryMsg::GetFailedCw20Payouts {
+                cw20_contract,
+                claimant,
+            } => {
+                let cw20_contract = deps.api.addr_validate(&cw20_contract)?;
+                let claimant = deps.api.addr_validate(&claimant)?;
+                to_json_binary(
+                    &state::FAILED_CW20_PAYOUTS
+                        .may_load(deps.storage, (&cw20_contract, &claimant))?
+                        .unwrap_or_default(),
+                )?
+            }
+            ContractQueryMsg::GetDataResult { dr_id } => {
+                let dr_id = Hash::from_hex_str(&dr_id)?;
+                to_json_binary(&state::DATA_RESULTS.may_load(deps.storage, &dr_id)?)?
+            }
+            ContractQueryMsg::GetDataRequestTimeline { dr_id } => {
+                let dr_id = Hash::from_hex_str(&dr_id)?;
+                let timeline = state::may_load_request(deps.storage, &dr_id)?
+                    .map(|dr| {
+                        let stage_deadline = state::may_get_dr_expiration_height(deps.storage, &dr_id)?;
+                        Ok::<_, ContractError>(DataRequestTimeline {
+                            status: state::load_status(deps.storage, dr_id)?,
+                            posted_height: dr.height,
+                            stage_deadline,
+                            blocks_remaining: stage_deadline.map(|deadline| deadline.saturating_sub(env.block.height)),
+                        })
+                    })
+                    .transpose()?;
+                to_json_binary(&timeline)?
+            }
+            ContractQueryMsg::GetDataRequestsExpiringBetween {
+                from_height,
+                to_height,
+                limit,
+            } => {
+                let expiring = state::expiring_between(deps.storage, from_height, to_height, limit)?
+                    .into_iter()
+                    .map(|(expiration_height, dr_id)| ExpiringDataRequest {
+                        dr_id: dr_id.to_hex(),
+                        expiration_height,
+                    })
+                    .collect::<Vec<_>>();
+                to_json_binary(&expiring)?
+            }
+            ContractQueryMsg::GetDataRequestsByProgram {
+                kind,
+                program_id,
+                status,
+                start_after,
+                limit,
+            } => {
+                let start_after = start_after.as_deref().map(Hash::from_hex_str).transpose()?;
+                let (keys, next) = state::requests_by_program(deps.storage, &kind, &program_id, start_after, limit)?;
+                let mut data_requests = Vec::with_capacity(keys.len());
+                for dr_id in keys {
+                    if let Some(status) = &status {
+                        if &state::load_status(deps.storage, dr_id)? != status {
+                            continue;
+                        }
+                    }
+                    if let Some(dr) = state::may_load_request_with_results(deps.storage, &dr_id)? {
+                        data_requests.push(dr);
+                    }
+                }
+                to_json_binary(&GetDataRequestsByProgramResponse {
+                    data_requests,
+                    next_start_after: next.map(|dr_id| dr_id.to_hex()),
+                })?
+            }
+            ContractQueryMsg::GetDataRequestCountByProgram { kind, program_id } => {
+                to_json_binary(&state::program_request_count(deps.storage, &kind, &program_id)?)?
+            }
+            ContractQueryMsg::GetDataRequestsByPoster {
+                poster,
+                start_after,
+                limit,
+            } => {
+                let poster = deps.api.addr_validate(&poster)?;
+                let start_after = start_after.as_deref().map(Hash::from_hex_str).transpose()?;
+                let data_requests = state::requests_by_poster(deps.storage, &poster, start_after, limit)?
+                    .into_iter()
+                    .map(|dr_id| {
+                        // an entry without its request or escrow is stale, it is left out rather than failing the page
+                        let (Some(dr), Some(escrow)) = (
+                            state::may_load_request(deps.storage, &dr_id)?,
+                            state::DR_ESCROW.may_load(deps.storage, &dr_id)?,
+                        ) else {
+                            return Ok(None);
+                        };
+                        Ok(Some(PosterDataRequest {
+                            dr_id:         dr_id.to_hex(),
+                            status:        state::load_status(deps.storage, dr_id)?,
+                            posted_height: dr.height,
+                            escrowed:      escrow.amount,
+                            cw20_contract: escrow.cw20_contract.map(Addr::into_string),
+                        }))
+                    })
+                    .filter_map(Result::transpose)
+                    .collect::<StdResult<Vec<_>>>()?;
+                to_json_binary(&data_requests)?
+            }
+            ContractQueryMsg::CanExecutorCommitWithVrfProof {
+                dr_id,
+                public_key,
+                commitment,
+                proof,
+                vrf_proof,
+            } => {
+                let commit = commit_result::Execute {
+                    dr_id,
+                    commitment,
+                    public_key,
+                    proof,
+                };
+                let vrf_proof = hex::decode(vrf_proof)?;
+                to_json_binary(&can_executor_commit(deps, &env, commit, Some(&vrf_proof))?)?
+            }
+            ContractQueryMsg::ExplainCanExecutorCommit {
+                dr_id,
+                public_key,
+                commitment,
+                proof,
+                vrf_proof,
+            } => {
+                let commit = commit_result::Execute {
+                    dr_id,
+                    commitment,
+                    public_key,
+                    proof,
+                };
+                let vrf_proof = vrf_proof.map(hex::decode).transpose()?;
+                let reason = explain_commit(deps, &env, commit, vrf_proof.as_deref())?;
+                to_json_binary(&ExecutorActionCheck::from(reason))?
+            }
+            ContractQueryMsg::ExplainCanExecutorReveal { dr_id, public_key } => to_json_binary(
+                &ExecutorActionCheck::from(explain_reveal(deps, &env, &dr_id, &public_key)?),
+            )?,
+            ContractQueryMsg::GetExecutorWorkQueue {
+                public_key,
+                committing_cursor,
+                revealing_cursor,
+                limit,
+            } => {
+                let (committing, next_committing_cursor) =
+                    committing_work(deps, &env, &public_key, committing_cursor, limit)?;
+                let (revealing, next_revealing_cursor) =
+                    revealing_work(deps, &env, &public_key, revealing_cursor, limit)?;
+                to_json_binary(&ExecutorWorkQueue {
+                    committing,
+                    revealing,
+                    next_committing_cursor,
+                    next_revealing_cursor,
+                })?
+            }
+            ContractQueryMsg::GetDataRequestWithTimeouts { dr_id } => {
+                let dr_id = Hash::from_hex_str(&dr_id)?;
+                let response = state::may_load_request_with_results(deps.storage, &dr_id)?
+                    .map(|data_request| {
+                        let timeouts = state::load_timeouts(deps.storage, &dr_id)?;
+                        Ok::<_, ContractError>(DataRequestWithTimeouts {
+                            data_request,
+                            commit_timeout_in_blocks: timeouts.commit_timeout_in_blocks,
+                            reveal_timeout_in_blocks: timeouts.reveal_timeout_in_blocks,
+                            expiration_height: state::may_get_dr_expiration_height(deps.storage, &dr_id)?,
+                        })
+                    })
+                    .transpose()?;
+                to_json_binary(&response)?
+            }
+        };
+
+        Ok(binary)
+    }
+}
+
+impl From<ContractQueryMsg> for crate::msgs::QueryMsg {
+    fn from(msg: ContractQueryMsg) -> Self {
+        crate::msgs::ContractQueryMsg::DataRequest(msg).into()
+    }
+}
@@ -1,11 +1,40 @@
 use super::*;
 
 pub struct DataRequestsMap<'a> {
-    pub reqs:       Map<&'a Hash, DataRequest>,
-    pub committing: EnumerableSet<Hash>,
-    pub revealing:  EnumerableSet<Hash>,
-    pub tallying:   EnumerableSet<Hash>,
-    pub timeouts:   Timeouts<'a>,
+    pub reqs:              Map<&'a Hash, DataRequest>,
+    pub committing:        EnumerableSet<Hash>,
+    pub revealing:         EnumerableSet<Hash>,
+    pub tallying:          EnumerableSet<Hash>,
+    /// Requests of each status ordered by posting height then id,
+    /// so paging through them is stable when requests change status.
+    pub by_status:         Map<(u8, u64, &'a Hash), ()>,
+    pub timeouts:          Timeouts<'a>,
+    /// Commitments of each request by executor public key,
+    /// kept out of the request so committing does not rewrite all the previous commits.
+    pub commits:           Map<(&'a Hash, &'a str), Hash>,
+    /// Reveals of each request by executor public key.
+    pub reveals:           Map<(&'a Hash, &'a str), RevealBody>,
+    pub commits_len:       Map<&'a Hash, u16>,
+    pub reveals_len:       Map<&'a Hash, u16>,
+    /// Commit and reveal timeouts of each request.
+    pub stage_timeouts:    Map<&'a Hash, DataRequestTimeouts>,
+    /// Requests of each exec program ordered by id.
+    pub by_exec_program:   Map<(&'a str, &'a Hash), ()>,
+    /// Requests of each tally program ordered by id.
+    pub by_tally_program:  Map<(&'a str, &'a Hash), ()>,
+    pub exec_program_len:  Map<&'a str, u32>,
+    pub tally_program_len: Map<&'a str, u32>,
+}
+
+/// Position of a data request among the requests of its status.
+pub type StatusCursor = (u64, Hash);
+
+fn status_prefix(status: &DataRequestStatus) -> u8 {
+    match status {
+        DataRequestStatus::Committing => 0,
+        DataRequestStatus::Revealing => 1,
+        DataRequestStatus::Tallying => 2,
+    }
 }
 
 use cosmwasm_std::{StdResult, Storage};
@@ -23,22 +52,152 @@ impl DataRequestsMap<'_> {
         self.reqs.has(store, key)
     }
 
-    fn add_to_status(&self, store: &mut dyn Storage, key: Hash, status: &DataRequestStatus) -> StdResult<()> {
+    fn add_to_status(
+        &self,
+        store: &mut dyn Storage,
+        key: Hash,
+        status: &DataRequestStatus,
+        posted_height: u64,
+    ) -> StdResult<()> {
         match status {
             DataRequestStatus::Committing => self.committing.add(store, key)?,
             DataRequestStatus::Revealing => self.revealing.add(store, key)?,
             DataRequestStatus::Tallying => self.tallying.add(store, key)?,
         }
+        self.by_status
+            .save(store, (status_prefix(status), posted_height, &key), &())?;
 
         Ok(())
     }
 
-    fn remove_from_status(&self, store: &mut dyn Storage, key: Hash, status: &DataRequestStatus) -> StdResult<()> {
+    fn remove_from_status(
+        &self,
+        store: &mut dyn Storage,
+        key: Hash,
+        status: &DataRequestStatus,
+        posted_height: u64,
+    ) -> StdResult<()> {
         match status {
             DataRequestStatus::Committing => self.committing.remove(store, key)?,
             DataRequestStatus::Revealing => self.revealing.remove(store, key)?,
             DataRequestStatus::Tallying => self.tallying.remove(store, key)?,
         }
+        self.by_status
+            .remove(store, (status_prefix(status), posted_height, &key));
+
+        Ok(())
+    }
+
+    fn program_index(&self, kind: &ProgramKind) -> (&Map<(&str, &Hash), ()>, &Map<&str, u32>) {
+        match kind {
+            ProgramKind::Exec => (&self.by_exec_program, &self.exec_program_len),
+            ProgramKind::Tally => (&self.by_tally_program, &self.tally_program_len),
+        }
+    }
+
+    fn add_to_programs(&self, store: &mut dyn Storage, key: &Hash, req: &DataRequest) -> StdResult<()> {
+        for (kind, program_id) in [
+            (ProgramKind::Exec, &req.exec_program_id),
+            (ProgramKind::Tally, &req.tally_program_id),
+        ] {
+            let (index, len) = self.program_index(&kind);
+            index.save(store, (program_id.as_str(), key), &())?;
+            let count = len.may_load(store, program_id)?.unwrap_or_default();
+            len.save(store, program_id, &(count + 1))?;
+        }
+
+        Ok(())
+    }
+
+    fn remove_from_programs(&self, store: &mut dyn Storage, key: &Hash, req: &DataRequest) -> StdResult<()> {
+        for (kind, program_id) in [
+            (ProgramKind::Exec, &req.exec_program_id),
+            (ProgramKind::Tally, &req.tally_program_id),
+        ] {
+            let (index, len) = self.program_index(&kind);
+            index.remove(store, (program_id.as_str(), key));
+            match len.may_load(store, program_id)?.unwrap_or_default() {
+                0 | 1 => len.remove(store, program_id),
+                count => len.save(store, program_id, &(count - 1))?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds the program indexes from the requests.
+    pub fn backfill_program_index(&self, store: &mut dyn Storage) -> StdResult<()> {
+        let reqs = self
+            .reqs
+            .range(store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (key, req) in reqs {
+            if !self.by_exec_program.has(store, (req.exec_program_id.as_str(), &key)) {
+                self.add_to_programs(store, &key, &req)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the ids of up to `limit` requests of a program, ordered by id and starting after `start_after`.
+    /// Also returns the id to resume from if there are more requests.
+    pub fn get_keys_by_program(
+        &self,
+        store: &dyn Storage,
+        kind: &ProgramKind,
+        program_id: &str,
+        start_after: Option<Hash>,
+        limit: u32,
+    ) -> StdResult<(Vec<Hash>, Option<Hash>)> {
+        let (index, _) = self.program_index(kind);
+        let mut keys = index
+            .prefix(program_id)
+            .keys(
+                store,
+                start_after.as_ref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit as usize + 1)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        // only return a cursor if there is at least one more request after this page
+        let next = if keys.len() > limit as usize {
+            keys.truncate(limit as usize);
+            keys.last().copied()
+        } else {
+            None
+        };
+
+        Ok((keys, next))
+    }
+
+    /// Number of requests of a program.
+    pub fn program_len(&self, store: &dyn Storage, kind: &ProgramKind, program_id: &str) -> StdResult<u32> {
+        let (_, len) = self.program_index(kind);
+        Ok(len.may_load(store, program_id)?.unwrap_or_default())
+    }
+
+    /// Rebuilds the ordered status index from the status sets.
+    pub fn backfill_status_index(&self, store: &mut dyn Storage) -> StdResult<()> {
+        for status in [
+            DataRequestStatus::Committing,
+            DataRequestStatus::Revealing,
+            DataRequestStatus::Tallying,
+        ] {
+            let set = match status {
+                DataRequestStatus::Committing => &self.committing,
+                DataRequestStatus::Revealing => &self.revealing,
+                DataRequestStatus::Tallying => &self.tallying,
+            };
+            for index in 0..set.len(store)? {
+                let key = set.index_to_key.load(store, index)?;
+                let req = self.get(store, &key)?;
+                self.by_status
+                    .save(store, (status_prefix(&status), req.height, &key), &())?;
+            }
+        }
 
         Ok(())
     }
@@ -50,21 +209,37 @@ impl DataRequestsMap<'_> {
         key: Hash,
         req: DataRequest,
         status: &DataRequestStatus,
+        timeouts: &DataRequestTimeouts,
     ) -> StdResult<()> {
         if self.has(store, &key) {
             return Err(StdError::generic_err("Key already exists"));
         }
 
+        self.add_to_status(store, key, status, req.height)?;
+        self.add_to_programs(store, &key, &req)?;
         self.reqs.save(store, &key, &req)?;
-        self.add_to_status(store, key, status)?;
-        let timeout_config = TIMEOUT_CONFIG.load(store)?;
+        self.stage_timeouts.save(store, &key, timeouts)?;
         self.timeouts
-            .insert(store, current_height + timeout_config.commit_timeout_in_blocks, &key)?;
+            .insert(store, current_height + timeouts.commit_timeout_in_blocks, &key)?;
 
         Ok(())
     }
 
-    fn find_status(&self, store: &dyn Storage, key: Hash) -> StdResult<DataRequestStatus> {
+    /// Returns the commit and reveal timeouts of a request.
+    /// Requests posted before they could be chosen use the global [`TimeoutConfig`].
+    pub fn get_timeouts(&self, store: &dyn Storage, key: &Hash) -> StdResult<DataRequestTimeouts> {
+        if let Some(timeouts) = self.stage_timeouts.may_load(store, key)? {
+            return Ok(timeouts);
+        }
+
+        let timeout_config = TIMEOUT_CONFIG.load(store)?;
+        Ok(DataRequestTimeouts {
+            commit_timeout_in_blocks: timeout_config.commit_timeout_in_blocks,
+            reveal_timeout_in_blocks: timeout_config.reveal_timeout_in_blocks,
+        })
+    }
+
+    pub fn find_status(&self, store: &dyn Storage, key: Hash) -> StdResult<DataRequestStatus> {
         if self.committing.has(store, key) {
             return Ok(DataRequestStatus::Committing);
         }
@@ -80,74 +255,143 @@ impl DataRequestsMap<'_> {
         Err(StdError::generic_err("Key does not exist"))
     }
 
-    pub fn update(
+    /// Moves a request to a new status, following the allowed [`check_status_transition`]s.
+    pub fn update_status(
         &self,
         store: &mut dyn Storage,
         key: Hash,
-        dr: DataRequest,
-        status: Option<DataRequestStatus>,
+        status: DataRequestStatus,
         current_height: u64,
         timeout: bool,
-    ) -> StdResult<()> {
+    ) -> Result<(), ContractError> {
         // Check if the key exists
-        if !self.has(store, &key) {
-            return Err(StdError::generic_err("Key does not exist"));
-        }
-
-        // If we need to update the status, we need to remove the key from the current status
-        if let Some(status) = status {
-            // Grab the current status.
-            let current_status = self.find_status(store, key)?;
-            // world view = we should only update from committing -> revealing -> tallying,
-            // or if it's a timeout, from any status to tallying.
-            // Either the concept is fundamentally flawed or the implementation is wrong.
-            match &current_status {
-                _ if timeout => {
-                    assert_eq!(
-                        status,
-                        DataRequestStatus::Tallying,
-                        "Cannot update a timed out request status to anything other than tallying"
-                    );
-                }
-                DataRequestStatus::Committing => {
-                    assert_eq!(
-                        status,
-                        DataRequestStatus::Revealing,
-                        "Cannot update a request status from committing to anything other than revealing"
-                    );
-
-                    // We change the timeout to the reveal timeout when commit -> reveal
-                    self.timeouts.remove_by_dr_id(store, &key)?;
-                    let timeout_config = TIMEOUT_CONFIG.load(store)?;
-                    self.timeouts
-                        .insert(store, timeout_config.reveal_timeout_in_blocks + current_height, &key)?;
-                }
-                DataRequestStatus::Revealing => {
-                    assert_eq!(
-                        status,
-                        DataRequestStatus::Tallying,
-                        "Cannot update a request status from revealing to anything other than tallying"
-                    );
-
-                    // We remove the timeout when reveal -> tally
-                    self.timeouts.remove_by_dr_id(store, &key)?;
-                }
-                DataRequestStatus::Tallying => {
-                    assert_ne!(
-                        current_status,
-                        DataRequestStatus::Tallying,
-                        "Cannot update a request's status that is tallying"
-                    );
-                }
+        let Some(req) = self.reqs.may_load(store, &key)? else {
+            return Err(StdError::generic_err("Key does not exist").into());
+        };
+
+        // Grab the current status.
+        let current_status = self.find_status(store, key)?;
+        check_status_transition(&current_status, &status, timeout)?;
+
+        match (&current_status, &status) {
+            (DataRequestStatus::Committing, DataRequestStatus::Revealing) => {
+                // We change the timeout to the reveal timeout when commit -> reveal
+                self.timeouts.remove_by_dr_id(store, &key)?;
+                let timeouts = self.get_timeouts(store, &key)?;
+                self.timeouts
+                    .insert(store, timeouts.reveal_timeout_in_blocks + current_height, &key)?;
             }
+            // We remove the timeout when reveal -> tally,
+            // a timed out request already had its timeout removed.
+            (DataRequestStatus::Revealing, DataRequestStatus::Tallying) if !timeout => {
+                self.timeouts.remove_by_dr_id(store, &key)?;
+            }
+            _ => {}
+        }
+
+        // remove from current status, then add to new one.
+        self.remove_from_status(store, key, &current_status, req.height)?;
+        self.add_to_status(store, key, &status, req.height)?;
+
+        Ok(())
+    }
 
-            // remove from current status, then add to new one.
-            self.remove_from_status(store, key, &current_status)?;
-            self.add_to_status(store, key, &status)?;
+    /// Stores the commitment of an executor and returns the number of commits on the request.
+    pub fn add_commit(
+        &self,
+        store: &mut dyn Storage,
+        key: &Hash,
+        public_key: &str,
+        commitment: &Hash,
+    ) -> StdResult<u16> {
+        self.commits.save(store, (key, public_key), commitment)?;
+        let len = self.commits_len(store, key)? + 1;
+        self.commits_len.save(store, key, &len)?;
+
+        Ok(len)
+    }
+
+    /// Stores the reveal of an executor and returns the number of reveals on the request.
+    pub fn add_reveal(
+        &self,
+        store: &mut dyn Storage,
+        key: &Hash,
+        public_key: &str,
+        reveal: &RevealBody,
+    ) -> StdResult<u16> {
+        self.reveals.save(store, (key, public_key), reveal)?;
+        let len = self.reveals_len.may_load(store, key)?.unwrap_or_default() + 1;
+        self.reveals_len.save(store, key, &len)?;
+
+        Ok(len)
+    }
+
+    pub fn commits_len(&self, store: &dyn Storage, key: &Hash) -> StdResult<u16> {
+        Ok(self.commits_len.may_load(store, key)?.unwrap_or_default())
+    }
+
+    pub fn reveals_len(&self, store: &dyn Storage, key: &Hash) -> StdResult<u16> {
+        Ok(self.reveals_len.may_load(store, key)?.unwrap_or_default())
+    }
+
+    pub fn get_commitment(&self, store: &dyn Storage, key: &Hash, public_key: &str) -> StdResult<Option<Hash>> {
+        self.commits.may_load(store, (key, public_key))
+    }
+
+    pub fn get_reveal(&self, store: &dyn Storage, key: &Hash, public_key: &str) -> StdResult<Option<RevealBody>> {
+        self.reveals.may_load(store, (key, public_key))
+    }
+
+    pub fn has_revealed(&self, store: &dyn Storage, key: &Hash, public_key: &str) -> bool {
+        self.reveals.has(store, (key, public_key))
+    }
+
+    /// Returns the public keys of the executors that committed on a request, in ascending order.
+    pub fn committers(&self, store: &dyn Storage, key: &Hash) -> StdResult<Vec<String>> {
+        self.commits
+            .prefix(key)
+            .keys(store, None, None, Order::Ascending)
+            .collect()
+    }
+
+    /// Fills in the commits and reveals of a request from their maps.
+    fn with_results(&self, store: &dyn Storage, key: &Hash, mut req: DataRequest) -> StdResult<DataRequest> {
+        req.commits = self
+            .commits
+            .prefix(key)
+            .range(store, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+        req.reveals = self
+            .reveals
+            .prefix(key)
+            .range(store, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+
+        Ok(req)
+    }
+
+    /// Moves the commits and reveals that used to be stored inside the requests to their own maps.
+    pub fn move_results_out_of_requests(&self, store: &mut dyn Storage) -> StdResult<()> {
+        let reqs = self
+            .reqs
+            .range(store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for (key, mut req) in reqs {
+            for (public_key, commitment) in &req.commits {
+                self.commits.save(store, (&key, public_key), commitment)?;
+            }
+            for (public_key, reveal) in &req.reveals {
+                self.reveals.save(store, (&key, public_key), reveal)?;
+            }
+            self.commits_len.save(store, &key, &(req.commits.len() as u16))?;
+            self.reveals_len.save(store, &key, &(req.reveals.len() as u16))?;
+
+            req.commits = Default::default();
+            req.reveals = Default::default();
+            self.reqs.save(store, &key, &req)?;
         }
 
-        // always update the request
-        self.reqs.save(store, &key, &dr)?;
         Ok(())
     }
 
@@ -159,27 +403,73 @@ impl DataRequestsMap<'_> {
         self.reqs.load(store, key)
     }
 
+    /// Like [`Self::may_get`], but with the commits and reveals of the request filled in.
+    pub fn may_get_with_results(&self, store: &dyn Storage, key: &Hash) -> StdResult<Option<DataRequest>> {
+        self.reqs
+            .may_load(store, key)?
+            .map(|req| self.with_results(store, key, req))
+            .transpose()
+    }
+
+    fn get_with_results(&self, store: &dyn Storage, key: &Hash) -> StdResult<DataRequest> {
+        let req = self.reqs.load(store, key)?;
+        self.with_results(store, key, req)
+    }
+
     /// Removes an req from the map by key.
     /// Swaps the last req with the req to remove.
     /// Then pops the last req.
-    pub fn remove(&self, store: &mut dyn Storage, key: Hash) -> Result<(), StdError> {
+    pub fn remove(&self, store: &mut dyn Storage, key: Hash) -> Result<(), ContractError> {
         if !self.has(store, &key) {
-            return Err(StdError::generic_err("Key does not exist"));
+            return Err(StdError::generic_err("Key does not exist").into());
         }
 
-        // world view = we only remove a data request that is done tallying.
-        // Either the concept is fundamentally flawed or the implementation is wrong.
+        // we only remove a data request that is done tallying.
         let current_status = self.find_status(store, key)?;
-        assert_eq!(
-            current_status,
-            DataRequestStatus::Tallying,
-            "Cannot remove a request that is not tallying"
-        );
+        check_removable(&current_status)?;
 
-        // remove the request
+        // remove the request along with its commits and reveals
+        let req = self.get(store, &key)?;
         self.reqs.remove(store, &key);
+        for public_key in self.committers(store, &key)? {
+            self.commits.remove(store, (&key, &public_key));
+            self.reveals.remove(store, (&key, &public_key));
+        }
+        self.commits_len.remove(store, &key);
+        self.reveals_len.remove(store, &key);
+        self.stage_timeouts.remove(store, &key);
+        self.remove_from_programs(store, &key, &req)?;
         // remove from the status
-        self.remove_from_status(store, key, &current_status)?;
+        self.remove_from_status(store, key, &current_status, req.height)?;
+
+        Ok(())
+    }
+
+    /// Errors unless the request is still committing.
+    pub fn ensure_committing(&self, store: &dyn Storage, key: Hash) -> Result<(), ContractError> {
+        if self.find_status(store, key)? != DataRequestStatus::Committing {
+            return Err(ContractError::DataRequestNotCommitting);
+        }
+
+        Ok(())
+    }
+
+    /// Removes a request that is still committing and has no commits, along with its timeout.
+    pub fn cancel(&self, store: &mut dyn Storage, key: Hash) -> Result<(), ContractError> {
+        let Some(req) = self.reqs.may_load(store, &key)? else {
+            return Err(StdError::generic_err("Key does not exist").into());
+        };
+
+        let current_status = self.find_status(store, key)?;
+        if current_status != DataRequestStatus::Committing || self.commits_len(store, &key)? > 0 {
+            return Err(ContractError::DataRequestNotCancellable);
+        }
+
+        self.reqs.remove(store, &key);
+        self.remove_from_status(store, key, &current_status, req.height)?;
+        self.remove_from_programs(store, &key, &req)?;
+        self.timeouts.remove_by_dr_id(store, &key)?;
+        self.stage_timeouts.remove(store, &key);
 
         Ok(())
     }
@@ -200,40 +490,107 @@ impl DataRequestsMap<'_> {
         }
         .index_to_key
         .range(store, start, end, Order::Ascending)
-        .flat_map(|result| result.map(|(_, key)| self.reqs.load(store, &key)))
+        .flat_map(|result| result.map(|(_, key)| self.get_with_results(store, &key)))
         .collect::<StdResult<Vec<_>>>()?;
 
         Ok(requests)
     }
 
-    pub fn expire_data_requests(&self, store: &mut dyn Storage, current_height: u64) -> StdResult<Vec<String>> {
+    /// Returns up to `limit` requests of a status ordered by posting height then id, starting after `after`.
+    /// Also returns the cursor to resume from if there are more requests.
+    pub fn get_requests_by_status_after(
+        &self,
+        store: &dyn Storage,
+        status: &DataRequestStatus,
+        after: Option<StatusCursor>,
+        limit: u32,
+    ) -> StdResult<(Vec<DataRequest>, Option<StatusCursor>)> {
+        let (keys, next) = self.get_keys_by_status_after(store, status, after, limit)?;
+        let requests = keys
+            .iter()
+            .map(|(_, key)| self.get_with_results(store, key))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        Ok((requests, next))
+    }
+
+    /// Like [`Self::get_requests_by_status_after`], but only returns the positions of the requests.
+    pub fn get_keys_by_status_after(
+        &self,
+        store: &dyn Storage,
+        status: &DataRequestStatus,
+        after: Option<StatusCursor>,
+        limit: u32,
+    ) -> StdResult<(Vec<StatusCursor>, Option<StatusCursor>)> {
+        let start = after.as_ref().map(|(height, dr_id)| Bound::exclusive((*height, dr_id)));
+        let mut keys = self
+            .by_status
+            .sub_prefix(status_prefix(status))
+            .keys(store, start, None, Order::Ascending)
+            .take(limit as usize + 1)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        // only return a cursor if there is at least one more request after this page
+        let next = if keys.len() > limit as usize {
+            keys.truncate(limit as usize);
+            keys.last().copied()
+        } else {
+            None
+        };
+
+        Ok((keys, next))
+    }
+
+    /// Moves up to `limit` requests that timed out at or before the current height to tallying.
+    /// Returns the ids of the expired requests along with the status they timed out in,
+    /// and the next timeout left to process, if any.
+    pub fn expire_data_requests(
+        &self,
+        store: &mut dyn Storage,
+        current_height: u64,
+        limit: u32,
+    ) -> Result<(Vec<(Hash, DataRequestStatus)>, Option<(u64, Hash)>), ContractError> {
         // remove them from the timeouts and return the hashes
-        let drs_to_update_to_tally = self.timeouts.remove_by_timeout_height(store, current_height)?;
+        let (drs_to_update_to_tally, next) = self
+            .timeouts
+            .remove_up_to_timeout_height(store, current_height, limit)?;
 
-        drs_to_update_to_tally
+        let expired = drs_to_update_to_tally
             .into_iter()
             .map(|hash| {
-                // get the dr itself
-                let dr = self.get(store, &hash)?;
+                // get the status it timed out in
+                let status = self.find_status(store, hash)?;
                 // update it to tallying
-                self.update(store, hash, dr, Some(DataRequestStatus::Tallying), current_height, true)?;
-                Ok(hash.to_hex())
+                self.update_status(store, hash, DataRequestStatus::Tallying, current_height, true)?;
+                Ok((hash, status))
             })
-            .collect::<StdResult<Vec<_>>>()
+            .collect::<Result<Vec<_>, ContractError>>()?;
+
+        Ok((expired, next))
     }
 }
 
 macro_rules! new_enumerable_status_map {
     ($namespace:literal) => {
         DataRequestsMap {
-            reqs:       Map::new(concat!($namespace, "_reqs")),
-            committing: $crate::enumerable_set!(concat!($namespace, "_committing")),
-            revealing:  $crate::enumerable_set!(concat!($namespace, "_revealing")),
-            tallying:   $crate::enumerable_set!(concat!($namespace, "_tallying")),
-            timeouts:   Timeouts {
+            reqs:              Map::new(concat!($namespace, "_reqs")),
+            committing:        $crate::enumerable_set!(concat!($namespace, "_committing")),
+            revealing:         $crate::enumerable_set!(concat!($namespace, "_revealing")),
+            tallying:          $crate::enumerable_set!(concat!($namespace, "_tallying")),
+            by_status:         Map::new(concat!($namespace, "_by_status")),
+            timeouts:          Timeouts {
                 timeouts:        Map::new(concat!($namespace, "_timeouts")),
                 hash_to_timeout: Map::new(concat!($namespace, "_hash_to_timeout")),
             },
+            commits:           Map::new(concat!($namespace, "_commits")),
+            reveals:           Map::new(concat!($namespace, "_reveals")),
+            commits_len:       Map::new(concat!($namespace, "_commits_len")),
+            reveals_len:       Map::new(concat!($namespace, "_reveals_len")),
+            stage_timeouts:    Map::new(concat!($namespace, "_stage_timeouts")),
+            by_exec_program:   Map::new(concat!($namespace, "_by_exec_program")),
+            by_tally_program:  Map::new(concat!($namespace, "_by_tally_program")),
+            exec_program_len:  Map::new(concat!($namespace, "_exec_program_len")),
+            tally_program_len: Map::new(concat!($namespace, "_tally_program_len")),
         }
     };
 }
@@ -67,6 +67,7 @@ impl TestInfo<'_> {
                 key,
                 value,
                 &DataRequestStatus::Committing,
+                &self.default_timeouts(),
             )
             .unwrap();
     }
@@ -80,14 +81,22 @@ impl TestInfo<'_> {
                 key,
                 value,
                 &DataRequestStatus::Tallying,
+                &self.default_timeouts(),
             )
             .unwrap();
     }
 
+    fn default_timeouts(&self) -> DataRequestTimeouts {
+        DataRequestTimeouts {
+            commit_timeout_in_blocks: INITIAL_COMMIT_TIMEOUT_IN_BLOCKS,
+            reveal_timeout_in_blocks: INITIAL_REVEAL_TIMEOUT_IN_BLOCKS,
+        }
+    }
+
     #[track_caller]
-    fn update(&mut self, key: Hash, dr: DataRequest, status: Option<DataRequestStatus>, current_height: u64) {
+    fn update_status(&mut self, key: Hash, status: DataRequestStatus, current_height: u64) {
         self.map
-            .update(&mut self.store, key, dr, status, current_height, false)
+            .update_status(&mut self.store, key, status, current_height, false)
             .unwrap();
     }
 
@@ -112,6 +121,18 @@ impl TestInfo<'_> {
             .get_requests_by_status(&self.store, &status, offset, limit)
             .unwrap()
     }
+
+    #[track_caller]
+    fn get_requests_by_status_after(
+        &self,
+        status: DataRequestStatus,
+        after: Option<StatusCursor>,
+        limit: u32,
+    ) -> (Vec<DataRequest>, Option<StatusCursor>) {
+        self.map
+            .get_requests_by_status_after(&self.store, &status, after, limit)
+            .unwrap()
+    }
 }
 
 fn create_test_dr(nonce: u128) -> (Hash, DataRequest) {
@@ -172,32 +193,34 @@ fn enum_map_insert_duplicate() {
 }
 
 #[test]
-fn enum_map_update() {
+fn enum_map_update_status() {
     let mut test_info = TestInfo::init();
-    const TEST_STATUS: &DataRequestStatus = &DataRequestStatus::Committing;
 
     let (key1, dr1) = create_test_dr(1);
-    let (_, dr2) = create_test_dr(2);
+    let (key2, dr2) = create_test_dr(2);
     let current_height = 1;
 
     test_info.insert(current_height, key1, dr1.clone());
-    test_info.assert_status_len(1, TEST_STATUS);
-    test_info.assert_status_key_to_index(TEST_STATUS, key1, Some(0));
-    test_info.assert_status_index_to_key(TEST_STATUS, 0, Some(key1));
-
-    test_info.update(key1, dr2.clone(), None, current_height);
-    test_info.assert_status_len(1, TEST_STATUS);
-    test_info.assert_status_key_to_index(TEST_STATUS, key1, Some(0));
-    test_info.assert_status_index_to_key(TEST_STATUS, 0, Some(key1));
+    test_info.insert(current_height, key2, dr2.clone());
+    test_info.assert_status_len(2, &DataRequestStatus::Committing);
+
+    test_info.update_status(key1, DataRequestStatus::Revealing, current_height);
+    test_info.assert_status_len(1, &DataRequestStatus::Committing);
+    test_info.assert_status_key_to_index(&DataRequestStatus::Committing, key2, Some(0));
+    test_info.assert_status_index_to_key(&DataRequestStatus::Committing, 0, Some(key2));
+    test_info.assert_status_len(1, &DataRequestStatus::Revealing);
+    test_info.assert_status_key_to_index(&DataRequestStatus::Revealing, key1, Some(0));
+    test_info.assert_status_index_to_key(&DataRequestStatus::Revealing, 0, Some(key1));
+    test_info.assert_request(&key1, Some(dr1));
 }
 
 #[test]
 #[should_panic(expected = "Key does not exist")]
 fn enum_map_update_non_existing() {
     let mut test_info = TestInfo::init();
-    let (key, req) = create_test_dr(1);
+    let (key, _) = create_test_dr(1);
     let current_height = 1;
-    test_info.update(key, req, None, current_height);
+    test_info.update_status(key, DataRequestStatus::Revealing, current_height);
 }
 
 #[test]
@@ -302,6 +325,33 @@ fn enum_map_remove_non_existing() {
     test_info.remove(2.to_string().hash());
 }
 
+#[test]
+fn invalid_status_transitions_return_errors() {
+    let mut test_info = TestInfo::init();
+    let (key, req) = create_test_dr(1);
+    test_info.insert(1, key, req.clone());
+
+    // a request cannot skip revealing unless it timed out
+    let err = test_info
+        .map
+        .update_status(&mut test_info.store, key, DataRequestStatus::Tallying, 1, false)
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidStatusTransition {
+            from:    DataRequestStatus::Committing,
+            to:      DataRequestStatus::Tallying,
+            timeout: false,
+        }
+    );
+    test_info.assert_status_len(1, &DataRequestStatus::Committing);
+
+    // and cannot be removed before tallying
+    let err = test_info.map.remove(&mut test_info.store, key).unwrap_err();
+    assert_eq!(err, ContractError::NotRemovableStatus(DataRequestStatus::Committing));
+    test_info.assert_request(&key, Some(req));
+}
+
 #[test]
 fn get_requests_by_status() {
     let mut test_info = TestInfo::init();
@@ -312,7 +362,7 @@ fn get_requests_by_status() {
 
     let (key2, req2) = create_test_dr(2);
     test_info.insert(current_height, key2, req2.clone());
-    test_info.update(key2, req2.clone(), Some(DataRequestStatus::Revealing), current_height);
+    test_info.update_status(key2, DataRequestStatus::Revealing, current_height);
 
     let committing = test_info.get_requests_by_status(DataRequestStatus::Committing, 0, 10);
     assert_eq!(committing.len(), 1);
@@ -352,6 +402,66 @@ fn get_requests_by_status_pagination() {
     assert!(five_nine.contains(&reqs[9]));
 }
 
+#[test]
+fn get_requests_by_status_cursor_pagination() {
+    let mut test_info = TestInfo::init();
+
+    // inserted out of order, the height of each request is its nonce
+    let mut reqs = Vec::with_capacity(10);
+    for i in [4, 0, 9, 2, 7, 1, 8, 3, 6, 5] {
+        let (key, req) = create_test_dr(i);
+        test_info.insert(1, key, req.clone());
+        reqs.push((i, req));
+    }
+    reqs.sort_by_key(|(i, _)| *i);
+    let reqs = reqs.into_iter().map(|(_, req)| req).collect::<Vec<_>>();
+
+    let (page, cursor) = test_info.get_requests_by_status_after(DataRequestStatus::Committing, None, 4);
+    assert_eq!(page, reqs[0..4]);
+    assert_eq!(cursor, Some((3, 3.to_string().hash())));
+
+    let (page, cursor) = test_info.get_requests_by_status_after(DataRequestStatus::Committing, cursor, 4);
+    assert_eq!(page, reqs[4..8]);
+
+    // the last page has no cursor
+    let (page, cursor) = test_info.get_requests_by_status_after(DataRequestStatus::Committing, cursor, 4);
+    assert_eq!(page, reqs[8..10]);
+    assert_eq!(cursor, None);
+}
+
+#[test]
+fn get_requests_by_status_cursor_stable_under_status_changes() {
+    let mut test_info = TestInfo::init();
+
+    let mut reqs = Vec::with_capacity(10);
+    for i in 0..10 {
+        let (key, req) = create_test_dr(i);
+        test_info.insert(1, key, req.clone());
+        reqs.push((key, req));
+    }
+
+    let (page, cursor) = test_info.get_requests_by_status_after(DataRequestStatus::Committing, None, 3);
+    assert_eq!(page, reqs[0..3].iter().map(|(_, req)| req.clone()).collect::<Vec<_>>());
+
+    // requests already seen and not yet seen move to revealing while paging
+    for i in [1, 4] {
+        let (key, _) = reqs[i].clone();
+        test_info.update_status(key, DataRequestStatus::Revealing, 1);
+    }
+
+    // the remaining committing requests are neither skipped nor repeated
+    let (page, cursor) = test_info.get_requests_by_status_after(DataRequestStatus::Committing, cursor, 10);
+    let expected = [3, 5, 6, 7, 8, 9]
+        .iter()
+        .map(|i| reqs[*i].1.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(page, expected);
+    assert_eq!(cursor, None);
+
+    let (page, _) = test_info.get_requests_by_status_after(DataRequestStatus::Revealing, None, 10);
+    assert_eq!(page, vec![reqs[1].1.clone(), reqs[4].1.clone()]);
+}
+
 #[test]
 #[should_panic(expected = "Key does not exist")]
 fn remove_from_empty() {
@@ -372,3 +482,212 @@ fn remove_only_item() {
     test_info.assert_status_index_to_key(TEST_STATUS, 0, None);
     test_info.assert_status_key_to_index(TEST_STATUS, key, None);
 }
+
+#[test]
+fn commits_and_reveals_are_stored_separately() {
+    let mut test_info = TestInfo::init();
+    let (key, req) = create_test_dr(1);
+    test_info.insert(1, key, req.clone());
+
+    let reveal = RevealBody {
+        id:                req.id.clone(),
+        salt:              "salt".to_string(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    assert_eq!(
+        1,
+        test_info
+            .map
+            .add_commit(&mut test_info.store, &key, "bob", &[2; 32])
+            .unwrap()
+    );
+    assert_eq!(
+        2,
+        test_info
+            .map
+            .add_commit(&mut test_info.store, &key, "alice", &[1; 32])
+            .unwrap()
+    );
+    assert_eq!(
+        1,
+        test_info
+            .map
+            .add_reveal(&mut test_info.store, &key, "alice", &reveal)
+            .unwrap()
+    );
+
+    // the request itself stays untouched
+    test_info.assert_request(&key, Some(req.clone()));
+    assert_eq!(2, test_info.map.commits_len(&test_info.store, &key).unwrap());
+    assert_eq!(
+        vec!["alice".to_string(), "bob".to_string()],
+        test_info.map.committers(&test_info.store, &key).unwrap()
+    );
+    assert!(test_info.map.has_revealed(&test_info.store, &key, "alice"));
+    assert!(!test_info.map.has_revealed(&test_info.store, &key, "bob"));
+
+    // but is returned with its commits and reveals filled in
+    let mut expected = req;
+    expected.commits.insert("alice".to_string(), [1; 32]);
+    expected.commits.insert("bob".to_string(), [2; 32]);
+    expected.reveals.insert("alice".to_string(), reveal);
+    assert_eq!(
+        Some(expected.clone()),
+        test_info.map.may_get_with_results(&test_info.store, &key).unwrap()
+    );
+    assert_eq!(
+        vec![expected],
+        test_info.get_requests_by_status(DataRequestStatus::Committing, 0, 10)
+    );
+
+    // removing the request removes its commits and reveals
+    test_info.update_status(key, DataRequestStatus::Revealing, 1);
+    test_info.update_status(key, DataRequestStatus::Tallying, 1);
+    test_info.remove(key);
+    assert_eq!(0, test_info.map.commits_len(&test_info.store, &key).unwrap());
+    assert!(test_info.map.committers(&test_info.store, &key).unwrap().is_empty());
+    assert!(!test_info.map.has_revealed(&test_info.store, &key, "alice"));
+}
+
+#[test]
+fn move_results_out_of_requests() {
+    let mut test_info = TestInfo::init();
+    let (key, req) = create_test_dr(1);
+    test_info.insert(1, key, req.clone());
+
+    // requests used to hold their commits and reveals
+    let reveal = RevealBody {
+        id:                req.id.clone(),
+        salt:              "salt".to_string(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    let mut legacy = req.clone();
+    legacy.commits.insert("alice".to_string(), [1; 32]);
+    legacy.commits.insert("bob".to_string(), [2; 32]);
+    legacy.reveals.insert("alice".to_string(), reveal.clone());
+    test_info.map.reqs.save(&mut test_info.store, &key, &legacy).unwrap();
+
+    test_info
+        .map
+        .move_results_out_of_requests(&mut test_info.store)
+        .unwrap();
+
+    test_info.assert_request(&key, Some(req));
+    assert_eq!(2, test_info.map.commits_len(&test_info.store, &key).unwrap());
+    assert_eq!(
+        Some([2; 32]),
+        test_info.map.get_commitment(&test_info.store, &key, "bob").unwrap()
+    );
+    assert_eq!(
+        Some(reveal),
+        test_info.map.get_reveal(&test_info.store, &key, "alice").unwrap()
+    );
+    assert_eq!(
+        Some(legacy),
+        test_info.map.may_get_with_results(&test_info.store, &key).unwrap()
+    );
+}
+
+#[test]
+fn cancel_only_without_commits() {
+    let mut test_info = TestInfo::init();
+    let (key1, req1) = create_test_dr(1);
+    let (key2, req2) = create_test_dr(2);
+    test_info.insert(1, key1, req1);
+    test_info.insert(1, key2, req2.clone());
+
+    test_info
+        .map
+        .add_commit(&mut test_info.store, &key2, "alice", &[1; 32])
+        .unwrap();
+    let err = test_info.map.cancel(&mut test_info.store, key2).unwrap_err();
+    assert_eq!(err, ContractError::DataRequestNotCancellable);
+    test_info.assert_request(&key2, Some(req2));
+
+    test_info.map.cancel(&mut test_info.store, key1).unwrap();
+    test_info.assert_request(&key1, None);
+    test_info.assert_status_len(1, &DataRequestStatus::Committing);
+    assert!(test_info
+        .map
+        .timeouts
+        .get_timeout_by_dr_id(&test_info.store, &key1)
+        .is_err());
+}
+
+#[test]
+fn program_index_follows_requests() {
+    let mut test_info = TestInfo::init();
+    let (key1, req1) = create_test_dr(1);
+    let (key2, req2) = create_test_dr(2);
+    test_info.insert(1, key1, req1.clone());
+    test_info.insert(1, key2, req2.clone());
+
+    let tally_program_id = req1.tally_program_id.as_str();
+    assert_eq!(
+        2,
+        test_info
+            .map
+            .program_len(&test_info.store, &ProgramKind::Tally, tally_program_id)
+            .unwrap()
+    );
+    let (keys, next) = test_info
+        .map
+        .get_keys_by_program(&test_info.store, &ProgramKind::Exec, &req1.exec_program_id, None, 10)
+        .unwrap();
+    assert_eq!((vec![key1], None), (keys, next));
+
+    test_info.map.cancel(&mut test_info.store, key1).unwrap();
+    assert_eq!(
+        1,
+        test_info
+            .map
+            .program_len(&test_info.store, &ProgramKind::Tally, tally_program_id)
+            .unwrap()
+    );
+    assert_eq!(
+        0,
+        test_info
+            .map
+            .program_len(&test_info.store, &ProgramKind::Exec, &req1.exec_program_id)
+            .unwrap()
+    );
+
+    // requests posted before the index existed are picked up by the backfill
+    test_info
+        .map
+        .by_exec_program
+        .remove(&mut test_info.store, (req2.exec_program_id.as_str(), &key2));
+    test_info
+        .map
+        .by_tally_program
+        .remove(&mut test_info.store, (tally_program_id, &key2));
+    test_info
+        .map
+        .exec_program_len
+        .remove(&mut test_info.store, &req2.exec_program_id);
+    test_info
+        .map
+        .tally_program_len
+        .remove(&mut test_info.store, tally_program_id);
+
+    test_info.map.backfill_program_index(&mut test_info.store).unwrap();
+    test_info.map.backfill_program_index(&mut test_info.store).unwrap();
+    let (keys, _) = test_info
+        .map
+        .get_keys_by_program(&test_info.store, &ProgramKind::Tally, tally_program_id, None, 10)
+        .unwrap();
+    assert_eq!(vec![key2], keys);
+    assert_eq!(
+        1,
+        test_info
+            .map
+            .program_len(&test_info.store, &ProgramKind::Exec, &req2.exec_program_id)
+            .unwrap()
+    );
+}
@@ -1,27 +1,294 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Storage;
+use cw20::Cw20ExecuteMsg;
 use cw_storage_plus::Bound;
 
 use super::*;
 mod data_requests_map;
+pub use data_requests_map::StatusCursor;
 use data_requests_map::{new_enumerable_status_map, DataRequestsMap};
+mod status_transitions;
+pub use status_transitions::{check_removable, check_status_transition};
 mod timeouts;
 use timeouts::Timeouts;
 
 /// Governance-controlled timeout configuration parameters.
 pub const TIMEOUT_CONFIG: Item<TimeoutConfig> = Item::new("timeout_config");
 
+/// Governance-controlled timeout parameters that are not part of [`TimeoutConfig`].
+#[cw_serde]
+pub struct ExtendedTimeoutConfig {
+    /// Maximum number of timed out data requests expired by a single `ExpireDataRequests` call.
+    /// Any remaining ones are expired by the following calls.
+    pub max_expired_per_sweep: u32,
+    /// Lower bound of the commit and reveal timeouts a poster can choose.
+    pub min_timeout_in_blocks: u64,
+    /// Upper bound of the commit and reveal timeouts a poster can choose.
+    pub max_timeout_in_blocks: u64,
+}
+
+/// Which program of a data request an index refers to.
+#[cw_serde]
+pub enum ProgramKind {
+    Exec,
+    Tally,
+}
+
+/// The commit and reveal timeouts of a data request.
+#[cw_serde]
+pub struct DataRequestTimeouts {
+    pub commit_timeout_in_blocks: u64,
+    pub reveal_timeout_in_blocks: u64,
+}
+
+/// Governance-controlled timeout configuration parameters specific to this contract.
+pub const EXTENDED_TIMEOUT_CONFIG: Item<ExtendedTimeoutConfig> = Item::new("extended_timeout_config");
+
 /// Stores the amount, and the poster address.
 #[cw_serde]
 pub struct Escrow {
-    pub amount: Uint128,
+    pub amount:        Uint128,
     // Safe to use Addr here as we aren't taking the type from a user input.
-    pub poster: Addr,
+    pub poster:        Addr,
+    /// The CW20 contract of the escrowed tokens, or `None` when they are the native token.
+    #[serde(default)]
+    pub cw20_contract: Option<Addr>,
+    /// Amount of the CW20 tokens worth one unit of the native token when the data request was posted.
+    #[serde(default)]
+    pub cw20_rate:     Option<Decimal>,
+}
+
+impl Escrow {
+    /// Converts an amount of the native token, which the chain prices gas in, into the escrowed tokens.
+    pub fn native_to_escrowed(&self, amount: Uint128) -> Uint128 {
+        match self.cw20_rate {
+            // the converted amounts are capped by the escrowed amount, so saturating is fine
+            Some(rate) => amount.checked_mul_floor(rate).unwrap_or(Uint128::MAX),
+            None => amount,
+        }
+    }
+
+    /// Message sending `amount` of the escrowed tokens to `recipient`.
+    pub fn transfer_msg(&self, native_token: &str, recipient: String, amount: Uint128) -> StdResult<CosmosMsg> {
+        Ok(match &self.cw20_contract {
+            Some(cw20_contract) => WasmMsg::Execute {
+                contract_addr: cw20_contract.to_string(),
+                msg:           to_json_binary(&Cw20ExecuteMsg::Transfer { recipient, amount })?,
+                funds:         vec![],
+            }
+            .into(),
+            None => BankMsg::Send {
+                to_address: recipient,
+                amount:     coins(amount.u128(), native_token),
+            }
+            .into(),
+        })
+    }
+
+    /// Message burning `amount` of the escrowed tokens.
+    pub fn burn_msg(&self, native_token: &str, amount: Uint128) -> StdResult<CosmosMsg> {
+        Ok(match &self.cw20_contract {
+            Some(cw20_contract) => WasmMsg::Execute {
+                contract_addr: cw20_contract.to_string(),
+                msg:           to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
+                funds:         vec![],
+            }
+            .into(),
+            None => BankMsg::Burn {
+                amount: coins(amount.u128(), native_token),
+            }
+            .into(),
+        })
+    }
 }
 
 /// Maps a data request ID to the staked funds.
 pub const DR_ESCROW: Map<&Hash, Escrow> = Map::new("dr_staked_funds");
 
+/// CW20 contracts whose tokens are accepted to pay for data requests, managed by the owner,
+/// with the amount of their tokens worth one unit of the native token.
+pub const CW20_FEE_TOKENS: Map<&Addr, Decimal> = Map::new("cw20_fee_tokens");
+
+/// CW20 payouts of removed data requests that the CW20 contract rejected,
+/// keyed by CW20 contract and the address that can claim them.
+pub const FAILED_CW20_PAYOUTS: Map<(&Addr, &Addr), Uint128> = Map::new("failed_cw20_payouts");
+
+/// Records a rejected CW20 payout, adding to what `claimant` can already claim.
+pub fn add_failed_cw20_payout(
+    store: &mut dyn Storage,
+    cw20_contract: &Addr,
+    claimant: &Addr,
+    amount: Uint128,
+) -> StdResult<()> {
+    FAILED_CW20_PAYOUTS.update(store, (cw20_contract, claimant), |claimable| {
+        Ok::<_, StdError>(claimable.unwrap_or_default() + amount)
+    })?;
+    Ok(())
+}
+
+/// Indexes the escrowed data requests by poster.
+pub const DR_BY_POSTER: Map<(&Addr, &Hash), ()> = Map::new("dr_by_poster");
+
+/// Saves the escrow of a data request and indexes it under its poster.
+pub fn save_escrow(store: &mut dyn Storage, dr_id: &Hash, escrow: &Escrow) -> StdResult<()> {
+    DR_ESCROW.save(store, dr_id, escrow)?;
+    DR_BY_POSTER.save(store, (&escrow.poster, dr_id), &())
+}
+
+/// Removes the escrow of a data request along with its poster index entry.
+pub fn remove_escrow(store: &mut dyn Storage, dr_id: &Hash) -> StdResult<()> {
+    if let Some(escrow) = DR_ESCROW.may_load(store, dr_id)? {
+        DR_BY_POSTER.remove(store, (&escrow.poster, dr_id));
+        DR_ESCROW.remove(store, dr_id);
+    }
+
+    Ok(())
+}
+
+/// Returns up to `limit` ids of the data requests of a poster, ordered by id and starting after `start_after`.
+pub fn requests_by_poster(
+    store: &dyn Storage,
+    poster: &Addr,
+    start_after: Option<Hash>,
+    limit: u32,
+) -> StdResult<Vec<Hash>> {
+    DR_BY_POSTER
+        .prefix(poster)
+        .keys(
+            store,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect()
+}
+
+/// Indexes the data requests that are already escrowed by poster.
+pub fn index_escrows_by_poster(store: &mut dyn Storage) -> StdResult<()> {
+    let escrows = DR_ESCROW
+        .range(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (dr_id, escrow) in escrows {
+        DR_BY_POSTER.save(store, (&escrow.poster, &dr_id), &())?;
+    }
+
+    Ok(())
+}
+
+/// Governance-controlled parameters of the data result archive and result callbacks.
+#[cw_serde]
+pub struct DataResultConfig {
+    /// Number of blocks a data result stays queryable after its data request was removed.
+    pub retention_in_blocks:    u64,
+    /// Maximum number of data results pruned by a single `ExpireDataRequests` call.
+    /// Any remaining ones are pruned by the following calls.
+    pub max_pruned_per_sweep:   u32,
+    /// Maximum gas limit a poster can give the result callback of a data request.
+    pub max_callback_gas_limit: u64,
+}
+
+/// Governance-controlled data result archive and result callback parameters.
+pub const DATA_RESULT_CONFIG: Item<DataResultConfig> = Item::new("data_result_config");
+
+/// The final result of a removed data request.
+#[cw_serde]
+pub struct DataResult {
+    pub dr_id:        String,
+    pub result:       Binary,
+    pub exit_code:    u8,
+    pub gas_used:     u64,
+    pub consensus:    bool,
+    /// Height at which the data request was removed with this result.
+    pub block_height: u64,
+}
+
+/// Maps a data request ID to its final result, kept for the retention window.
+pub const DATA_RESULTS: Map<&Hash, DataResult> = Map::new("data_results");
+
+/// Indexes the data results by the height they were archived at, oldest first.
+const DATA_RESULTS_BY_HEIGHT: Map<(u64, &Hash), ()> = Map::new("data_results_by_height");
+
+/// Archives the final result of a data request, replacing any older result with the same id.
+pub fn archive_result(store: &mut dyn Storage, dr_id: &Hash, result: &DataResult) -> StdResult<()> {
+    if let Some(old) = DATA_RESULTS.may_load(store, dr_id)? {
+        DATA_RESULTS_BY_HEIGHT.remove(store, (old.block_height, dr_id));
+    }
+    DATA_RESULTS_BY_HEIGHT.save(store, (result.block_height, dr_id), &())?;
+    DATA_RESULTS.save(store, dr_id, result)
+}
+
+/// Removes up to `max_pruned_per_sweep` data results that are past the retention window, oldest first.
+/// Returns the ids of the pruned results.
+pub fn prune_results(store: &mut dyn Storage, current_height: u64, config: &DataResultConfig) -> StdResult<Vec<Hash>> {
+    // results archived before this height are past the retention window
+    let Some(cutoff) = current_height.checked_sub(config.retention_in_blocks) else {
+        return Ok(vec![]);
+    };
+
+    let expired = DATA_RESULTS_BY_HEIGHT
+        .keys(
+            store,
+            None,
+            Some(Bound::exclusive((cutoff, &Hash::default()))),
+            Order::Ascending,
+        )
+        .take(config.max_pruned_per_sweep as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(expired
+        .into_iter()
+        .map(|(height, dr_id)| {
+            DATA_RESULTS_BY_HEIGHT.remove(store, (height, &dr_id));
+            DATA_RESULTS.remove(store, &dr_id);
+            dr_id
+        })
+        .collect())
+}
+
+/// Contract the final result of a data request is sent to once it resolves.
+#[cw_serde]
+pub struct ResultCallback {
+    pub contract:  Addr,
+    /// Gas the callback may use, it fails on its own when running out.
+    pub gas_limit: u64,
+}
+
+/// Maps a data request ID to the callback registered by its poster.
+pub const DR_RESULT_CALLBACKS: Map<&Hash, ResultCallback> = Map::new("dr_result_callbacks");
+
+/// Removes the callback of a data request, returning it if there was one.
+pub fn take_result_callback(store: &mut dyn Storage, dr_id: &Hash) -> StdResult<Option<ResultCallback>> {
+    let callback = DR_RESULT_CALLBACKS.may_load(store, dr_id)?;
+    DR_RESULT_CALLBACKS.remove(store, dr_id);
+    Ok(callback)
+}
+
+/// Maps a data request ID to the committee drawn when it was posted with stake-weighted selection.
+pub const DR_COMMITTEES: Map<&Hash, Vec<PublicKey>> = Map::new("dr_committees");
+
+/// Gas price and gas limits a data request was bumped to.
+#[cw_serde]
+pub struct FeeBump {
+    pub gas_price:       Uint128,
+    pub exec_gas_limit:  u64,
+    pub tally_gas_limit: u64,
+}
+
+/// Maps a data request ID to the fee it was bumped to. The bump is kept apart from the data request,
+/// so that the stored arguments still hash to the id, and is applied when loading the data request.
+pub const DR_FEE_BUMPS: Map<&Hash, FeeBump> = Map::new("dr_fee_bumps");
+
+fn with_fee_bump(store: &dyn Storage, mut dr: DataRequest) -> StdResult<DataRequest> {
+    let dr_id = Hash::from_hex_str(&dr.id).map_err(|err| StdError::generic_err(err.to_string()))?;
+    if let Some(bump) = DR_FEE_BUMPS.may_load(store, &dr_id)? {
+        dr.gas_price = bump.gas_price;
+        dr.exec_gas_limit = bump.exec_gas_limit;
+        dr.tally_gas_limit = bump.tally_gas_limit;
+    }
+    Ok(dr)
+}
+
 const DATA_REQUESTS: DataRequestsMap = new_enumerable_status_map!("data_request_pool");
 
 pub fn init_data_requests(store: &mut dyn Storage) -> Result<(), ContractError> {
@@ -32,37 +299,123 @@ pub fn data_request_exists(deps: Deps, dr_id: Hash) -> bool {
     DATA_REQUESTS.has(deps.storage, &dr_id)
 }
 
+/// Loads a data request without its commits and reveals, which are stored separately.
 pub fn may_load_request(store: &dyn Storage, dr_id: &Hash) -> StdResult<Option<DataRequest>> {
-    DATA_REQUESTS.may_get(store, dr_id)
+    DATA_REQUESTS
+        .may_get(store, dr_id)?
+        .map(|dr| with_fee_bump(store, dr))
+        .transpose()
 }
 
+/// Loads a data request without its commits and reveals, which are stored separately.
 pub fn load_request(store: &dyn Storage, dr_id: &Hash) -> StdResult<DataRequest> {
+    with_fee_bump(store, DATA_REQUESTS.get(store, dr_id)?)
+}
+
+/// Loads a data request with the gas price and gas limits it was posted with, ignoring any fee bump.
+/// Its arguments are the ones its id is the hash of.
+pub fn load_request_as_posted(store: &dyn Storage, dr_id: &Hash) -> StdResult<DataRequest> {
     DATA_REQUESTS.get(store, dr_id)
 }
 
+/// Loads a data request with its commits and reveals filled in.
+pub fn may_load_request_with_results(store: &dyn Storage, dr_id: &Hash) -> StdResult<Option<DataRequest>> {
+    DATA_REQUESTS
+        .may_get_with_results(store, dr_id)?
+        .map(|dr| with_fee_bump(store, dr))
+        .transpose()
+}
+
+pub fn load_commitment(store: &dyn Storage, dr_id: &Hash, public_key: &str) -> StdResult<Option<Hash>> {
+    DATA_REQUESTS.get_commitment(store, dr_id, public_key)
+}
+
+pub fn load_reveal(store: &dyn Storage, dr_id: &Hash, public_key: &str) -> StdResult<Option<RevealBody>> {
+    DATA_REQUESTS.get_reveal(store, dr_id, public_key)
+}
+
+pub fn has_revealed(store: &dyn Storage, dr_id: &Hash, public_key: &str) -> bool {
+    DATA_REQUESTS.has_revealed(store, dr_id, public_key)
+}
+
+/// Public keys of the executors that committed on a data request, in ascending order.
+pub fn committers(store: &dyn Storage, dr_id: &Hash) -> StdResult<Vec<String>> {
+    DATA_REQUESTS.committers(store, dr_id)
+}
+
+/// Whether the replication factor of commits was reached and the data request moved on to revealing.
+pub fn reveal_started(store: &dyn Storage, dr_id: &Hash, replication_factor: u16) -> StdResult<bool> {
+    Ok(DATA_REQUESTS.commits_len(store, dr_id)? >= replication_factor)
+}
+
+pub fn commit_count(store: &dyn Storage, dr_id: &Hash) -> StdResult<u16> {
+    DATA_REQUESTS.commits_len(store, dr_id)
+}
+
+pub fn reveal_count(store: &dyn Storage, dr_id: &Hash) -> StdResult<u16> {
+    DATA_REQUESTS.reveals_len(store, dr_id)
+}
+
+pub fn load_timeouts(store: &dyn Storage, dr_id: &Hash) -> StdResult<DataRequestTimeouts> {
+    DATA_REQUESTS.get_timeouts(store, dr_id)
+}
+
 pub fn get_dr_expiration_height(store: &dyn Storage, dr_id: &Hash) -> StdResult<u64> {
     DATA_REQUESTS.timeouts.get_timeout_by_dr_id(store, dr_id)
 }
 
+pub fn load_status(store: &dyn Storage, dr_id: Hash) -> StdResult<DataRequestStatus> {
+    DATA_REQUESTS.find_status(store, dr_id)
+}
+
+/// Returns up to `limit` data requests timing out from `from_height` to `to_height` inclusive,
+/// along with their timeout height.
+pub fn expiring_between(
+    store: &dyn Storage,
+    from_height: u64,
+    to_height: u64,
+    limit: u32,
+) -> StdResult<Vec<(u64, Hash)>> {
+    DATA_REQUESTS.timeouts.get_range(store, from_height, to_height, limit)
+}
+
+/// Height at which the current stage of a data request times out, if it can still time out.
+pub fn may_get_dr_expiration_height(store: &dyn Storage, dr_id: &Hash) -> StdResult<Option<u64>> {
+    DATA_REQUESTS.timeouts.may_get_timeout_by_dr_id(store, dr_id)
+}
+
 pub fn post_request(
     store: &mut dyn Storage,
     current_height: u64,
     dr_id: Hash,
     dr: DataRequest,
+    timeouts: &DataRequestTimeouts,
 ) -> Result<(), ContractError> {
     // insert the data request
-    DATA_REQUESTS.insert(store, current_height, dr_id, dr, &DataRequestStatus::Committing)?;
+    DATA_REQUESTS.insert(
+        store,
+        current_height,
+        dr_id,
+        dr,
+        &DataRequestStatus::Committing,
+        timeouts,
+    )?;
 
     Ok(())
 }
 
-pub fn commit(store: &mut dyn Storage, current_height: u64, dr_id: Hash, dr: DataRequest) -> StdResult<()> {
-    let status = if dr.reveal_started() {
-        Some(DataRequestStatus::Revealing)
-    } else {
-        None
-    };
-    DATA_REQUESTS.update(store, dr_id, dr, status, current_height, false)?;
+pub fn commit(
+    store: &mut dyn Storage,
+    current_height: u64,
+    dr_id: Hash,
+    replication_factor: u16,
+    public_key: &str,
+    commitment: &Hash,
+) -> Result<(), ContractError> {
+    let commits = DATA_REQUESTS.add_commit(store, &dr_id, public_key, commitment)?;
+    if commits >= replication_factor {
+        DATA_REQUESTS.update_status(store, dr_id, DataRequestStatus::Revealing, current_height, false)?;
+    }
 
     Ok(())
 }
@@ -73,32 +426,118 @@ pub fn requests_by_status(
     offset: u32,
     limit: u32,
 ) -> StdResult<Vec<DataRequest>> {
-    DATA_REQUESTS.get_requests_by_status(store, status, offset, limit)
+    DATA_REQUESTS
+        .get_requests_by_status(store, status, offset, limit)?
+        .into_iter()
+        .map(|dr| with_fee_bump(store, dr))
+        .collect()
+}
+
+pub fn requests_by_status_after(
+    store: &dyn Storage,
+    status: &DataRequestStatus,
+    after: Option<StatusCursor>,
+    limit: u32,
+) -> StdResult<(Vec<DataRequest>, Option<StatusCursor>)> {
+    let (requests, next) = DATA_REQUESTS.get_requests_by_status_after(store, status, after, limit)?;
+    let requests = requests
+        .into_iter()
+        .map(|dr| with_fee_bump(store, dr))
+        .collect::<StdResult<_>>()?;
+    Ok((requests, next))
 }
 
-pub fn reveal(store: &mut dyn Storage, dr_id: Hash, dr: DataRequest, current_height: u64) -> StdResult<()> {
-    let status = if dr.is_tallying() {
+pub fn keys_by_status_after(
+    store: &dyn Storage,
+    status: &DataRequestStatus,
+    after: Option<StatusCursor>,
+    limit: u32,
+) -> StdResult<(Vec<StatusCursor>, Option<StatusCursor>)> {
+    DATA_REQUESTS.get_keys_by_status_after(store, status, after, limit)
+}
+
+/// Returns the ids of up to `limit` data requests of a program, ordered by id and starting after `start_after`.
+/// Also returns the id to resume from if there are more requests.
+pub fn requests_by_program(
+    store: &dyn Storage,
+    kind: &ProgramKind,
+    program_id: &str,
+    start_after: Option<Hash>,
+    limit: u32,
+) -> StdResult<(Vec<Hash>, Option<Hash>)> {
+    DATA_REQUESTS.get_keys_by_program(store, kind, program_id, start_after, limit)
+}
+
+pub fn program_request_count(store: &dyn Storage, kind: &ProgramKind, program_id: &str) -> StdResult<u32> {
+    DATA_REQUESTS.program_len(store, kind, program_id)
+}
+
+pub fn backfill_program_index(store: &mut dyn Storage) -> StdResult<()> {
+    DATA_REQUESTS.backfill_program_index(store)
+}
+
+pub fn backfill_status_index(store: &mut dyn Storage) -> StdResult<()> {
+    DATA_REQUESTS.backfill_status_index(store)
+}
+
+pub fn reveal(
+    store: &mut dyn Storage,
+    dr_id: Hash,
+    replication_factor: u16,
+    public_key: &str,
+    reveal: &RevealBody,
+    current_height: u64,
+) -> Result<(), ContractError> {
+    let reveals = DATA_REQUESTS.add_reveal(store, &dr_id, public_key, reveal)?;
+    if reveals >= replication_factor {
         // We update the status of the request from Revealing to Tallying
         // So the chain can grab it and start tallying
-        Some(DataRequestStatus::Tallying)
-    } else {
-        None
-    };
-    DATA_REQUESTS.update(store, dr_id, dr, status, current_height, false)?;
+        DATA_REQUESTS.update_status(store, dr_id, DataRequestStatus::Tallying, current_height, false)?;
+    }
+
+    Ok(())
+}
+
+/// Moves the commits and reveals stored inside the data requests to their own maps.
+pub fn move_results_out_of_requests(store: &mut dyn Storage) -> StdResult<()> {
+    DATA_REQUESTS.move_results_out_of_requests(store)
+}
+
+/// Bumps the gas price and gas limits of a data request that is still committing.
+pub fn bump_request_fee(store: &mut dyn Storage, dr_id: Hash, bump: &FeeBump) -> Result<(), ContractError> {
+    DATA_REQUESTS.ensure_committing(store, dr_id)?;
+    DR_FEE_BUMPS.save(store, &dr_id, bump)?;
+
+    Ok(())
+}
+
+/// Removes a data request that is still committing and has no commits.
+pub fn cancel_request(store: &mut dyn Storage, dr_id: Hash) -> Result<(), ContractError> {
+    DATA_REQUESTS.cancel(store, dr_id)?;
+    // a cancelled request never resolves, so its callback is never sent
+    DR_RESULT_CALLBACKS.remove(store, &dr_id);
+    DR_COMMITTEES.remove(store, &dr_id);
+    DR_FEE_BUMPS.remove(store, &dr_id);
 
     Ok(())
 }
 
-pub fn remove_request(store: &mut dyn Storage, dr_id: Hash) -> StdResult<()> {
+pub fn remove_request(store: &mut dyn Storage, dr_id: Hash) -> Result<(), ContractError> {
     // we have to remove the request from the pool
     DATA_REQUESTS.remove(store, dr_id)?;
     // no need to update status as we remove it from the requests pool
+    DR_COMMITTEES.remove(store, &dr_id);
+    DR_FEE_BUMPS.remove(store, &dr_id);
 
     Ok(())
 }
 
-pub fn expire_data_requests(store: &mut dyn Storage, current_height: u64) -> StdResult<Vec<String>> {
-    DATA_REQUESTS.expire_data_requests(store, current_height)
+pub fn expire_data_requests(
+    store: &mut dyn Storage,
+    current_height: u64,
+    limit: u32,
+) -> Result<(Vec<(Hash, DataRequestStatus)>, Option<(u64, Hash)>), ContractError> {
+    DATA_REQUESTS.expire_data_requests(store, current_height, limit)
 }
 
 #[cfg(test)]
@@ -106,5 +545,6 @@ pub fn expire_data_requests(store: &mut dyn Storage, current_height: u64) -> Std
 mod tests {
     use super::*;
     mod data_requests_map_tests;
+    mod status_transitions_tests;
     mod timeouts_tests;
 }
@@ -0,0 +1,43 @@
+use super::*;
+
+/// Checks a data request may move from the `from` status to the `to` status.
+///
+/// | from         | to           | cause                       |
+/// |--------------|--------------|-----------------------------|
+/// | `Committing` | `Revealing`  | replication factor commits  |
+/// | `Committing` | `Tallying`   | commit timeout              |
+/// | `Revealing`  | `Tallying`   | replication factor reveals  |
+/// | `Revealing`  | `Tallying`   | reveal timeout              |
+///
+/// Every other transition, including anything out of `Tallying`, is rejected.
+pub fn check_status_transition(
+    from: &DataRequestStatus,
+    to: &DataRequestStatus,
+    timeout: bool,
+) -> Result<(), ContractError> {
+    let allowed = match (from, to) {
+        (DataRequestStatus::Committing, DataRequestStatus::Revealing) => !timeout,
+        (DataRequestStatus::Committing, DataRequestStatus::Tallying) => timeout,
+        (DataRequestStatus::Revealing, DataRequestStatus::Tallying) => true,
+        _ => false,
+    };
+
+    if !allowed {
+        return Err(ContractError::InvalidStatusTransition {
+            from: from.clone(),
+            to: to.clone(),
+            timeout,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks a data request in the given status may be removed, which is only once it is tallying.
+pub fn check_removable(status: &DataRequestStatus) -> Result<(), ContractError> {
+    if status != &DataRequestStatus::Tallying {
+        return Err(ContractError::NotRemovableStatus(status.clone()));
+    }
+
+    Ok(())
+}
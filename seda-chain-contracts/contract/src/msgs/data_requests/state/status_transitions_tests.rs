@@ -0,0 +1,52 @@
+use super::*;
+
+const ALL_STATUSES: [DataRequestStatus; 3] = [
+    DataRequestStatus::Committing,
+    DataRequestStatus::Revealing,
+    DataRequestStatus::Tallying,
+];
+
+#[test]
+fn status_transition_table() {
+    use DataRequestStatus::*;
+
+    let allowed = [
+        (Committing, Revealing, false),
+        (Revealing, Tallying, false),
+        (Committing, Tallying, true),
+        (Revealing, Tallying, true),
+    ];
+
+    for from in ALL_STATUSES {
+        for to in ALL_STATUSES {
+            for timeout in [false, true] {
+                let result = check_status_transition(&from, &to, timeout);
+                if allowed.contains(&(from.clone(), to.clone(), timeout)) {
+                    assert_eq!(Ok(()), result, "{from:?} -> {to:?} (timeout: {timeout})");
+                } else {
+                    assert_eq!(
+                        Err(ContractError::InvalidStatusTransition {
+                            from: from.clone(),
+                            to: to.clone(),
+                            timeout,
+                        }),
+                        result,
+                        "{from:?} -> {to:?} (timeout: {timeout})"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn only_tallying_is_removable() {
+    for status in ALL_STATUSES {
+        let result = check_removable(&status);
+        if status == DataRequestStatus::Tallying {
+            assert_eq!(Ok(()), result);
+        } else {
+            assert_eq!(Err(ContractError::NotRemovableStatus(status)), result);
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use cosmwasm_std::{StdResult, Storage};
-use cw_storage_plus::Map;
+use cosmwasm_std::{Order, StdResult, Storage};
+use cw_storage_plus::{Map, PrefixBound};
 use seda_common::types::Hash;
 
 pub struct Timeouts<'a> {
@@ -22,18 +22,43 @@ impl Timeouts<'_> {
         Ok(())
     }
 
-    pub fn remove_by_timeout_height(&self, store: &mut dyn Storage, timeout_height: u64) -> StdResult<Vec<Hash>> {
-        // Once the new rust borrow checker is released:
-        // It would allow us to to not allocate all the dr_ids for the timeout_height at once.
+    /// Removes up to `limit` timeouts at or below `timeout_height`, oldest first.
+    /// Returns the removed dr_ids and the next timeout left at or below `timeout_height`, if any,
+    /// from which the following call continues.
+    pub fn remove_up_to_timeout_height(
+        &self,
+        store: &mut dyn Storage,
+        timeout_height: u64,
+        limit: u32,
+    ) -> StdResult<(Vec<Hash>, Option<(u64, Hash)>)> {
+        let mut timed_out = self
+            .timeouts
+            .prefix_range(
+                store,
+                None,
+                Some(PrefixBound::inclusive(timeout_height)),
+                Order::Ascending,
+            )
+            .take(limit as usize + 1)
+            .map(|item| item.map(|(key, _)| key))
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let next = if timed_out.len() > limit as usize {
+            timed_out.pop()
+        } else {
+            None
+        };
 
-        // This call allocates all the dr_ids for the timeout_height at once...
-        let removed = self.get_all_by_timeout_height(store, timeout_height)?;
-        removed.iter().for_each(|hash| {
-            self.timeouts.remove(store, (timeout_height, hash));
-            self.hash_to_timeout.remove(store, hash);
-        });
+        let removed = timed_out
+            .into_iter()
+            .map(|(height, hash)| {
+                self.timeouts.remove(store, (height, &hash));
+                self.hash_to_timeout.remove(store, &hash);
+                hash
+            })
+            .collect();
 
-        Ok(removed)
+        Ok((removed, next))
     }
 
     pub fn get_timeout_by_dr_id(&self, store: &dyn Storage, dr_id: &Hash) -> StdResult<u64> {
@@ -41,6 +66,31 @@ impl Timeouts<'_> {
         Ok(timeout_block)
     }
 
+    /// Returns up to `limit` timeouts from `from_height` to `to_height` inclusive, ordered by height then dr_id.
+    pub fn get_range(
+        &self,
+        store: &dyn Storage,
+        from_height: u64,
+        to_height: u64,
+        limit: u32,
+    ) -> StdResult<Vec<(u64, Hash)>> {
+        self.timeouts
+            .prefix_range(
+                store,
+                Some(PrefixBound::inclusive(from_height)),
+                Some(PrefixBound::inclusive(to_height)),
+                Order::Ascending,
+            )
+            .take(limit as usize)
+            .map(|item| item.map(|(key, _)| key))
+            .collect()
+    }
+
+    pub fn may_get_timeout_by_dr_id(&self, store: &dyn Storage, dr_id: &Hash) -> StdResult<Option<u64>> {
+        self.hash_to_timeout.may_load(store, dr_id)
+    }
+
+    #[cfg(test)]
     pub fn get_all_by_timeout_height(&self, store: &dyn Storage, timeout_height: u64) -> StdResult<Vec<Hash>> {
         let res: StdResult<Vec<_>> = self
             .timeouts
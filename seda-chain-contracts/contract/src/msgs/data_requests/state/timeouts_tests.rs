@@ -28,10 +28,10 @@ impl TestInfo<'_> {
     }
 
     #[track_caller]
-    fn remove_by_timeout_height(&mut self, timeout_height: u64) {
+    fn remove_up_to_timeout_height(&mut self, timeout_height: u64, limit: u32) -> (Vec<Hash>, Option<(u64, Hash)>) {
         self.timeouts
-            .remove_by_timeout_height(&mut self.store, timeout_height)
-            .unwrap();
+            .remove_up_to_timeout_height(&mut self.store, timeout_height, limit)
+            .unwrap()
     }
 
     #[track_caller]
@@ -100,7 +100,7 @@ fn remove_by_dr_id() {
 }
 
 #[test]
-fn remove_by_timeout_height() {
+fn remove_up_to_timeout_height() {
     let mut info = TestInfo::init();
     let timeout = 1000;
     let num = 10;
@@ -111,7 +111,7 @@ fn remove_by_timeout_height() {
     let retrieved = info.get_all_by_timeout_height(timeout);
     assert_eq!(retrieved.len(), num as usize);
 
-    info.remove_by_timeout_height(timeout);
+    info.remove_up_to_timeout_height(timeout, u32::MAX);
     assert_eq!(info.get_all_by_timeout_height(timeout).len(), 0);
 }
 
@@ -156,7 +156,7 @@ fn remove_one_timeout_from_two() {
     let retrieved2 = info.get_all_by_timeout_height(timeout2);
     assert_eq!(retrieved2.len(), num as usize);
 
-    info.remove_by_timeout_height(timeout1);
+    info.remove_up_to_timeout_height(timeout1, u32::MAX);
 
     let retrieved1 = info.get_all_by_timeout_height(timeout1);
     assert_eq!(retrieved1.len(), 0);
@@ -164,3 +164,53 @@ fn remove_one_timeout_from_two() {
     let retrieved2 = info.get_all_by_timeout_height(timeout2);
     assert_eq!(retrieved2.len(), num as usize);
 }
+
+#[test]
+fn remove_up_to_timeout_height_catches_up() {
+    let mut info = TestInfo::init();
+    info.insert(1000, [1; 32]);
+    info.insert(1001, [2; 32]);
+    info.insert(1003, [3; 32]);
+
+    // skipped heights are included, later ones are not
+    let (removed, next) = info.remove_up_to_timeout_height(1002, u32::MAX);
+    assert_eq!(removed, vec![[1; 32], [2; 32]]);
+    assert_eq!(next, None);
+    assert_eq!(info.get_all_by_timeout_height(1003), vec![[3; 32]]);
+}
+
+#[test]
+fn remove_up_to_timeout_height_is_bounded() {
+    let mut info = TestInfo::init();
+    (0..5).for_each(|i| info.insert(1000 + i as u64, [i; 32]));
+
+    // oldest timeouts go first, and the next one left is returned
+    let (removed, next) = info.remove_up_to_timeout_height(1010, 2);
+    assert_eq!(removed, vec![[0; 32], [1; 32]]);
+    assert_eq!(next, Some((1002, [2; 32])));
+
+    let (removed, next) = info.remove_up_to_timeout_height(1010, 2);
+    assert_eq!(removed, vec![[2; 32], [3; 32]]);
+    assert_eq!(next, Some((1004, [4; 32])));
+
+    let (removed, next) = info.remove_up_to_timeout_height(1010, 2);
+    assert_eq!(removed, vec![[4; 32]]);
+    assert_eq!(next, None);
+}
+
+#[test]
+fn get_range() {
+    let mut info = TestInfo::init();
+    info.insert(1000, [2; 32]);
+    info.insert(1000, [1; 32]);
+    info.insert(1001, [3; 32]);
+    info.insert(1003, [4; 32]);
+
+    let range = info.timeouts.get_range(&info.store, 1000, 1001, 10).unwrap();
+    assert_eq!(range, vec![(1000, [1; 32]), (1000, [2; 32]), (1001, [3; 32])]);
+
+    let range = info.timeouts.get_range(&info.store, 1001, 1010, 1).unwrap();
+    assert_eq!(range, vec![(1001, [3; 32])]);
+
+    assert!(info.timeouts.get_range(&info.store, 1004, 1010, 10).unwrap().is_empty());
+}
@@ -1,21 +1,105 @@
-use cosmwasm_std::{to_json_string, DepsMut, Env, Response};
-use seda_common::msgs::data_requests::sudo::expire_data_requests;
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{to_json_string, DepsMut, Env, Event, Response, Storage};
+use seda_common::{
+    msgs::data_requests::{sudo::expire_data_requests, DataRequestStatus},
+    types::{Hash, ToHexStr},
+};
 
 use super::{ContractError, SudoHandler};
-use crate::msgs::data_requests::state;
+use crate::{
+    contract::CONTRACT_VERSION,
+    msgs::{
+        data_requests::state,
+        staking::{
+            execute::{slashing::slash_executor, staking_events::create_executor_event},
+            state::EXTENDED_STAKING_CONFIG,
+        },
+    },
+    state::TOKEN,
+    types::{FromHexStr, PublicKey},
+};
 
 impl SudoHandler for expire_data_requests::Sudo {
-    /// Expires all data requests that have timed out
+    /// Expires the data requests that have timed out at or before the current height
     /// by moving them from whatever state they are in to the tallying state.
+    ///
+    /// At most `max_expired_per_sweep` requests are expired per call, oldest timeout first.
+    /// When more are left, the next timeout to process is returned in the `next_timeout`
+    /// attribute and the following calls pick up from there.
+    ///
+    /// As the chain sends this every block, it also prunes the archived data results
+    /// that are past the retention window, at most `max_pruned_per_sweep` per call.
     fn sudo(self, deps: DepsMut, env: Env) -> Result<Response, ContractError> {
-        let ids = state::expire_data_requests(deps.storage, env.block.height)?;
+        let limit = state::EXTENDED_TIMEOUT_CONFIG.load(deps.storage)?.max_expired_per_sweep;
+        let (expired, next) = state::expire_data_requests(deps.storage, env.block.height, limit)?;
+
+        let mut response = Response::new().add_attribute("method", "expire-data-requests");
+        if let Some((timeout_height, dr_id)) = next {
+            response = response.add_attribute("next_timeout", format!("{timeout_height}:{}", dr_id.to_hex()));
+        }
 
-        let response = Response::new().add_attribute("method", "expire-data-requests");
+        let config = state::DATA_RESULT_CONFIG.load(deps.storage)?;
+        let pruned = state::prune_results(deps.storage, env.block.height, &config)?;
+        if !pruned.is_empty() {
+            response = response.add_event(Event::new("seda-data-results-pruned").add_attributes([
+                (
+                    "dr_ids",
+                    pruned.iter().map(|dr_id| dr_id.to_hex()).collect::<Vec<_>>().join(","),
+                ),
+                ("version", CONTRACT_VERSION.to_string()),
+            ]));
+        }
 
-        if ids.is_empty() {
+        if expired.is_empty() {
             return Ok(response);
         }
 
+        let response = slash_unrevealed_commits(deps.storage, env.block.height, &expired, response)?;
+        let ids = expired.iter().map(|(dr_id, _)| dr_id.to_hex()).collect::<Vec<_>>();
+
         Ok(response.add_attribute("timed_out_drs", to_json_string(&ids)?))
     }
 }
+
+/// Slashes the executors that committed on a data request that timed out
+/// during the reveal stage without revealing.
+fn slash_unrevealed_commits(
+    store: &mut dyn Storage,
+    height: u64,
+    expired: &[(Hash, DataRequestStatus)],
+    mut response: Response,
+) -> Result<Response, ContractError> {
+    let Some(slashing) = EXTENDED_STAKING_CONFIG.load(store)?.slashing else {
+        return Ok(response);
+    };
+    let token = TOKEN.load(store)?;
+
+    let mut slashed_stakers = BTreeMap::new();
+    for (dr_id, _) in expired
+        .iter()
+        .filter(|(_, status)| status == &DataRequestStatus::Revealing)
+    {
+        // committers come in ascending order so the order of the messages is deterministic
+        let no_reveal = state::committers(store, dr_id)?
+            .into_iter()
+            .filter(|committer| !state::has_revealed(store, dr_id, committer))
+            .collect::<Vec<_>>();
+
+        for committer in no_reveal {
+            let public_key = PublicKey::from_hex_str(&committer)?;
+            if let Some((bank_msg, event, staker)) =
+                slash_executor(store, &public_key, &slashing, &token, &dr_id.to_hex(), height)?
+            {
+                response = response.add_message(bank_msg).add_event(event);
+                slashed_stakers.insert(committer, staker);
+            }
+        }
+    }
+
+    for (identity, staker) in slashed_stakers {
+        response = response.add_event(create_executor_event(staker, identity));
+    }
+
+    Ok(response)
+}
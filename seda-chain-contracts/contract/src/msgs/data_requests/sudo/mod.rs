@@ -1,11 +1,19 @@
-use cosmwasm_std::{DepsMut, Env, Response};
-use seda_common::msgs::data_requests::sudo::SudoMsg;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{from_json, Addr, Binary, DepsMut, Env, Event, Reply, Response, SubMsgResult, Uint128};
+use seda_common::msgs::data_requests::sudo::{DistributionMessage, SudoMsg};
 
-use super::{ContractError, SudoHandler};
+use super::{state, ContractError, SudoHandler};
+use crate::{contract::CONTRACT_VERSION, msgs::owner::state::OWNER};
 
 pub(in crate::msgs::data_requests) mod expire_data_requests;
 pub(in crate::msgs::data_requests) mod remove_requests;
 
+/// Reply id of the result callbacks sent when removing data requests.
+pub const RESULT_CALLBACK_REPLY_ID: u64 = 1;
+
+/// Reply id of the CW20 transfers and burns sent when removing data requests.
+pub const CW20_PAYOUT_REPLY_ID: u64 = 2;
+
 impl SudoHandler for SudoMsg {
     fn sudo(self, deps: DepsMut, env: Env) -> Result<Response, ContractError> {
         match self {
@@ -14,3 +22,113 @@ impl SudoHandler for SudoMsg {
         }
     }
 }
+
+/// Sudo messages of the data request module that are specific to this contract.
+#[cw_serde]
+pub enum ContractSudoMsg {
+    /// Removes resolved data requests like `RemoveDataRequests`, along with their final result.
+    /// This is what the chain sends once it has tallied data requests.
+    /// The result is archived for the retention window of the data result config,
+    /// and sent to the callback contract registered for the data request, if any.
+    RemoveDataRequestsWithResults { requests: Vec<ResolvedDataRequest> },
+}
+
+/// A data request to remove, with its final result and the distributions of its escrow.
+#[cw_serde]
+pub struct ResolvedDataRequest {
+    pub dr_id:    String,
+    pub result:   DataRequestResult,
+    pub messages: Vec<DistributionMessage>,
+}
+
+/// The final result of a data request, as tallied by the chain.
+#[cw_serde]
+pub struct DataRequestResult {
+    pub result:    Binary,
+    pub exit_code: u8,
+    pub gas_used:  u64,
+    pub consensus: bool,
+}
+
+/// Message executed on the callback contract of a data request once it resolves.
+/// Consumer contracts accept it as one of their execute messages.
+#[cw_serde]
+pub enum ResultCallbackMsg {
+    SedaDataResult {
+        dr_id:     String,
+        result:    Binary,
+        exit_code: u8,
+        consensus: bool,
+    },
+}
+
+impl SudoHandler for ContractSudoMsg {
+    fn sudo(self, deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        match self {
+            ContractSudoMsg::RemoveDataRequestsWithResults { requests } => {
+                remove_requests::remove_requests_with_results(deps, env, requests)
+            }
+        }
+    }
+}
+
+impl From<ContractSudoMsg> for crate::msgs::SudoMsg {
+    fn from(msg: ContractSudoMsg) -> Self {
+        crate::msgs::ContractSudoMsg::DataRequest(msg).into()
+    }
+}
+
+/// Handles a failed result callback, recording the failure instead of reverting the removal.
+pub fn result_callback_reply(_deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+    let dr_id = String::from_utf8_lossy(&reply.payload).to_string();
+    let error = match reply.result {
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+        SubMsgResult::Err(error) => error,
+    };
+
+    let event = Event::new("seda-result-callback-failed").add_attributes([
+        ("dr_id", dr_id),
+        ("error", error),
+        ("version", CONTRACT_VERSION.to_string()),
+    ]);
+
+    Ok(Response::new().add_event(event))
+}
+
+/// A CW20 transfer or burn sent when removing a data request, carried by its submessage
+/// so that a failed one can be recorded.
+#[cw_serde]
+pub struct Cw20Payout {
+    pub dr_id:         String,
+    pub cw20_contract: Addr,
+    /// Receiver of the tokens, `None` when they are burned.
+    pub recipient:     Option<Addr>,
+    pub amount:        Uint128,
+}
+
+/// Handles a failed CW20 transfer or burn, recording the tokens as claimable instead of reverting the removal.
+/// Transferred tokens are claimable by their recipient, burned ones by the owner, who can burn them later on.
+pub fn cw20_payout_reply(deps: DepsMut, _env: Env, reply: Reply) -> Result<Response, ContractError> {
+    let error = match reply.result {
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+        SubMsgResult::Err(error) => error,
+    };
+
+    let payout: Cw20Payout = from_json(&reply.payload)?;
+    let claimant = match payout.recipient {
+        Some(recipient) => recipient,
+        None => OWNER.load(deps.storage)?,
+    };
+    state::add_failed_cw20_payout(deps.storage, &payout.cw20_contract, &claimant, payout.amount)?;
+
+    let event = Event::new("seda-cw20-payout-failed").add_attributes([
+        ("dr_id", payout.dr_id),
+        ("cw20_contract", payout.cw20_contract.into_string()),
+        ("claimant", claimant.into_string()),
+        ("amount", payout.amount.to_string()),
+        ("error", error),
+        ("version", CONTRACT_VERSION.to_string()),
+    ]);
+
+    Ok(Response::new().add_event(event))
+}
@@ -1,18 +1,30 @@
 use std::collections::HashSet;
 
-use cosmwasm_std::{to_json_binary, BankMsg, Coin, DepsMut, Env, Event, Response, Uint128};
+use cosmwasm_std::{to_json_binary, Addr, DepsMut, Env, Event, Response, StdResult, SubMsg, Uint128, WasmMsg};
 use seda_common::{
     msgs::data_requests::sudo::{remove_requests, DistributionMessage},
     types::{Hash, ToHexStr},
 };
 
-use super::{ContractError, SudoHandler};
+use super::{
+    ContractError,
+    Cw20Payout,
+    DataRequestResult,
+    ResolvedDataRequest,
+    ResultCallbackMsg,
+    SudoHandler,
+    CW20_PAYOUT_REPLY_ID,
+    RESULT_CALLBACK_REPLY_ID,
+};
 use crate::{
     msgs::{
-        data_requests::state::{self, Escrow, DR_ESCROW},
+        data_requests::{
+            execute::post_request::callback_funds,
+            state::{self, DataResult, Escrow, DR_ESCROW},
+        },
         staking::{
             execute::staking_events::create_executor_event,
-            state::{STAKERS, STAKING_CONFIG},
+            state::{add_cw20_reward, STAKERS, STAKING_CONFIG},
         },
         PublicKey,
     },
@@ -20,19 +32,37 @@ use crate::{
     types::FromHexStr,
 };
 
-fn amount_to_tokens(amount: Uint128, token: &str) -> Coin {
-    Coin {
-        denom: token.to_string(),
-        amount,
-    }
+fn burn(amount: Uint128, token: &str, escrow: &mut Escrow, dr_id: &str) -> StdResult<SubMsg> {
+    payout(dr_id, token, escrow, None, amount)
 }
 
-fn burn(amount: Uint128, token: &str, escrow: &mut Escrow) -> BankMsg {
+/// Sends `amount` of the escrow to `recipient`, or burns it when there is none.
+/// CW20 payouts are replied to on failure, so that a CW20 contract rejecting them is recorded
+/// for the recipient to claim instead of reverting the removal of every data request.
+fn payout(
+    dr_id: &str,
+    token: &str,
+    escrow: &mut Escrow,
+    recipient: Option<Addr>,
+    amount: Uint128,
+) -> StdResult<SubMsg> {
+    let msg = match &recipient {
+        Some(recipient) => escrow.transfer_msg(token, recipient.to_string(), amount)?,
+        None => escrow.burn_msg(token, amount)?,
+    };
     escrow.amount = escrow.amount.saturating_sub(amount);
 
-    BankMsg::Burn {
-        amount: vec![amount_to_tokens(amount, token)],
-    }
+    Ok(match &escrow.cw20_contract {
+        Some(cw20_contract) => {
+            SubMsg::reply_on_error(msg, CW20_PAYOUT_REPLY_ID).with_payload(to_json_binary(&Cw20Payout {
+                dr_id: dr_id.to_string(),
+                cw20_contract: cw20_contract.clone(),
+                recipient,
+                amount,
+            })?)
+        }
+        None => SubMsg::new(msg),
+    })
 }
 
 // TODO: use this everywhere we used to build json strings
@@ -45,19 +75,46 @@ macro_rules! json_str {
     };
 }
 
+/// Sends the final result to the callback contract of the data request.
+/// Only failures are replied to, so that a failing callback is recorded instead of reverting the removal.
+fn result_callback(
+    dr_id: &str,
+    callback: state::ResultCallback,
+    result: &DataRequestResult,
+) -> Result<SubMsg, ContractError> {
+    let msg = WasmMsg::Execute {
+        contract_addr: callback.contract.into_string(),
+        msg:           to_json_binary(&ResultCallbackMsg::SedaDataResult {
+            dr_id:     dr_id.to_string(),
+            result:    result.result.clone(),
+            exit_code: result.exit_code,
+            consensus: result.consensus,
+        })?,
+        funds:         vec![],
+    };
+
+    Ok(SubMsg::reply_on_error(msg, RESULT_CALLBACK_REPLY_ID)
+        .with_gas_limit(callback.gas_limit)
+        .with_payload(dr_id.as_bytes().to_vec()))
+}
+
+#[allow(clippy::type_complexity)]
 fn remove_request_and_process_distributions(
     dr_id_str: String,
     messages: &[DistributionMessage],
+    result: Option<&DataRequestResult>,
+    block_height: u64,
     deps: &mut DepsMut,
     token: &str,
     minimum_stake: &Uint128,
-) -> Result<(Event, Vec<BankMsg>, HashSet<PublicKey>, u8), ContractError> {
+) -> Result<(Event, Vec<SubMsg>, Option<SubMsg>, HashSet<PublicKey>, u8), ContractError> {
     let mut event = Event::new("seda-remove-dr");
 
     let Ok(dr_id) = Hash::from_hex_str(&dr_id_str) else {
         return Ok((
             event.add_attribute("invalid_dr_id", dr_id_str),
             vec![],
+            None,
             HashSet::new(),
             1,
         ));
@@ -66,6 +123,7 @@ fn remove_request_and_process_distributions(
         return Ok((
             event.add_attribute("dr_not_found", dr_id_str),
             vec![],
+            None,
             HashSet::new(),
             2,
         ));
@@ -91,21 +149,22 @@ fn remove_request_and_process_distributions(
 
         // Regardless of the message type we first need to get the min of the escrowed amount and the message amount
         // as this will prevent overflows and over-sending of tokens.
+        // Message amounts are in the native token, so CW20 escrows convert them at their rate first.
         match &message {
             DistributionMessage::Burn(distribution_burn) => {
-                let amount_to_burn = distribution_burn.amount.min(dr_escrow.amount);
-                bank_messages.push(burn(amount_to_burn, token, &mut dr_escrow));
+                let amount_to_burn = dr_escrow
+                    .native_to_escrowed(distribution_burn.amount)
+                    .min(dr_escrow.amount);
+                bank_messages.push(burn(amount_to_burn, token, &mut dr_escrow, &dr_id_str)?);
                 event = event.add_attribute("burn", json_str!("amount": amount_to_burn));
             }
             DistributionMessage::DataProxyReward(distribution_send) => {
-                let amount_to_reward = distribution_send.amount.min(dr_escrow.amount);
+                let amount_to_reward = dr_escrow
+                    .native_to_escrowed(distribution_send.amount)
+                    .min(dr_escrow.amount);
 
                 if let Ok(addr) = deps.api.addr_validate(&distribution_send.payout_address) {
-                    bank_messages.push(BankMsg::Send {
-                        to_address: addr.to_string(),
-                        amount:     vec![amount_to_tokens(amount_to_reward, token)],
-                    });
-                    dr_escrow.amount = dr_escrow.amount.saturating_sub(amount_to_reward);
+                    bank_messages.push(payout(&dr_id_str, token, &mut dr_escrow, Some(addr), amount_to_reward)?);
 
                     event = event.add_attribute(
                         "data_proxy_reward",
@@ -115,7 +174,7 @@ fn remove_request_and_process_distributions(
                         ),
                     );
                 } else {
-                    bank_messages.push(burn(amount_to_reward, token, &mut dr_escrow));
+                    bank_messages.push(burn(amount_to_reward, token, &mut dr_escrow, &dr_id_str)?);
                     event = event.add_attribute(
                         "data_proxy_reward_invalid_address",
                         json_str!(
@@ -126,10 +185,12 @@ fn remove_request_and_process_distributions(
                 }
             }
             DistributionMessage::ExecutorReward(distribution_executor_reward) => {
-                let amount_to_reward = distribution_executor_reward.amount.min(dr_escrow.amount);
+                let amount_to_reward = dr_escrow
+                    .native_to_escrowed(distribution_executor_reward.amount)
+                    .min(dr_escrow.amount);
 
                 let Ok(public_key) = PublicKey::from_hex_str(&distribution_executor_reward.identity) else {
-                    bank_messages.push(burn(amount_to_reward, token, &mut dr_escrow));
+                    bank_messages.push(burn(amount_to_reward, token, &mut dr_escrow, &dr_id_str)?);
                     event = event.add_attribute(
                         "executor_reward_invalid_identity",
                         json_str!(
@@ -141,7 +202,7 @@ fn remove_request_and_process_distributions(
                 };
 
                 let Ok(mut staker) = STAKERS.get_staker(deps.storage, &public_key) else {
-                    bank_messages.push(burn(amount_to_reward, token, &mut dr_escrow));
+                    bank_messages.push(burn(amount_to_reward, token, &mut dr_escrow, &dr_id_str)?);
                     event = event.add_attribute(
                         "executor_reward_invalid_identity",
                         json_str!(
@@ -152,6 +213,22 @@ fn remove_request_and_process_distributions(
                     continue 'process_message;
                 };
 
+                // stakes are in the native token, so rewards in CW20 tokens are set aside for the executor
+                if let Some(cw20_contract) = &dr_escrow.cw20_contract {
+                    add_cw20_reward(deps.storage, cw20_contract, &public_key, amount_to_reward)?;
+                    dr_escrow.amount = dr_escrow.amount.saturating_sub(amount_to_reward);
+
+                    event = event.add_attribute(
+                        "executor_cw20_reward",
+                        json_str!(
+                            "amount": amount_to_reward,
+                            "cw20_contract": cw20_contract,
+                            "identity": distribution_executor_reward.identity,
+                        ),
+                    );
+                    continue 'process_message;
+                }
+
                 let (remaining_reward, topped_up) = if &staker.tokens_staked < minimum_stake {
                     // top the staker up to minimum stake from the amount in the reward & escrow
                     let top_up = minimum_stake.saturating_sub(staker.tokens_staked);
@@ -188,49 +265,124 @@ fn remove_request_and_process_distributions(
         }
     }
 
-    if !dr_escrow.amount.is_zero() {
-        bank_messages.push(BankMsg::Send {
-            to_address: dr_escrow.poster.to_string(),
-            amount:     vec![amount_to_tokens(dr_escrow.amount, token)],
-        });
-        event = event.add_attribute("refund", dr_escrow.amount.to_string());
+    // only a data request that was actually removed has its result archived and sent to its callback
+    let removed = state::remove_request(deps.storage, dr_id).is_ok();
+    if !removed {
+        event = event.add_attribute("failed_to_remove_dr", dr_id_str.clone());
     }
+    let result = result.filter(|_| removed);
 
-    if state::remove_request(deps.storage, dr_id).is_err() {
-        event = event.add_attribute("failed_to_remove_dr", dr_id_str);
+    // requests removed without a result drop their callback, refunding its gas with the rest of the escrow
+    let callback = if removed {
+        state::take_result_callback(deps.storage, &dr_id)?
+    } else {
+        None
+    };
+    let callback = match (callback, result) {
+        (Some(callback), Some(result)) => {
+            let callback_fee = callback_funds(callback.gas_limit, dr.gas_price)?.min(dr_escrow.amount);
+            if !callback_fee.is_zero() {
+                bank_messages.push(burn(callback_fee, token, &mut dr_escrow, &dr_id_str)?);
+            }
+            event = event.add_attributes([
+                ("result_callback", callback.contract.to_string()),
+                ("result_callback_burn", json_str!("amount": callback_fee)),
+            ]);
+            Some(result_callback(&dr_id_str, callback, result)?)
+        }
+        _ => None,
     };
-    DR_ESCROW.remove(deps.storage, &dr_id);
 
-    Ok((event, bank_messages, stakers_effected, 0))
+    if !dr_escrow.amount.is_zero() {
+        let refund = dr_escrow.amount;
+        let poster = dr_escrow.poster.clone();
+        bank_messages.push(payout(&dr_id_str, token, &mut dr_escrow, Some(poster), refund)?);
+        event = event.add_attribute("refund", refund.to_string());
+    }
+    state::remove_escrow(deps.storage, &dr_id)?;
+
+    if let Some(result) = result {
+        state::archive_result(
+            deps.storage,
+            &dr_id,
+            &DataResult {
+                dr_id: dr_id_str,
+                result: result.result.clone(),
+                exit_code: result.exit_code,
+                gas_used: result.gas_used,
+                consensus: result.consensus,
+                block_height,
+            },
+        )?;
+    }
+
+    Ok((event, bank_messages, callback, stakers_effected, 0))
 }
 
-impl SudoHandler for remove_requests::Sudo {
-    fn sudo(self, mut deps: DepsMut, _: Env) -> Result<Response, ContractError> {
-        let token = TOKEN.load(deps.storage)?;
-        let minimum_stake = STAKING_CONFIG.load(deps.storage)?.minimum_stake_to_register;
-        let mut response = Response::new();
-
-        let mut all_stakers_effected = HashSet::new();
-        let mut dr_ids_with_status_codes = Vec::new();
-        for (dr_id, removal_details) in self.requests.into_iter().map(|(dr_id, messages)| {
-            (
-                dr_id.clone(),
-                remove_request_and_process_distributions(dr_id, &messages, &mut deps, &token, &minimum_stake),
-            )
-        }) {
-            let (event, bank_messages, stakers_effected, status_code) = removal_details?;
-            all_stakers_effected.extend(stakers_effected);
-            response = response.add_event(event).add_messages(bank_messages);
-            dr_ids_with_status_codes.push((dr_id, status_code));
-        }
+/// Removes the data requests in the given order, responding with the status code of each removal.
+fn remove_requests(
+    mut deps: DepsMut,
+    block_height: u64,
+    requests: impl IntoIterator<Item = (String, Vec<DistributionMessage>, Option<DataRequestResult>)>,
+) -> Result<Response, ContractError> {
+    let token = TOKEN.load(deps.storage)?;
+    let minimum_stake = STAKING_CONFIG.load(deps.storage)?.minimum_stake_to_register;
+    let mut response = Response::new();
 
-        for staker in all_stakers_effected {
-            response = response.add_event(create_executor_event(
-                STAKERS.get_staker(deps.storage, &staker)?,
-                staker.to_hex(),
-            ));
-        }
+    let mut all_stakers_effected = HashSet::new();
+    let mut dr_ids_with_status_codes = Vec::new();
+    for (dr_id, messages, result) in requests {
+        let (event, bank_messages, callback, stakers_effected, status_code) = remove_request_and_process_distributions(
+            dr_id.clone(),
+            &messages,
+            result.as_ref(),
+            block_height,
+            &mut deps,
+            &token,
+            &minimum_stake,
+        )?;
+        all_stakers_effected.extend(stakers_effected);
+        response = response
+            .add_event(event)
+            .add_submessages(bank_messages)
+            .add_submessages(callback);
+        dr_ids_with_status_codes.push((dr_id, status_code));
+    }
 
-        Ok(response.set_data(to_json_binary(&dr_ids_with_status_codes)?))
+    for staker in all_stakers_effected {
+        response = response.add_event(create_executor_event(
+            STAKERS.get_staker(deps.storage, &staker)?,
+            staker.to_hex(),
+        ));
     }
+
+    Ok(response.set_data(to_json_binary(&dr_ids_with_status_codes)?))
+}
+
+impl SudoHandler for remove_requests::Sudo {
+    fn sudo(self, deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        remove_requests(
+            deps,
+            env.block.height,
+            self.requests
+                .into_iter()
+                .map(|(dr_id, messages)| (dr_id, messages, None)),
+        )
+    }
+}
+
+/// Removes resolved data requests, archiving their final results and sending each to its callback contract
+/// if one is registered.
+pub(in crate::msgs::data_requests) fn remove_requests_with_results(
+    deps: DepsMut,
+    env: Env,
+    requests: Vec<ResolvedDataRequest>,
+) -> Result<Response, ContractError> {
+    remove_requests(
+        deps,
+        env.block.height,
+        requests
+            .into_iter()
+            .map(|request| (request.dr_id, request.messages, Some(request.result))),
+    )
 }
@@ -8,7 +8,28 @@ use super::{
     msgs::data_requests::{execute, query, sudo},
     *,
 };
-use crate::{TestExecutor, TestInfo};
+use crate::{
+    msgs::data_requests::{
+        execute::{BatchCommit, BatchItemResult, BatchReveal, ContractExecuteMsg, Cw20HookMsg},
+        query::{
+            ContractQueryMsg,
+            Cw20FeeToken,
+            DataRequestTimeline,
+            DataRequestWithTimeouts,
+            ExecutorActionCheck,
+            ExecutorWorkQueue,
+            ExpiringDataRequest,
+            GetDataRequestSummariesResponse,
+            GetDataRequestsByProgramResponse,
+            GetDataRequestsByStatusCursorResponse,
+            PosterDataRequest,
+        },
+        state::{DataResult, DataResultConfig, ExtendedTimeoutConfig, ProgramKind},
+        sudo::{ContractSudoMsg, ResolvedDataRequest},
+    },
+    TestExecutor,
+    TestInfo,
+};
 
 pub fn calculate_dr_id_and_args(nonce: u128, replication_factor: u16) -> PostDataRequestArgs {
     let exec_program_id = nonce.to_string().hash().to_hex();
@@ -97,7 +118,7 @@ impl TestInfo {
         env_height: u64,
         funds: Option<u128>,
     ) -> Result<String, ContractError> {
-        let msg = execute::post_request::Execute {
+        let msg: msgs::ExecuteMsg = execute::post_request::Execute {
             posted_dr,
             seda_payload: seda_payload.into(),
             payback_address: payback_address.into(),
@@ -120,6 +141,225 @@ impl TestInfo {
         Ok(res.dr_id)
     }
 
+    #[track_caller]
+    pub fn post_data_request_with_timeouts(
+        &mut self,
+        sender: &mut TestExecutor,
+        posted_dr: PostDataRequestArgs,
+        env_height: u64,
+        commit_timeout_in_blocks: Option<u64>,
+        reveal_timeout_in_blocks: Option<u64>,
+    ) -> Result<String, ContractError> {
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::PostDataRequestWithTimeouts {
+            post: execute::post_request::Execute {
+                posted_dr,
+                seda_payload: vec![].into(),
+                payback_address: vec![].into(),
+            },
+            commit_timeout_in_blocks,
+            reveal_timeout_in_blocks,
+        }
+        .into();
+
+        self.set_block_height(env_height);
+        let res: PostRequestResponsePayload = self.execute_with_funds(sender, &msg, 20)?;
+        Ok(res.dr_id)
+    }
+
+    #[track_caller]
+    pub fn post_data_request_with_callback(
+        &mut self,
+        sender: &mut TestExecutor,
+        posted_dr: PostDataRequestArgs,
+        env_height: u64,
+        callback_contract: &Addr,
+        callback_gas_limit: u64,
+        timeouts: (Option<u64>, Option<u64>),
+    ) -> Result<String, ContractError> {
+        // pay for the execution, tally and callback gas
+        let funds = (posted_dr.exec_gas_limit + posted_dr.tally_gas_limit + callback_gas_limit) as u128
+            * posted_dr.gas_price.u128();
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::PostDataRequestWithCallback {
+            post: execute::post_request::Execute {
+                posted_dr,
+                seda_payload: vec![].into(),
+                payback_address: vec![].into(),
+            },
+            callback_contract: callback_contract.to_string(),
+            callback_gas_limit,
+            commit_timeout_in_blocks: timeouts.0,
+            reveal_timeout_in_blocks: timeouts.1,
+        }
+        .into();
+
+        self.set_block_height(env_height);
+        let res: PostRequestResponsePayload = self.execute_with_funds(sender, &msg, funds)?;
+        Ok(res.dr_id)
+    }
+
+    /// Posts a data request paid for with CW20 tokens, sent through the CW20 contract.
+    #[track_caller]
+    pub fn post_data_request_with_cw20(
+        &mut self,
+        sender: &TestExecutor,
+        cw20_contract: &Addr,
+        posted_dr: PostDataRequestArgs,
+        env_height: u64,
+        amount: u128,
+        timeouts: (Option<u64>, Option<u64>),
+    ) -> Result<String, ContractError> {
+        let dr_id = posted_dr.try_hash().unwrap().to_hex();
+        let post = execute::post_request::Execute {
+            posted_dr,
+            seda_payload: vec![].into(),
+            payback_address: vec![].into(),
+        };
+        let msg = match timeouts {
+            (None, None) => Cw20HookMsg::PostDataRequest(post),
+            (commit_timeout_in_blocks, reveal_timeout_in_blocks) => Cw20HookMsg::PostDataRequestWithTimeouts {
+                post,
+                commit_timeout_in_blocks,
+                reveal_timeout_in_blocks,
+            },
+        };
+
+        self.set_block_height(env_height);
+        self.send_cw20::<_, ()>(sender, cw20_contract, amount, &msg)?;
+        Ok(dr_id)
+    }
+
+    #[track_caller]
+    pub fn add_cw20_fee_token(
+        &mut self,
+        sender: &TestExecutor,
+        cw20_contract: &Addr,
+        rate: Decimal,
+    ) -> Result<(), ContractError> {
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::AddCw20FeeToken {
+            cw20_contract: cw20_contract.to_string(),
+            rate,
+        }
+        .into();
+        self.execute(sender, &msg)
+    }
+
+    #[track_caller]
+    pub fn remove_cw20_fee_token(&mut self, sender: &TestExecutor, cw20_contract: &Addr) -> Result<(), ContractError> {
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::RemoveCw20FeeToken {
+            cw20_contract: cw20_contract.to_string(),
+        }
+        .into();
+        self.execute(sender, &msg)
+    }
+
+    #[track_caller]
+    pub fn claim_failed_cw20_payouts(
+        &mut self,
+        sender: &TestExecutor,
+        cw20_contract: &Addr,
+    ) -> Result<(), ContractError> {
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::ClaimFailedCw20Payouts {
+            cw20_contract: cw20_contract.to_string(),
+        }
+        .into();
+        self.execute(sender, &msg)
+    }
+
+    #[track_caller]
+    pub fn get_failed_cw20_payouts(&self, cw20_contract: &Addr, claimant: &Addr) -> u128 {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetFailedCw20Payouts {
+            cw20_contract: cw20_contract.to_string(),
+            claimant:      claimant.to_string(),
+        }
+        .into();
+        self.query::<_, Uint128>(msg).unwrap().u128()
+    }
+
+    #[track_caller]
+    pub fn get_cw20_fee_tokens(&self) -> Vec<Cw20FeeToken> {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetCw20FeeTokens {}.into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn post_data_requests(
+        &mut self,
+        sender: &mut TestExecutor,
+        posted_drs: Vec<PostDataRequestArgs>,
+        env_height: u64,
+        funds: u128,
+        timeouts: (Option<u64>, Option<u64>),
+    ) -> Result<Vec<PostRequestResponsePayload>, ContractError> {
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::PostDataRequests {
+            posts:                    posted_drs
+                .into_iter()
+                .map(|posted_dr| execute::post_request::Execute {
+                    posted_dr,
+                    seda_payload: vec![].into(),
+                    payback_address: vec![].into(),
+                })
+                .collect(),
+            commit_timeout_in_blocks: timeouts.0,
+            reveal_timeout_in_blocks: timeouts.1,
+        }
+        .into();
+
+        self.set_block_height(env_height);
+        self.execute_with_funds(sender, &msg, funds)
+    }
+
+    #[track_caller]
+    pub fn get_data_request_with_timeouts(&self, dr_id: &str) -> Option<DataRequestWithTimeouts> {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetDataRequestWithTimeouts {
+            dr_id: dr_id.to_string(),
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn get_data_request_timeline(&self, dr_id: &str) -> Option<DataRequestTimeline> {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetDataRequestTimeline {
+            dr_id: dr_id.to_string(),
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn get_data_requests_expiring_between(
+        &self,
+        from_height: u64,
+        to_height: u64,
+        limit: u32,
+    ) -> Vec<ExpiringDataRequest> {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetDataRequestsExpiringBetween {
+            from_height,
+            to_height,
+            limit,
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn get_executor_work_queue(
+        &self,
+        executor: &TestExecutor,
+        committing_cursor: Option<String>,
+        revealing_cursor: Option<String>,
+        limit: u32,
+    ) -> ExecutorWorkQueue {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetExecutorWorkQueue {
+            public_key: executor.pub_key_hex(),
+            committing_cursor,
+            revealing_cursor,
+            limit,
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
     #[track_caller]
     pub fn can_executor_commit(&self, sender: &TestExecutor, dr_id: &str, commitment: Hash) -> bool {
         let dr = self.get_data_request(dr_id).unwrap();
@@ -144,6 +384,38 @@ impl TestInfo {
         .unwrap()
     }
 
+    #[track_caller]
+    pub fn can_executor_commit_with_vrf_proof(
+        &self,
+        sender: &TestExecutor,
+        dr_id: &str,
+        commitment: Hash,
+        vrf_proof: Vec<u8>,
+    ) -> bool {
+        let dr = self.get_data_request(dr_id).unwrap();
+        let commitment = commitment.to_hex();
+
+        let factory = execute::commit_result::Execute::factory(
+            dr_id.to_string(),
+            commitment.clone(),
+            sender.pub_key_hex(),
+            self.chain_id(),
+            self.contract_addr_str(),
+            dr.height,
+        );
+        let proof = sender.prove(factory.get_hash());
+
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::CanExecutorCommitWithVrfProof {
+            dr_id: dr_id.to_string(),
+            public_key: sender.pub_key_hex(),
+            commitment,
+            proof: proof.to_hex(),
+            vrf_proof: vrf_proof.to_hex(),
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
     #[track_caller]
     pub fn commit_result(&mut self, sender: &TestExecutor, dr_id: &str, commitment: Hash) -> Result<(), ContractError> {
         let dr = self.get_data_request(dr_id).unwrap();
@@ -163,6 +435,38 @@ impl TestInfo {
         self.execute(sender, &msg)
     }
 
+    #[track_caller]
+    pub fn commit_result_with_vrf_proof(
+        &mut self,
+        sender: &TestExecutor,
+        dr_id: &str,
+        commitment: Hash,
+        vrf_proof: Vec<u8>,
+    ) -> Result<(), ContractError> {
+        let dr = self.get_data_request(dr_id).unwrap();
+        let commitment = commitment.to_hex();
+
+        let factory = execute::commit_result::Execute::factory(
+            dr_id.to_string(),
+            commitment.clone(),
+            sender.pub_key_hex(),
+            self.chain_id(),
+            self.contract_addr_str(),
+            dr.height,
+        );
+        let proof = sender.prove(factory.get_hash());
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::CommitDataResultWithVrfProof {
+            dr_id: dr_id.to_string(),
+            commitment,
+            public_key: sender.pub_key_hex(),
+            proof: proof.to_hex(),
+            vrf_proof: vrf_proof.to_hex(),
+        }
+        .into();
+
+        self.execute(sender, &msg)
+    }
+
     #[track_caller]
     pub fn commit_result_wrong_height(
         &mut self,
@@ -187,6 +491,170 @@ impl TestInfo {
         self.execute(sender, &msg)
     }
 
+    /// Commits to each `(dr_id, commitment)` pair in one batch, returning the outcome per commit.
+    #[track_caller]
+    pub fn batch_commit_results(
+        &mut self,
+        sender: &TestExecutor,
+        commits: Vec<(&str, Hash)>,
+    ) -> Result<Vec<BatchItemResult>, ContractError> {
+        let commits = commits
+            .into_iter()
+            .map(|(dr_id, commitment)| {
+                let dr_height = self.get_data_request(dr_id).map_or(0, |dr| dr.height);
+                let factory = execute::commit_result::Execute::factory(
+                    dr_id.to_string(),
+                    commitment.to_hex(),
+                    sender.pub_key_hex(),
+                    self.chain_id(),
+                    self.contract_addr_str(),
+                    dr_height,
+                );
+                BatchCommit {
+                    dr_id:      dr_id.to_string(),
+                    commitment: commitment.to_hex(),
+                    proof:      sender.prove_hex(factory.get_hash()),
+                    vrf_proof:  None,
+                }
+            })
+            .collect();
+
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::BatchCommitDataResults {
+            public_key: sender.pub_key_hex(),
+            commits,
+        }
+        .into();
+        self.execute(sender, &msg)
+    }
+
+    /// Reveals each of the reveal bodies in one batch, returning the outcome per reveal.
+    #[track_caller]
+    pub fn batch_reveal_results(
+        &mut self,
+        sender: &TestExecutor,
+        reveal_bodies: Vec<RevealBody>,
+    ) -> Result<Vec<BatchItemResult>, ContractError> {
+        let reveals = reveal_bodies
+            .into_iter()
+            .map(|reveal_body| {
+                let dr_height = self.get_data_request(&reveal_body.id).map_or(0, |dr| dr.height);
+                let factory = execute::reveal_result::Execute::factory(
+                    reveal_body.id.clone(),
+                    reveal_body.clone(),
+                    sender.pub_key_hex(),
+                    vec![],
+                    vec![],
+                    self.chain_id(),
+                    self.contract_addr_str(),
+                    dr_height,
+                    reveal_body.try_hash().unwrap(),
+                );
+                BatchReveal {
+                    dr_id: reveal_body.id.clone(),
+                    proof: sender.prove_hex(factory.get_hash()),
+                    reveal_body,
+                    stderr: vec![],
+                    stdout: vec![],
+                }
+            })
+            .collect();
+
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::BatchRevealDataResults {
+            public_key: sender.pub_key_hex(),
+            reveals,
+        }
+        .into();
+        self.execute(sender, &msg)
+    }
+
+    /// Explains whether `sender` can commit, signing the commit for the given data request posting height.
+    #[track_caller]
+    pub fn explain_can_executor_commit(
+        &self,
+        sender: &TestExecutor,
+        dr_id: &str,
+        commitment: Hash,
+        dr_height: u64,
+        vrf_proof: Option<Vec<u8>>,
+    ) -> ExecutorActionCheck {
+        let commitment = commitment.to_hex();
+        let factory = execute::commit_result::Execute::factory(
+            dr_id.to_string(),
+            commitment.clone(),
+            sender.pub_key_hex(),
+            self.chain_id(),
+            self.contract_addr_str(),
+            dr_height,
+        );
+        let proof = sender.prove(factory.get_hash());
+
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::ExplainCanExecutorCommit {
+            dr_id: dr_id.to_string(),
+            public_key: sender.pub_key_hex(),
+            commitment,
+            proof: proof.to_hex(),
+            vrf_proof: vrf_proof.map(|vrf_proof| vrf_proof.to_hex()),
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn explain_can_executor_reveal(&self, dr_id: &str, public_key: &str) -> ExecutorActionCheck {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::ExplainCanExecutorReveal {
+            dr_id:      dr_id.to_string(),
+            public_key: public_key.to_string(),
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn get_data_requests_by_program(
+        &self,
+        kind: ProgramKind,
+        program_id: &str,
+        status: Option<DataRequestStatus>,
+        start_after: Option<String>,
+        limit: u32,
+    ) -> GetDataRequestsByProgramResponse {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetDataRequestsByProgram {
+            kind,
+            program_id: program_id.to_string(),
+            status,
+            start_after,
+            limit,
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn get_data_request_count_by_program(&self, kind: ProgramKind, program_id: &str) -> u32 {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetDataRequestCountByProgram {
+            kind,
+            program_id: program_id.to_string(),
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn get_data_requests_by_poster(
+        &self,
+        poster: &TestExecutor,
+        start_after: Option<String>,
+        limit: u32,
+    ) -> Vec<PosterDataRequest> {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetDataRequestsByPoster {
+            poster: poster.addr().to_string(),
+            start_after,
+            limit,
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
     #[track_caller]
     pub fn can_executor_reveal(&self, dr_id: &str, public_key: &str) -> bool {
         self.query(query::QueryMsg::CanExecutorReveal {
@@ -223,6 +691,38 @@ impl TestInfo {
         self.execute(sender, &msg)
     }
 
+    #[track_caller]
+    pub fn cancel_data_request(&mut self, sender: &TestExecutor, dr_id: &str) -> Result<(), ContractError> {
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::CancelDataRequest {
+            dr_id: dr_id.to_string(),
+        }
+        .into();
+        self.execute(sender, &msg)
+    }
+
+    #[track_caller]
+    pub fn bump_data_request_fee(
+        &mut self,
+        sender: &mut TestExecutor,
+        dr_id: &str,
+        gas_price: u128,
+        gas_limits: (u64, u64),
+        funds: u128,
+    ) -> Result<(), ContractError> {
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::BumpDataRequestFee {
+            dr_id:           dr_id.to_string(),
+            gas_price:       gas_price.into(),
+            exec_gas_limit:  gas_limits.0,
+            tally_gas_limit: gas_limits.1,
+        }
+        .into();
+        if funds == 0 {
+            self.execute(sender, &msg)
+        } else {
+            self.execute_with_funds(sender, &msg, funds)
+        }
+    }
+
     #[track_caller]
     pub fn remove_data_request(
         &mut self,
@@ -231,7 +731,7 @@ impl TestInfo {
     ) -> Result<Vec<(String, u8)>, ContractError> {
         let mut requests = HashMap::new();
         requests.insert(dr_id, msgs);
-        let msg = sudo::remove_requests::Sudo { requests }.into();
+        let msg: msgs::SudoMsg = sudo::remove_requests::Sudo { requests }.into();
         self.sudo(&msg)
     }
 
@@ -240,7 +740,16 @@ impl TestInfo {
         &mut self,
         requests: HashMap<String, Vec<DistributionMessage>>,
     ) -> Result<Vec<(String, u8)>, ContractError> {
-        let msg = sudo::remove_requests::Sudo { requests }.into();
+        let msg: msgs::SudoMsg = sudo::remove_requests::Sudo { requests }.into();
+        self.sudo(&msg)
+    }
+
+    #[track_caller]
+    pub fn remove_data_requests_with_results(
+        &mut self,
+        requests: Vec<ResolvedDataRequest>,
+    ) -> Result<Vec<(String, u8)>, ContractError> {
+        let msg: crate::msgs::SudoMsg = ContractSudoMsg::RemoveDataRequestsWithResults { requests }.into();
         self.sudo(&msg)
     }
 
@@ -277,7 +786,7 @@ impl TestInfo {
 
     #[track_caller]
     pub fn get_data_request_reveals(&self, dr_id: Hash) -> HashMap<String, RevealBody> {
-        self.query(query::QueryMsg::GetDataRequestCommitments { dr_id: dr_id.to_hex() })
+        self.query(query::QueryMsg::GetDataRequestReveals { dr_id: dr_id.to_hex() })
             .unwrap()
     }
 
@@ -292,9 +801,33 @@ impl TestInfo {
             .unwrap()
     }
 
+    #[track_caller]
+    pub fn get_data_requests_by_status_cursor(
+        &self,
+        status: DataRequestStatus,
+        cursor: Option<String>,
+        limit: u32,
+    ) -> Result<GetDataRequestsByStatusCursorResponse, cosmwasm_std::StdError> {
+        let msg: crate::msgs::QueryMsg =
+            ContractQueryMsg::GetDataRequestsByStatusCursor { status, cursor, limit }.into();
+        self.query(msg)
+    }
+
+    #[track_caller]
+    pub fn get_data_request_summaries_by_status(
+        &self,
+        status: DataRequestStatus,
+        cursor: Option<String>,
+        limit: u32,
+    ) -> GetDataRequestSummariesResponse {
+        let msg: crate::msgs::QueryMsg =
+            ContractQueryMsg::GetDataRequestSummariesByStatus { status, cursor, limit }.into();
+        self.query(msg).unwrap()
+    }
+
     #[track_caller]
     pub fn expire_data_requests(&mut self) -> Result<(), ContractError> {
-        let msg = expire_data_requests::Sudo {}.into();
+        let msg: msgs::SudoMsg = expire_data_requests::Sudo {}.into();
         self.sudo(&msg)
     }
 
@@ -304,7 +837,42 @@ impl TestInfo {
         sender: &TestExecutor,
         timeout_config: TimeoutConfig,
     ) -> Result<(), ContractError> {
-        let msg = execute::ExecuteMsg::SetTimeoutConfig(timeout_config).into();
+        let msg: msgs::ExecuteMsg = execute::ExecuteMsg::SetTimeoutConfig(timeout_config).into();
         self.execute(sender, &msg)
     }
+
+    #[track_caller]
+    pub fn set_extended_timeout_config(
+        &mut self,
+        sender: &TestExecutor,
+        config: ExtendedTimeoutConfig,
+    ) -> Result<(), ContractError> {
+        let msg: crate::msgs::ExecuteMsg = config.into();
+        self.execute(sender, &msg)
+    }
+
+    #[track_caller]
+    pub fn set_data_result_config(
+        &mut self,
+        sender: &TestExecutor,
+        config: DataResultConfig,
+    ) -> Result<(), ContractError> {
+        let msg: crate::msgs::ExecuteMsg = config.into();
+        self.execute(sender, &msg)
+    }
+
+    #[track_caller]
+    pub fn get_data_result(&self, dr_id: &str) -> Option<DataResult> {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetDataResult {
+            dr_id: dr_id.to_string(),
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn get_extended_timeout_config(&self) -> ExtendedTimeoutConfig {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetExtendedTimeoutConfig {}.into();
+        self.query(msg).unwrap()
+    }
 }
@@ -6,10 +6,29 @@ use msgs::data_requests::sudo::{
     DistributionExecutorReward,
     DistributionMessage,
 };
-use state::DR_ESCROW;
+use query::{Cw20FeeToken, DataRequestSummary, ExecutorRejection};
+use state::{DataResult, DataResultConfig, ExtendedTimeoutConfig, ProgramKind, DR_ESCROW, DR_RESULT_CALLBACKS};
 
 use super::*;
-use crate::{new_public_key, TestInfo};
+use crate::{
+    consts::{INITIAL_MAX_TIMEOUT_IN_BLOCKS, INITIAL_REVEAL_TIMEOUT_IN_BLOCKS},
+    msgs::{
+        data_requests::sudo::{DataRequestResult, ResolvedDataRequest, ResultCallbackMsg},
+        staking::{
+            query::StakerUnbonding,
+            state::{
+                CommitteeSelection,
+                ExtendedStakingConfig,
+                SlashDestination,
+                SlashingConfig,
+                SlashingPenalty,
+                UnbondingEntry,
+            },
+        },
+    },
+    new_public_key,
+    TestInfo,
+};
 
 #[test]
 fn query_drs_by_status_has_none() {
@@ -446,6 +465,61 @@ fn reveal_result() {
     assert!(revealing.data_requests.iter().any(|r| r.id == dr_id));
 }
 
+#[test]
+fn commits_and_reveals_are_returned_by_queries() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(22));
+    alice.stake(&mut test_info, 1).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(2));
+    bob.stake(&mut test_info, 1).unwrap();
+
+    // post a data request
+    let dr = test_helpers::calculate_dr_id_and_args(1, 2);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+    let dr_hash = Hash::from_hex_str(&dr_id).unwrap();
+
+    // alice and bob commit, alice reveals
+    let alice_reveal = RevealBody {
+        id:                dr_id.clone(),
+        salt:              alice.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    let alice_commitment = alice_reveal.try_hash().unwrap();
+    test_info.commit_result(&alice, &dr_id, alice_commitment).unwrap();
+    let bob_commitment = "bob".hash();
+    test_info.commit_result(&bob, &dr_id, bob_commitment).unwrap();
+    test_info.reveal_result(&alice, &dr_id, alice_reveal.clone()).unwrap();
+
+    let dr = test_info.get_data_request(&dr_id).unwrap();
+    let commits = HashMap::from([
+        (alice.pub_key_hex(), alice_commitment),
+        (bob.pub_key_hex(), bob_commitment),
+    ]);
+    let reveals = HashMap::from([(alice.pub_key_hex(), alice_reveal.clone())]);
+    assert_eq!(commits, dr.commits);
+    assert_eq!(reveals, dr.reveals);
+
+    let revealing = test_info.get_data_requests_by_status(DataRequestStatus::Revealing, 0, 10);
+    assert_eq!(vec![dr], revealing.data_requests);
+
+    assert_eq!(commits, test_info.get_data_request_commits(dr_hash));
+    assert_eq!(reveals, test_info.get_data_request_reveals(dr_hash));
+    assert_eq!(
+        Some(bob_commitment),
+        test_info.get_data_request_commit(dr_hash, bob.pub_key())
+    );
+    assert_eq!(
+        Some(alice_reveal),
+        test_info.get_data_request_reveal(dr_hash, alice.pub_key())
+    );
+    assert_eq!(None, test_info.get_data_request_reveal(dr_hash, bob.pub_key()));
+}
+
 #[test]
 fn reveal_result_with_proxies() {
     let mut test_info = TestInfo::init();
@@ -1541,176 +1615,2237 @@ fn only_owner_can_change_timeout_config() {
 }
 
 #[test]
-pub fn paused_contract_returns_pause_property_dr_query_by_status() {
+fn poster_can_cancel_before_commits() {
     let mut test_info = TestInfo::init();
-    let mut anyone = test_info.new_executor("anyone", Some(22));
-    anyone.stake(&mut test_info, 1).unwrap();
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 1).unwrap();
 
-    // post a data request
-    let dr = crate::msgs::data_requests::test::test_helpers::calculate_dr_id_and_args(1, 1);
-    let _dr_id = test_info
-        .post_data_request(&mut anyone, dr, vec![], vec![], 2, None)
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, Some(20))
         .unwrap();
+    assert_eq!(21, test_info.executor_balance("alice"));
 
-    let drs = test_info.get_data_requests_by_status(DataRequestStatus::Committing, 0, 10);
-    assert!(!drs.is_paused);
-    assert_eq!(1, drs.data_requests.len());
-
-    test_info.pause(&test_info.creator()).unwrap();
-    assert!(test_info.is_paused());
+    // alice cancels and gets the escrow back
+    test_info.cancel_data_request(&alice, &dr_id).unwrap();
+    assert_eq!(41, test_info.executor_balance("alice"));
+    assert_eq!(None, test_info.get_data_request(&dr_id));
+    let committing = test_info.get_data_requests_by_status(DataRequestStatus::Committing, 0, 10);
+    assert!(committing.data_requests.is_empty());
 
-    let drs = test_info.get_data_requests_by_status(DataRequestStatus::Committing, 0, 10);
-    assert!(drs.is_paused);
-    assert_eq!(1, drs.data_requests.len());
+    // the timeout is gone as well
+    test_info.set_block_height(11);
+    test_info.expire_data_requests().unwrap();
+    let tallying = test_info.get_data_requests_by_status(DataRequestStatus::Tallying, 0, 10);
+    assert!(tallying.data_requests.is_empty());
 
-    test_info.unpause(&test_info.creator()).unwrap();
-    assert!(!test_info.is_paused());
+    // the same request can be posted again
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 11, None)
+        .unwrap();
 }
 
 #[test]
-pub fn execute_messages_get_paused() {
+#[should_panic(expected = "NotDataRequestPoster")]
+fn only_poster_can_cancel() {
     let mut test_info = TestInfo::init();
-    let mut alice = test_info.new_executor("alice", Some(1000));
-
-    // register alice as a staker
+    let mut alice = test_info.new_executor("alice", Some(42));
     alice.stake(&mut test_info, 1).unwrap();
+    let bob = test_info.new_executor("bob", Some(2));
 
-    // post a data request we can commit on
-    let dr = crate::msgs::data_requests::test::test_helpers::calculate_dr_id_and_args(1, 1);
-    let dr_id_committable = test_info
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
         .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
         .unwrap();
 
-    // post a data request we can reveal on
-    let dr = crate::msgs::data_requests::test::test_helpers::calculate_dr_id_and_args(2, 1);
-    let dr_id_revealable = test_info
+    test_info.cancel_data_request(&bob, &dr_id).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "DataRequestNotCancellable")]
+fn cannot_cancel_after_commit() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 1).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(2));
+    bob.stake(&mut test_info, 1).unwrap();
+
+    let dr = test_helpers::calculate_dr_id_and_args(1, 2);
+    let dr_id = test_info
         .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
         .unwrap();
-    // commit on it
-    let alice_reveal = RevealBody {
-        id:                dr_id_revealable.clone(),
-        salt:              alice.salt(),
-        reveal:            "10".hash().into(),
-        gas_used:          0,
-        exit_code:         0,
-        proxy_public_keys: vec![],
-    };
-    test_info
-        .commit_result(&alice, &dr_id_revealable, alice_reveal.try_hash().unwrap())
-        .unwrap();
-
-    // pause the contract
-    test_info.pause(&test_info.creator()).unwrap();
-    assert!(test_info.is_paused());
+    test_info.commit_result(&bob, &dr_id, "0xcommitment".hash()).unwrap();
 
-    // try to post another data request
-    let dr = crate::msgs::data_requests::test::test_helpers::calculate_dr_id_and_args(3, 1);
-    let res = test_info.post_data_request(&mut alice, dr, vec![], vec![], 1, None);
-    assert!(res.is_err_and(|e| e.to_string().contains("pause")));
+    test_info.cancel_data_request(&alice, &dr_id).unwrap();
+}
 
-    // try to commit a data result
-    let res = test_info.commit_result(&alice, &dr_id_committable, alice_reveal.try_hash().unwrap());
-    assert!(res.is_err_and(|e| e.to_string().contains("pause")));
+#[test]
+fn poster_can_bump_fee_while_committing() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
 
-    // try to reveal a data result
-    let res = test_info.reveal_result(&alice, &dr_id_revealable, alice_reveal.clone());
-    assert!(res.is_err_and(|e| e.to_string().contains("pause")));
+    // posted with a gas price of 10 and gas limits of 1
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, Some(20))
+        .unwrap();
+    assert_eq!(79, test_info.executor_balance("alice"));
 
-    // can still change the timeout config
-    let timeout_config = TimeoutConfig {
-        commit_timeout_in_blocks: 1,
-        reveal_timeout_in_blocks: 1,
+    // (2 + 2) * 15 = 60 required, 20 already escrowed
+    test_info
+        .bump_data_request_fee(&mut alice, &dr_id, 15, (2, 2), 40)
+        .unwrap();
+    assert_eq!(39, test_info.executor_balance("alice"));
+
+    let dr = test_info.get_data_request(&dr_id).unwrap();
+    assert_eq!(Uint128::new(15), dr.gas_price);
+    assert_eq!(2, dr.exec_gas_limit);
+    assert_eq!(2, dr.tally_gas_limit);
+
+    // the stored arguments are the posted ones, and still hash to the id
+    let posted = state::load_request_as_posted(
+        &*test_info.app().contract_storage(&test_info.contract_addr()),
+        &Hash::from_hex_str(&dr_id).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(Uint128::new(10), posted.gas_price);
+    let args = PostDataRequestArgs {
+        version:            posted.version,
+        exec_program_id:    posted.exec_program_id,
+        exec_inputs:        posted.exec_inputs,
+        exec_gas_limit:     posted.exec_gas_limit,
+        tally_program_id:   posted.tally_program_id,
+        tally_inputs:       posted.tally_inputs,
+        tally_gas_limit:    posted.tally_gas_limit,
+        memo:               posted.memo,
+        replication_factor: posted.replication_factor,
+        consensus_filter:   posted.consensus_filter,
+        gas_price:          posted.gas_price,
     };
+    assert_eq!(dr_id, args.try_hash().unwrap().to_hex());
+
+    // raising only the gas price can be covered by the existing escrow
     test_info
-        .set_timeout_config(&test_info.creator(), timeout_config)
+        .bump_data_request_fee(&mut alice, &dr_id, 15, (2, 2), 0)
         .unwrap();
+
+    // the whole escrow is refunded on cancel
+    test_info.cancel_data_request(&alice, &dr_id).unwrap();
+    assert_eq!(99, test_info.executor_balance("alice"));
 }
 
 #[test]
-fn unstake_before_dr_removal_rewards_staker() {
+fn fee_bump_requires_funds_for_new_values() {
     let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
 
-    // post a data request
-    let mut alice = test_info.new_executor("alice", Some(22));
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, Some(20))
+        .unwrap();
+
+    // (1 + 1) * 20 = 40 required, only 30 escrowed
+    let err = test_info
+        .bump_data_request_fee(&mut alice, &dr_id, 20, (1, 1), 10)
+        .unwrap_err();
+    assert_eq!(err, ContractError::InsufficientFunds(40u128.into(), 30u128.into()));
+
+    // values cannot be lowered
+    let err = test_info
+        .bump_data_request_fee(&mut alice, &dr_id, 20, (0, 1), 20)
+        .unwrap_err();
+    assert_eq!(err, ContractError::FeeBumpLowersFee);
+
+    // only the poster can bump the fee
+    let mut bob = test_info.new_executor("bob", Some(100));
+    let err = test_info
+        .bump_data_request_fee(&mut bob, &dr_id, 20, (1, 1), 20)
+        .unwrap_err();
+    assert_eq!(err, ContractError::NotDataRequestPoster);
+}
 
-    let mut bob = test_info.new_executor("bob", Some(22));
-    bob.stake(&mut test_info, 1).unwrap();
+#[test]
+#[should_panic(expected = "DataRequestNotCommitting")]
+fn cannot_bump_fee_after_committing() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
 
     let dr = test_helpers::calculate_dr_id_and_args(1, 1);
     let dr_id = test_info
         .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
         .unwrap();
+    test_info.commit_result(&alice, &dr_id, "0xcommitment".hash()).unwrap();
 
-    // bob commits a data result
-    let bob_reveal = RevealBody {
-        id:                dr_id.clone(),
-        salt:              bob.salt(),
-        reveal:            "10".hash().into(),
-        gas_used:          0,
-        exit_code:         0,
-        proxy_public_keys: vec![],
-    };
     test_info
-        .commit_result(&bob, &dr_id, bob_reveal.try_hash().unwrap())
+        .bump_data_request_fee(&mut alice, &dr_id, 20, (1, 1), 20)
         .unwrap();
-    test_info.reveal_result(&bob, &dr_id, bob_reveal.clone()).unwrap();
+}
 
-    // bob unstakes before the data request is removed
-    bob.unstake(&mut test_info, 1).unwrap();
+#[test]
+fn poster_chosen_timeouts_are_used() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
 
-    test_info
-        .remove_data_request(
-            dr_id,
-            vec![DistributionMessage::ExecutorReward(DistributionExecutorReward {
-                identity: bob.pub_key_hex(),
-                amount:   5u128.into(),
-            })],
-        )
+    // one request times out while committing, the other one while revealing
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request_with_timeouts(&mut alice, dr, 1, Some(3), Some(5))
+        .unwrap();
+    let dr2 = test_helpers::calculate_dr_id_and_args(2, 1);
+    let dr_id2 = test_info
+        .post_data_request_with_timeouts(&mut alice, dr2, 1, Some(3), None)
         .unwrap();
 
-    // bob should still get the reward
-    // get the staker info for the executor
-    let staker = test_info.get_staker(bob.pub_key()).unwrap();
-    assert_eq!(5, staker.tokens_pending_withdrawal.u128());
+    let with_timeouts = test_info.get_data_request_with_timeouts(&dr_id).unwrap();
+    assert_eq!(3, with_timeouts.commit_timeout_in_blocks);
+    assert_eq!(5, with_timeouts.reveal_timeout_in_blocks);
+    assert_eq!(Some(4), with_timeouts.expiration_height);
+    assert_eq!(test_info.get_data_request(&dr_id), Some(with_timeouts.data_request));
 
-    // bob can withdraw the reward
-    test_info.withdraw(&mut bob, 5).unwrap();
+    // the reveal timeout falls back to the global one
+    test_info.set_block_height(2);
+    test_info.commit_result(&alice, &dr_id2, "0xcommitment".hash()).unwrap();
+    let with_timeouts = test_info.get_data_request_with_timeouts(&dr_id2).unwrap();
+    assert_eq!(INITIAL_REVEAL_TIMEOUT_IN_BLOCKS, with_timeouts.reveal_timeout_in_blocks);
+    assert_eq!(
+        Some(2 + INITIAL_REVEAL_TIMEOUT_IN_BLOCKS),
+        with_timeouts.expiration_height
+    );
+
+    test_info.set_block_height(4);
+    test_info.expire_data_requests().unwrap();
+    let tallying = test_info.get_data_requests_by_status(DataRequestStatus::Tallying, 0, 10);
+    assert_eq!(
+        vec![dr_id.clone()],
+        tallying.data_requests.into_iter().map(|dr| dr.id).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        None,
+        test_info
+            .get_data_request_with_timeouts(&dr_id)
+            .unwrap()
+            .expiration_height
+    );
 }
 
 #[test]
-fn can_reveal_after_unstaking() {
+fn poster_chosen_timeouts_are_clamped() {
     let mut test_info = TestInfo::init();
-    let mut alice = test_info.new_executor("alice", Some(22));
+    let mut alice = test_info.new_executor("alice", Some(100));
     alice.stake(&mut test_info, 1).unwrap();
 
-    // post a data request
+    test_info
+        .set_extended_timeout_config(
+            &test_info.creator(),
+            ExtendedTimeoutConfig {
+                min_timeout_in_blocks: 5,
+                max_timeout_in_blocks: 20,
+                ..test_info.get_extended_timeout_config()
+            },
+        )
+        .unwrap();
+
     let dr = test_helpers::calculate_dr_id_and_args(1, 1);
     let dr_id = test_info
-        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .post_data_request_with_timeouts(&mut alice, dr, 1, Some(1), Some(100))
         .unwrap();
 
-    // alice commits a data result
-    let alice_reveal = RevealBody {
-        id:                dr_id.clone(),
-        salt:              alice.salt(),
-        reveal:            "10".hash().into(),
-        gas_used:          0,
-        exit_code:         0,
-        proxy_public_keys: vec![],
-    };
+    let with_timeouts = test_info.get_data_request_with_timeouts(&dr_id).unwrap();
+    assert_eq!(5, with_timeouts.commit_timeout_in_blocks);
+    assert_eq!(20, with_timeouts.reveal_timeout_in_blocks);
+    assert_eq!(Some(6), with_timeouts.expiration_height);
+}
+
+#[test]
+fn poster_chosen_timeouts_apply_to_every_post_path() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(1000));
+    alice.stake(&mut test_info, 10).unwrap();
+    let consumer = test_info.new_result_consumer();
+    let cw20 = test_info.new_cw20(&[(&alice.addr(), 100)]);
     test_info
-        .commit_result(&alice, &dr_id, alice_reveal.try_hash().unwrap())
+        .add_cw20_fee_token(&test_info.creator(), &cw20, Decimal::one())
         .unwrap();
 
-    // alice unstakes after committing
-    alice.unstake(&mut test_info, 1).unwrap();
+    let dr_id = test_info
+        .post_data_request_with_callback(
+            &mut alice,
+            test_helpers::calculate_dr_id_and_args(1, 1),
+            1,
+            &consumer,
+            10,
+            (Some(3), Some(5)),
+        )
+        .unwrap();
+    let payloads = test_info
+        .post_data_requests(
+            &mut alice,
+            vec![test_helpers::calculate_dr_id_and_args(2, 1)],
+            1,
+            20,
+            (Some(3), Some(5)),
+        )
+        .unwrap();
+    let cw20_dr_id = test_info
+        .post_data_request_with_cw20(
+            &alice,
+            &cw20,
+            test_helpers::calculate_dr_id_and_args(3, 1),
+            1,
+            20,
+            (Some(3), Some(5)),
+        )
+        .unwrap();
 
-    // alice should still be able to reveal
-    test_info.reveal_result(&alice, &dr_id, alice_reveal.clone()).unwrap();
+    for dr_id in [dr_id, payloads[0].dr_id.clone(), cw20_dr_id] {
+        let with_timeouts = test_info.get_data_request_with_timeouts(&dr_id).unwrap();
+        assert_eq!(3, with_timeouts.commit_timeout_in_blocks);
+        assert_eq!(5, with_timeouts.reveal_timeout_in_blocks);
+        assert_eq!(Some(4), with_timeouts.expiration_height);
+    }
+}
 
-    // verify the request moved to tallying state
-    let tallying = test_info.get_data_requests_by_status(DataRequestStatus::Tallying, 0, 10);
-    assert!(!tallying.is_paused);
-    assert_eq!(1, tallying.data_requests.len());
-    assert!(tallying.data_requests.iter().any(|r| r.id == dr_id));
+#[test]
+fn timeout_bounds_must_be_ordered() {
+    let mut test_info = TestInfo::init();
+
+    let err = test_info
+        .set_extended_timeout_config(
+            &test_info.creator(),
+            ExtendedTimeoutConfig {
+                min_timeout_in_blocks: 21,
+                max_timeout_in_blocks: 20,
+                ..test_info.get_extended_timeout_config()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(err, ContractError::InvalidTimeoutBounds(21, 20));
+
+    let err = test_info
+        .set_extended_timeout_config(
+            &test_info.creator(),
+            ExtendedTimeoutConfig {
+                min_timeout_in_blocks: 0,
+                ..test_info.get_extended_timeout_config()
+            },
+        )
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidTimeoutBounds(0, INITIAL_MAX_TIMEOUT_IN_BLOCKS)
+    );
+}
+
+#[test]
+fn data_requests_by_program() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
+
+    let mut dr_ids = Vec::new();
+    for nonce in 1..=3 {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 1);
+        let dr_id = test_info
+            .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+            .unwrap();
+        dr_ids.push(dr_id);
+    }
+    dr_ids.sort();
+
+    let args = test_helpers::calculate_dr_id_and_args(1, 1);
+    assert_eq!(
+        3,
+        test_info.get_data_request_count_by_program(ProgramKind::Tally, &args.tally_program_id)
+    );
+    assert_eq!(
+        1,
+        test_info.get_data_request_count_by_program(ProgramKind::Exec, &args.exec_program_id)
+    );
+    assert_eq!(
+        0,
+        test_info.get_data_request_count_by_program(ProgramKind::Exec, "unknown")
+    );
+
+    // paging through the requests of the tally program
+    let first = test_info.get_data_requests_by_program(ProgramKind::Tally, &args.tally_program_id, None, None, 2);
+    assert_eq!(
+        dr_ids[..2],
+        first.data_requests.iter().map(|dr| dr.id.clone()).collect::<Vec<_>>()
+    );
+    let second = test_info.get_data_requests_by_program(
+        ProgramKind::Tally,
+        &args.tally_program_id,
+        None,
+        first.next_start_after,
+        2,
+    );
+    assert_eq!(
+        vec![dr_ids[2].clone()],
+        second.data_requests.iter().map(|dr| dr.id.clone()).collect::<Vec<_>>()
+    );
+    assert_eq!(None, second.next_start_after);
+
+    // filtering by status
+    test_info
+        .commit_result(&alice, &dr_ids[1], "0xcommitment".hash())
+        .unwrap();
+    let revealing = test_info.get_data_requests_by_program(
+        ProgramKind::Tally,
+        &args.tally_program_id,
+        Some(DataRequestStatus::Revealing),
+        None,
+        10,
+    );
+    assert_eq!(
+        vec![dr_ids[1].clone()],
+        revealing
+            .data_requests
+            .iter()
+            .map(|dr| dr.id.clone())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(1, revealing.data_requests[0].commits.len());
+
+    // removed requests leave the index
+    test_info.set_block_height(20);
+    test_info.expire_data_requests().unwrap();
+    test_info.remove_data_request(dr_ids[0].clone(), vec![]).unwrap();
+    assert_eq!(
+        2,
+        test_info.get_data_request_count_by_program(ProgramKind::Tally, &args.tally_program_id)
+    );
+}
+
+#[test]
+fn data_requests_by_poster() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(100));
+
+    let mut alice_dr_ids = Vec::new();
+    for nonce in 1..=2 {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 1);
+        let dr_id = test_info
+            .post_data_request(&mut alice, dr, vec![], vec![], nonce as u64, None)
+            .unwrap();
+        alice_dr_ids.push(dr_id);
+    }
+    let dr = test_helpers::calculate_dr_id_and_args(3, 1);
+    let bob_dr_id = test_info
+        .post_data_request(&mut bob, dr, vec![], vec![], 3, None)
+        .unwrap();
+
+    let requests = test_info.get_data_requests_by_poster(&alice, None, 10);
+    let mut expected = alice_dr_ids.clone();
+    expected.sort();
+    assert_eq!(expected, requests.iter().map(|dr| dr.dr_id.clone()).collect::<Vec<_>>());
+    assert!(requests.iter().all(|dr| dr.status == DataRequestStatus::Committing
+        && dr.escrowed.u128() == 20
+        && dr.cw20_contract.is_none()));
+
+    // paging resumes after the last id
+    let first = test_info.get_data_requests_by_poster(&alice, None, 1);
+    let second = test_info.get_data_requests_by_poster(&alice, Some(first[0].dr_id.clone()), 1);
+    assert_eq!(
+        vec![expected[1].clone()],
+        second.into_iter().map(|dr| dr.dr_id).collect::<Vec<_>>()
+    );
+
+    // cancelled and removed requests leave the index
+    test_info.cancel_data_request(&alice, &alice_dr_ids[0]).unwrap();
+    test_info
+        .commit_result(&alice, &alice_dr_ids[1], "0xcommitment".hash())
+        .unwrap();
+    let requests = test_info.get_data_requests_by_poster(&alice, None, 10);
+    assert_eq!(1, requests.len());
+    assert_eq!(DataRequestStatus::Revealing, requests[0].status);
+    assert_eq!(2, requests[0].posted_height);
+
+    test_info.set_block_height(20);
+    test_info.expire_data_requests().unwrap();
+    test_info.remove_data_request(alice_dr_ids[1].clone(), vec![]).unwrap();
+    assert!(test_info.get_data_requests_by_poster(&alice, None, 10).is_empty());
+
+    let requests = test_info.get_data_requests_by_poster(&bob, None, 10);
+    assert_eq!(1, requests.len());
+    assert_eq!(bob_dr_id, requests[0].dr_id);
+
+    // an index entry left without its escrow is skipped instead of failing the query
+    let contract_addr = test_info.contract_addr();
+    DR_ESCROW.remove(
+        &mut *test_info.app_mut().contract_storage_mut(&contract_addr),
+        &Hash::from_hex_str(&bob_dr_id).unwrap(),
+    );
+    assert!(test_info.get_data_requests_by_poster(&bob, None, 10).is_empty());
+}
+
+#[test]
+fn explain_can_executor_commit() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(2));
+    bob.stake(&mut test_info, 1).unwrap();
+    let carol = test_info.new_executor("carol", Some(2));
+
+    let missing = "missing".hash().to_hex();
+    let check = test_info.explain_can_executor_commit(&alice, &missing, "0xcommitment".hash(), 1, None);
+    assert_eq!(Some(ExecutorRejection::NotFound), check.reason);
+    assert!(!check.allowed);
+
+    let dr = test_helpers::calculate_dr_id_and_args(1, 2);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    let check = test_info.explain_can_executor_commit(&alice, &dr_id, "0xcommitment".hash(), 1, None);
+    assert!(check.allowed);
+    assert_eq!(None, check.reason);
+
+    // signed for the wrong posting height
+    let check = test_info.explain_can_executor_commit(&alice, &dr_id, "0xcommitment".hash(), 2, None);
+    assert_eq!(Some(ExecutorRejection::InvalidProof), check.reason);
+
+    // a proof that is not even hex is bad input rather than a bad proof
+    let msg: crate::msgs::QueryMsg = query::ContractQueryMsg::ExplainCanExecutorCommit {
+        dr_id:      dr_id.clone(),
+        public_key: alice.pub_key_hex(),
+        commitment: "0xcommitment".hash().to_hex(),
+        proof:      "not a proof".to_string(),
+        vrf_proof:  None,
+    }
+    .into();
+    let check: query::ExecutorActionCheck = test_info.query(msg).unwrap();
+    assert_eq!(Some(ExecutorRejection::InvalidInput), check.reason);
+
+    // carol never staked
+    let check = test_info.explain_can_executor_commit(&carol, &dr_id, "0xcommitment".hash(), 1, None);
+    assert_eq!(
+        Some(ExecutorRejection::InsufficientStake {
+            required: 1u8.into(),
+            actual:   0u8.into(),
+        }),
+        check.reason
+    );
+
+    test_info.commit_result(&alice, &dr_id, "0xcommitment".hash()).unwrap();
+    let check = test_info.explain_can_executor_commit(&alice, &dr_id, "0xcommitment".hash(), 1, None);
+    assert_eq!(Some(ExecutorRejection::AlreadyCommitted), check.reason);
+
+    // bob is dropped from the allowlist once it is enabled
+    test_info
+        .set_staking_config(
+            &test_info.creator(),
+            StakingConfig {
+                minimum_stake_to_register:               1u8.into(),
+                minimum_stake_for_committee_eligibility: 1u8.into(),
+                allowlist_enabled:                       true,
+            },
+        )
+        .unwrap();
+    let check = test_info.explain_can_executor_commit(&bob, &dr_id, "0xcommitment".hash(), 1, None);
+    assert_eq!(Some(ExecutorRejection::NotOnAllowlist), check.reason);
+
+    test_info.set_block_height(11);
+    let check = test_info.explain_can_executor_commit(&bob, &dr_id, "0xcommitment".hash(), 1, None);
+    assert_eq!(Some(ExecutorRejection::Expired { height: 11 }), check.reason);
+}
+
+#[test]
+fn explain_can_executor_reveal() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(2));
+    bob.stake(&mut test_info, 1).unwrap();
+    let carol = test_info.new_executor("carol", Some(2));
+
+    let missing = "missing".hash().to_hex();
+    let check = test_info.explain_can_executor_reveal(&missing, &alice.pub_key_hex());
+    assert_eq!(Some(ExecutorRejection::NotFound), check.reason);
+
+    let dr = test_helpers::calculate_dr_id_and_args(1, 2);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    let alice_reveal = RevealBody {
+        id:                dr_id.clone(),
+        salt:              alice.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    test_info
+        .commit_result(&alice, &dr_id, alice_reveal.try_hash().unwrap())
+        .unwrap();
+    let check = test_info.explain_can_executor_reveal(&dr_id, &alice.pub_key_hex());
+    assert_eq!(Some(ExecutorRejection::RevealNotStarted), check.reason);
+
+    test_info.commit_result(&bob, &dr_id, "0xcommitment".hash()).unwrap();
+    let check = test_info.explain_can_executor_reveal(&dr_id, &alice.pub_key_hex());
+    assert!(check.allowed);
+    let check = test_info.explain_can_executor_reveal(&dr_id, &carol.pub_key_hex());
+    assert_eq!(Some(ExecutorRejection::NotCommitted), check.reason);
+
+    test_info.reveal_result(&alice, &dr_id, alice_reveal).unwrap();
+    let check = test_info.explain_can_executor_reveal(&dr_id, &alice.pub_key_hex());
+    assert_eq!(Some(ExecutorRejection::AlreadyRevealed), check.reason);
+
+    test_info.set_block_height(11);
+    let check = test_info.explain_can_executor_reveal(&dr_id, &bob.pub_key_hex());
+    assert_eq!(Some(ExecutorRejection::Expired { height: 11 }), check.reason);
+}
+
+#[test]
+fn executor_work_queue() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(2));
+    bob.stake(&mut test_info, 1).unwrap();
+
+    let mut dr_ids = Vec::new();
+    for nonce in 1..=3 {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 2);
+        let dr_id = test_info
+            .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+            .unwrap();
+        dr_ids.push(dr_id);
+    }
+
+    // both executors commit to the first request, moving it to revealing
+    test_info.set_block_height(2);
+    let alice_reveal = RevealBody {
+        id:                dr_ids[0].clone(),
+        salt:              alice.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    test_info
+        .commit_result(&alice, &dr_ids[0], alice_reveal.try_hash().unwrap())
+        .unwrap();
+    test_info
+        .commit_result(&bob, &dr_ids[0], "0xcommitment".hash())
+        .unwrap();
+    // alice also commits to the second request
+    test_info
+        .commit_result(&alice, &dr_ids[1], "0xcommitment".hash())
+        .unwrap();
+
+    let queue = test_info.get_executor_work_queue(&alice, None, None, 10);
+    assert_eq!(
+        vec![dr_ids[2].clone()],
+        queue
+            .committing
+            .iter()
+            .map(|item| item.dr_id.clone())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(11, queue.committing[0].deadline);
+    assert_eq!(1, queue.revealing.len());
+    assert_eq!(dr_ids[0], queue.revealing[0].dr_id);
+    assert_eq!(12, queue.revealing[0].deadline);
+    assert_eq!(None, queue.next_committing_cursor);
+    assert_eq!(None, queue.next_revealing_cursor);
+
+    // once alice reveals only bob still has to
+    test_info.reveal_result(&alice, &dr_ids[0], alice_reveal).unwrap();
+    assert!(test_info
+        .get_executor_work_queue(&alice, None, None, 10)
+        .revealing
+        .is_empty());
+    let queue = test_info.get_executor_work_queue(&bob, None, None, 10);
+    assert_eq!(dr_ids[0], queue.revealing[0].dr_id);
+    assert_eq!(2, queue.committing.len());
+
+    // paging goes through the committing requests one at a time
+    let first = test_info.get_executor_work_queue(&bob, None, None, 1);
+    assert_eq!(1, first.committing.len());
+    let second = test_info.get_executor_work_queue(&bob, first.next_committing_cursor, None, 1);
+    assert_eq!(1, second.committing.len());
+    assert_ne!(first.committing[0].dr_id, second.committing[0].dr_id);
+    assert_eq!(None, second.next_committing_cursor);
+
+    // executors that cannot commit get no committing work
+    let carol = test_info.new_executor("carol", Some(2));
+    assert!(test_info
+        .get_executor_work_queue(&carol, None, None, 10)
+        .committing
+        .is_empty());
+
+    // requests past their deadline are no longer work, even before they are expired
+    test_info.set_block_height(11);
+    let queue = test_info.get_executor_work_queue(&bob, None, None, 10);
+    assert!(queue.committing.is_empty());
+    assert_eq!(dr_ids[0], queue.revealing[0].dr_id);
+    test_info.set_block_height(12);
+    assert!(test_info
+        .get_executor_work_queue(&bob, None, None, 10)
+        .revealing
+        .is_empty());
+    assert_eq!(
+        2,
+        test_info
+            .get_data_requests_by_status(DataRequestStatus::Committing, 0, 10)
+            .data_requests
+            .len()
+    );
+}
+
+#[test]
+fn data_request_timeline() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
+
+    assert_eq!(None, test_info.get_data_request_timeline(&"missing".hash().to_hex()));
+
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    test_info.set_block_height(4);
+    let timeline = test_info.get_data_request_timeline(&dr_id).unwrap();
+    assert_eq!(DataRequestStatus::Committing, timeline.status);
+    assert_eq!(1, timeline.posted_height);
+    assert_eq!(Some(11), timeline.stage_deadline);
+    assert_eq!(Some(7), timeline.blocks_remaining);
+
+    // committing moves the deadline to the reveal timeout
+    test_info.commit_result(&alice, &dr_id, "0xcommitment".hash()).unwrap();
+    let timeline = test_info.get_data_request_timeline(&dr_id).unwrap();
+    assert_eq!(DataRequestStatus::Revealing, timeline.status);
+    assert_eq!(Some(14), timeline.stage_deadline);
+    assert_eq!(Some(10), timeline.blocks_remaining);
+
+    // a timed out request has no deadline left
+    test_info.set_block_height(20);
+    test_info.expire_data_requests().unwrap();
+    let timeline = test_info.get_data_request_timeline(&dr_id).unwrap();
+    assert_eq!(DataRequestStatus::Tallying, timeline.status);
+    assert_eq!(None, timeline.stage_deadline);
+    assert_eq!(None, timeline.blocks_remaining);
+}
+
+#[test]
+fn data_requests_expiring_between() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 1).unwrap();
+
+    // timing out at 11, 12 and 13
+    let mut dr_ids = Vec::new();
+    for nonce in 1..=3 {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 1);
+        let dr_id = test_info
+            .post_data_request(&mut alice, dr, vec![], vec![], nonce as u64, None)
+            .unwrap();
+        dr_ids.push(dr_id);
+    }
+
+    let expiring = test_info.get_data_requests_expiring_between(12, 20, 10);
+    assert_eq!(
+        vec![(dr_ids[1].clone(), 12), (dr_ids[2].clone(), 13)],
+        expiring
+            .into_iter()
+            .map(|expiring| (expiring.dr_id, expiring.expiration_height))
+            .collect::<Vec<_>>()
+    );
+
+    let expiring = test_info.get_data_requests_expiring_between(0, 20, 1);
+    assert_eq!(1, expiring.len());
+    assert_eq!(dr_ids[0], expiring[0].dr_id);
+
+    assert!(test_info.get_data_requests_expiring_between(14, 20, 10).is_empty());
+}
+
+#[test]
+fn expire_catches_up_on_skipped_heights() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 1).unwrap();
+
+    // post data requests that time out at heights 11 and 12
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+    test_info.set_block_height(2);
+    let dr2 = test_helpers::calculate_dr_id_and_args(2, 1);
+    let dr_id2 = test_info
+        .post_data_request(&mut alice, dr2, vec![], vec![], 2, None)
+        .unwrap();
+
+    // no sweep ran at heights 11 and 12
+    test_info.set_block_height(15);
+    test_info.expire_data_requests().unwrap();
+
+    let tallying = test_info
+        .get_data_requests_by_status(DataRequestStatus::Tallying, 0, 10)
+        .data_requests
+        .into_iter()
+        .map(|r| r.id)
+        .collect::<Vec<_>>();
+    assert_eq!(tallying, vec![dr_id, dr_id2]);
+}
+
+#[test]
+fn expire_is_bounded_per_sweep() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 1).unwrap();
+
+    test_info
+        .set_extended_timeout_config(
+            &test_info.creator(),
+            ExtendedTimeoutConfig {
+                max_expired_per_sweep: 2,
+                ..test_info.get_extended_timeout_config()
+            },
+        )
+        .unwrap();
+    assert_eq!(2, test_info.get_extended_timeout_config().max_expired_per_sweep);
+
+    for nonce in 1..=5 {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 1);
+        test_info
+            .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+            .unwrap();
+    }
+
+    // each sweep expires at most 2 requests, the next ones continue where it stopped
+    test_info.set_block_height(11);
+    for expected in [2, 4, 5, 5] {
+        test_info.expire_data_requests().unwrap();
+        let tallying = test_info.get_data_requests_by_status(DataRequestStatus::Tallying, 0, 10);
+        assert_eq!(expected, tallying.data_requests.len());
+    }
+    let committing = test_info.get_data_requests_by_status(DataRequestStatus::Committing, 0, 10);
+    assert!(committing.data_requests.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "NotOwner")]
+fn only_owner_can_change_extended_timeout_config() {
+    let mut test_info = TestInfo::init();
+
+    let alice = test_info.new_executor("alice", Some(2));
+    test_info
+        .set_extended_timeout_config(
+            &alice,
+            ExtendedTimeoutConfig {
+                max_expired_per_sweep: 1,
+                ..test_info.get_extended_timeout_config()
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+#[should_panic(expected = "ZeroMaxExpiredPerSweep")]
+fn max_expired_per_sweep_cannot_be_zero() {
+    let mut test_info = TestInfo::init();
+
+    test_info
+        .set_extended_timeout_config(
+            &test_info.creator(),
+            ExtendedTimeoutConfig {
+                max_expired_per_sweep: 0,
+                ..test_info.get_extended_timeout_config()
+            },
+        )
+        .unwrap();
+}
+
+#[test]
+pub fn paused_contract_returns_pause_property_dr_query_by_status() {
+    let mut test_info = TestInfo::init();
+    let mut anyone = test_info.new_executor("anyone", Some(22));
+    anyone.stake(&mut test_info, 1).unwrap();
+
+    // post a data request
+    let dr = crate::msgs::data_requests::test::test_helpers::calculate_dr_id_and_args(1, 1);
+    let _dr_id = test_info
+        .post_data_request(&mut anyone, dr, vec![], vec![], 2, None)
+        .unwrap();
+
+    let drs = test_info.get_data_requests_by_status(DataRequestStatus::Committing, 0, 10);
+    assert!(!drs.is_paused);
+    assert_eq!(1, drs.data_requests.len());
+
+    test_info.pause(&test_info.creator()).unwrap();
+    assert!(test_info.is_paused());
+
+    let drs = test_info.get_data_requests_by_status(DataRequestStatus::Committing, 0, 10);
+    assert!(drs.is_paused);
+    assert_eq!(1, drs.data_requests.len());
+
+    test_info.unpause(&test_info.creator()).unwrap();
+    assert!(!test_info.is_paused());
+}
+
+#[test]
+pub fn execute_messages_get_paused() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(1000));
+
+    // register alice as a staker
+    alice.stake(&mut test_info, 1).unwrap();
+
+    // post a data request we can commit on
+    let dr = crate::msgs::data_requests::test::test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id_committable = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // post a data request we can reveal on
+    let dr = crate::msgs::data_requests::test::test_helpers::calculate_dr_id_and_args(2, 1);
+    let dr_id_revealable = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+    // commit on it
+    let alice_reveal = RevealBody {
+        id:                dr_id_revealable.clone(),
+        salt:              alice.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    test_info
+        .commit_result(&alice, &dr_id_revealable, alice_reveal.try_hash().unwrap())
+        .unwrap();
+
+    // pause the contract
+    test_info.pause(&test_info.creator()).unwrap();
+    assert!(test_info.is_paused());
+
+    // try to post another data request
+    let dr = crate::msgs::data_requests::test::test_helpers::calculate_dr_id_and_args(3, 1);
+    let res = test_info.post_data_request(&mut alice, dr, vec![], vec![], 1, None);
+    assert!(res.is_err_and(|e| e.to_string().contains("pause")));
+
+    // try to commit a data result
+    let res = test_info.commit_result(&alice, &dr_id_committable, alice_reveal.try_hash().unwrap());
+    assert!(res.is_err_and(|e| e.to_string().contains("pause")));
+
+    // try to reveal a data result
+    let res = test_info.reveal_result(&alice, &dr_id_revealable, alice_reveal.clone());
+    assert!(res.is_err_and(|e| e.to_string().contains("pause")));
+
+    // can still change the timeout config
+    let timeout_config = TimeoutConfig {
+        commit_timeout_in_blocks: 1,
+        reveal_timeout_in_blocks: 1,
+    };
+    test_info
+        .set_timeout_config(&test_info.creator(), timeout_config)
+        .unwrap();
+}
+
+#[test]
+fn unstake_before_dr_removal_rewards_staker() {
+    let mut test_info = TestInfo::init();
+
+    // post a data request
+    let mut alice = test_info.new_executor("alice", Some(22));
+
+    let mut bob = test_info.new_executor("bob", Some(22));
+    bob.stake(&mut test_info, 1).unwrap();
+
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // bob commits a data result
+    let bob_reveal = RevealBody {
+        id:                dr_id.clone(),
+        salt:              bob.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    test_info
+        .commit_result(&bob, &dr_id, bob_reveal.try_hash().unwrap())
+        .unwrap();
+    test_info.reveal_result(&bob, &dr_id, bob_reveal.clone()).unwrap();
+
+    // bob unstakes before the data request is removed
+    bob.unstake(&mut test_info, 1).unwrap();
+
+    test_info
+        .remove_data_request(
+            dr_id,
+            vec![DistributionMessage::ExecutorReward(DistributionExecutorReward {
+                identity: bob.pub_key_hex(),
+                amount:   5u128.into(),
+            })],
+        )
+        .unwrap();
+
+    // bob should still get the reward
+    // get the staker info for the executor
+    let staker = test_info.get_staker(bob.pub_key()).unwrap();
+    assert_eq!(5, staker.tokens_pending_withdrawal.u128());
+
+    // bob can withdraw the reward
+    test_info.withdraw(&mut bob, 5).unwrap();
+}
+
+#[test]
+fn can_reveal_after_unstaking() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(22));
+    alice.stake(&mut test_info, 1).unwrap();
+
+    // post a data request
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // alice commits a data result
+    let alice_reveal = RevealBody {
+        id:                dr_id.clone(),
+        salt:              alice.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    test_info
+        .commit_result(&alice, &dr_id, alice_reveal.try_hash().unwrap())
+        .unwrap();
+
+    // alice unstakes after committing
+    alice.unstake(&mut test_info, 1).unwrap();
+
+    // alice should still be able to reveal
+    test_info.reveal_result(&alice, &dr_id, alice_reveal.clone()).unwrap();
+
+    // verify the request moved to tallying state
+    let tallying = test_info.get_data_requests_by_status(DataRequestStatus::Tallying, 0, 10);
+    assert!(!tallying.is_paused);
+    assert_eq!(1, tallying.data_requests.len());
+    assert!(tallying.data_requests.iter().any(|r| r.id == dr_id));
+}
+
+#[test]
+fn executors_that_do_not_reveal_get_slashed() {
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   Some(SlashingConfig {
+                    penalty:     SlashingPenalty::Fraction(Decimal::percent(10)),
+                    destination: SlashDestination::Burn,
+                }),
+                committee_selection:        CommitteeSelection::Window,
+            },
+        )
+        .unwrap();
+
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 20).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(42));
+    bob.stake(&mut test_info, 20).unwrap();
+
+    // post a data request
+    let dr = test_helpers::calculate_dr_id_and_args(1, 2);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // alice and bob commit, only alice reveals
+    let alice_reveal = RevealBody {
+        id:                dr_id.clone(),
+        salt:              alice.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    let bob_reveal = RevealBody {
+        salt: bob.salt(),
+        ..alice_reveal.clone()
+    };
+    test_info
+        .commit_result(&alice, &dr_id, alice_reveal.try_hash().unwrap())
+        .unwrap();
+    test_info
+        .commit_result(&bob, &dr_id, bob_reveal.try_hash().unwrap())
+        .unwrap();
+    test_info.reveal_result(&alice, &dr_id, alice_reveal).unwrap();
+
+    // the request times out during the reveal stage
+    test_info.set_block_height(11);
+    test_info.expire_data_requests().unwrap();
+
+    // only bob is slashed
+    assert_eq!(20, test_info.get_staker(alice.pub_key()).unwrap().tokens_staked.u128());
+    assert_eq!(18, test_info.get_staker(bob.pub_key()).unwrap().tokens_staked.u128());
+}
+
+#[test]
+fn slashed_funds_can_go_to_treasury() {
+    let mut test_info = TestInfo::init();
+    let treasury = test_info.new_executor("treasury", None);
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   Some(SlashingConfig {
+                    penalty:     SlashingPenalty::Fixed(3u128.into()),
+                    destination: SlashDestination::Treasury {
+                        address: treasury.addr().into_string(),
+                    },
+                }),
+                committee_selection:        CommitteeSelection::Window,
+            },
+        )
+        .unwrap();
+
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 20).unwrap();
+
+    // post a data request
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // alice commits but never reveals
+    test_info.commit_result(&alice, &dr_id, "0xcommitment".hash()).unwrap();
+
+    test_info.set_block_height(11);
+    test_info.expire_data_requests().unwrap();
+
+    assert_eq!(17, test_info.get_staker(alice.pub_key()).unwrap().tokens_staked.u128());
+    assert_eq!(3, test_info.executor_balance("treasury"));
+}
+
+#[test]
+fn unstaking_after_commit_does_not_escape_slashing() {
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 100,
+                slashing:                   Some(SlashingConfig {
+                    penalty:     SlashingPenalty::Fixed(3u128.into()),
+                    destination: SlashDestination::Burn,
+                }),
+                committee_selection:        CommitteeSelection::Window,
+            },
+        )
+        .unwrap();
+
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 20).unwrap();
+
+    // post a data request
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // alice commits, unstakes everything and never reveals
+    test_info.commit_result(&alice, &dr_id, "0xcommitment".hash()).unwrap();
+    alice.unstake(&mut test_info, 20).unwrap();
+
+    test_info.set_block_height(11);
+    test_info.expire_data_requests().unwrap();
+
+    // the penalty comes out of the tokens that are still unbonding
+    let staker = test_info.get_staker(alice.pub_key()).unwrap();
+    assert_eq!(0, staker.tokens_staked.u128());
+    assert_eq!(17, staker.tokens_pending_withdrawal.u128());
+    assert_eq!(
+        test_info.get_staker_unbonding(alice.pub_key()),
+        Some(StakerUnbonding {
+            withdrawable: 0u8.into(),
+            unbonding:    vec![UnbondingEntry {
+                amount:          17u8.into(),
+                withdrawable_at: 101,
+            }],
+        })
+    );
+}
+
+#[test]
+fn executors_slashed_to_zero_are_removed() {
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   Some(SlashingConfig {
+                    penalty:     SlashingPenalty::Fraction(Decimal::one()),
+                    destination: SlashDestination::Burn,
+                }),
+                committee_selection:        CommitteeSelection::Window,
+            },
+        )
+        .unwrap();
+
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 20).unwrap();
+
+    // post a data request
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // alice commits but never reveals, and loses the whole stake
+    test_info.commit_result(&alice, &dr_id, "0xcommitment".hash()).unwrap();
+
+    test_info.set_block_height(11);
+    test_info.expire_data_requests().unwrap();
+
+    // alice is no longer a staker, nor eligible for committees
+    assert_eq!(None, test_info.get_staker(alice.pub_key()));
+    let dr = test_helpers::calculate_dr_id_and_args(2, 1);
+    let res = test_info.post_data_request(&mut alice, dr, vec![], vec![], 11, None);
+    assert!(res.is_err_and(|x| x == ContractError::DataRequestReplicationFactorTooHigh(0)));
+}
+
+#[test]
+fn no_slashing_when_timing_out_during_commit() {
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   Some(SlashingConfig {
+                    penalty:     SlashingPenalty::Fixed(3u128.into()),
+                    destination: SlashDestination::Burn,
+                }),
+                committee_selection:        CommitteeSelection::Window,
+            },
+        )
+        .unwrap();
+
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 20).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(42));
+    bob.stake(&mut test_info, 20).unwrap();
+
+    // post a data request
+    let dr = test_helpers::calculate_dr_id_and_args(1, 2);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // only alice commits so the request never reaches the reveal stage
+    test_info.commit_result(&alice, &dr_id, "0xcommitment".hash()).unwrap();
+
+    test_info.set_block_height(11);
+    test_info.expire_data_requests().unwrap();
+
+    assert_eq!(20, test_info.get_staker(alice.pub_key()).unwrap().tokens_staked.u128());
+}
+
+#[test]
+fn slashing_fraction_cannot_exceed_one() {
+    let mut test_info = TestInfo::init();
+
+    let res = test_info.set_extended_staking_config(
+        &test_info.creator(),
+        ExtendedStakingConfig {
+            unbonding_period_in_blocks: 0,
+            slashing:                   Some(SlashingConfig {
+                penalty:     SlashingPenalty::Fraction(Decimal::percent(101)),
+                destination: SlashDestination::Burn,
+            }),
+            committee_selection:        CommitteeSelection::Window,
+        },
+    );
+    assert!(res.is_err_and(|x| x == ContractError::InvalidSlashingFraction(Decimal::percent(101))));
+}
+
+#[test]
+fn vrf_commit_requires_valid_proof() {
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   None,
+                committee_selection:        CommitteeSelection::Vrf,
+            },
+        )
+        .unwrap();
+
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 10).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(42));
+    bob.stake(&mut test_info, 10).unwrap();
+
+    // with a replication factor covering every executor both are selected
+    let dr = test_helpers::calculate_dr_id_and_args(1, 2);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // committing without a VRF proof fails
+    let res = test_info.commit_result(&alice, &dr_id, "0xcommitment".hash());
+    assert!(res.is_err_and(|x| x == ContractError::MissingVrfProof));
+
+    // committing with someone else's VRF proof fails
+    let bob_proof = test_info.committee_selection_proof(&bob, &dr_id);
+    let res = test_info.commit_result_with_vrf_proof(&alice, &dr_id, "0xcommitment".hash(), bob_proof.clone());
+    assert!(res.is_err_and(|x| x == ContractError::InvalidVrfProof));
+
+    // the queries tell the missing and invalid proofs apart
+    let check = test_info.explain_can_executor_commit(&alice, &dr_id, "0xcommitment".hash(), 1, None);
+    assert_eq!(Some(ExecutorRejection::MissingVrfProof), check.reason);
+    let check =
+        test_info.explain_can_executor_commit(&alice, &dr_id, "0xcommitment".hash(), 1, Some(bob_proof.clone()));
+    assert_eq!(Some(ExecutorRejection::InvalidVrfProof), check.reason);
+    assert!(!test_info.can_executor_commit(&alice, &dr_id, "0xcommitment".hash()));
+    assert!(!test_info.can_executor_commit_with_vrf_proof(&alice, &dr_id, "0xcommitment".hash(), bob_proof));
+
+    // committing with their own VRF proof works
+    let alice_proof = test_info.committee_selection_proof(&alice, &dr_id);
+    let check =
+        test_info.explain_can_executor_commit(&alice, &dr_id, "0xcommitment".hash(), 1, Some(alice_proof.clone()));
+    assert!(check.allowed);
+    assert!(test_info.can_executor_commit_with_vrf_proof(&alice, &dr_id, "0xcommitment".hash(), alice_proof.clone()));
+    test_info
+        .commit_result_with_vrf_proof(&alice, &dr_id, "0xcommitment".hash(), alice_proof)
+        .unwrap();
+}
+
+#[test]
+fn vrf_commit_only_by_selected_executors() {
+    const EXECUTORS: [&str; 10] = ["e0", "e1", "e2", "e3", "e4", "e5", "e6", "e7", "e8", "e9"];
+
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   None,
+                committee_selection:        CommitteeSelection::Vrf,
+            },
+        )
+        .unwrap();
+
+    let mut executors = Vec::with_capacity(EXECUTORS.len());
+    for name in EXECUTORS {
+        let mut executor = test_info.new_executor(name, Some(42));
+        executor.stake(&mut test_info, 10).unwrap();
+        executors.push(executor);
+    }
+
+    // post a data request
+    let mut anyone = test_info.new_executor("anyone", Some(42));
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut anyone, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // split the executors by whether their VRF output selects them
+    let (selected, not_selected): (Vec<_>, Vec<_>) = executors
+        .iter()
+        .partition(|executor| test_info.is_executor_eligible_with_vrf(executor, dr_id.clone()));
+
+    for executor in not_selected {
+        let proof = test_info.committee_selection_proof(executor, &dr_id);
+        let res = test_info.commit_result_with_vrf_proof(executor, &dr_id, "0xcommitment".hash(), proof);
+        assert!(res.is_err_and(|x| x == ContractError::NotInCommittee));
+    }
+
+    if let Some(executor) = selected.first() {
+        let proof = test_info.committee_selection_proof(executor, &dr_id);
+        test_info
+            .commit_result_with_vrf_proof(executor, &dr_id, "0xcommitment".hash(), proof)
+            .unwrap();
+    }
+}
+
+#[test]
+fn undersized_vrf_committees_are_widened_to_the_window() {
+    const EXECUTORS: [&str; 5] = ["e0", "e1", "e2", "e3", "e4"];
+
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   None,
+                committee_selection:        CommitteeSelection::Vrf,
+            },
+        )
+        .unwrap();
+
+    let mut executors = Vec::with_capacity(EXECUTORS.len());
+    for name in EXECUTORS {
+        let mut executor = test_info.new_executor(name, Some(42));
+        executor.stake(&mut test_info, 10).unwrap();
+        executors.push(executor);
+    }
+
+    // post data requests until the VRF selects fewer executors than the replication factor
+    let mut anyone = test_info.new_executor("anyone", Some(10_000));
+    let (dr_id, selected) = (1..=100)
+        .find_map(|nonce| {
+            let dr = test_helpers::calculate_dr_id_and_args(nonce, 2);
+            let dr_id = test_info
+                .post_data_request(&mut anyone, dr, vec![], vec![], 1, None)
+                .unwrap();
+            let selected = executors
+                .iter()
+                .filter(|executor| test_info.is_executor_eligible_with_vrf(executor, dr_id.clone()))
+                .count();
+            (selected < 2).then_some((dr_id, selected))
+        })
+        .unwrap();
+
+    // before half of the commit stage has passed only the selected executors can commit
+    test_info.set_block_height(5);
+    let eligible = |test_info: &TestInfo| {
+        executors
+            .iter()
+            .filter(|executor| test_info.is_executor_eligible_with_vrf(executor, dr_id.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(selected, eligible(&test_info).len());
+
+    // from then on the window executors join in, so the committee can fill up
+    test_info.set_block_height(6);
+    let committee = eligible(&test_info);
+    assert!(committee.len() >= 2);
+    for executor in &committee[..2] {
+        let proof = test_info.committee_selection_proof(executor, &dr_id);
+        test_info
+            .commit_result_with_vrf_proof(executor, &dr_id, "0xcommitment".hash(), proof)
+            .unwrap();
+    }
+    assert_eq!(
+        DataRequestStatus::Revealing,
+        test_info.get_data_request_timeline(&dr_id).unwrap().status
+    );
+}
+
+#[test]
+fn query_drs_by_status_with_cursor() {
+    let mut test_info = TestInfo::init();
+
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 10).unwrap();
+
+    let mut dr_ids = Vec::with_capacity(5);
+    for nonce in 1..=5 {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 1);
+        let dr_id = test_info
+            .post_data_request(&mut alice, dr, vec![], vec![], nonce as u64, None)
+            .unwrap();
+        dr_ids.push(dr_id);
+        test_info.set_block_height(nonce as u64 + 1);
+    }
+
+    let page = test_info
+        .get_data_requests_by_status_cursor(DataRequestStatus::Committing, None, 2)
+        .unwrap();
+    assert!(!page.is_paused);
+    assert_eq!(
+        dr_ids[0..2],
+        page.data_requests.into_iter().map(|dr| dr.id).collect::<Vec<_>>()
+    );
+
+    // the first request moves to revealing between pages
+    test_info
+        .commit_result(&alice, &dr_ids[0], "0xcommitment".hash())
+        .unwrap();
+
+    let page = test_info
+        .get_data_requests_by_status_cursor(DataRequestStatus::Committing, page.next_cursor, 10)
+        .unwrap();
+    assert_eq!(
+        dr_ids[2..5],
+        page.data_requests.into_iter().map(|dr| dr.id).collect::<Vec<_>>()
+    );
+    assert_eq!(None, page.next_cursor);
+
+    // garbage cursors are rejected
+    let res = test_info.get_data_requests_by_status_cursor(DataRequestStatus::Committing, Some("zz".to_string()), 10);
+    assert!(res.is_err());
+}
+
+#[test]
+fn query_dr_summaries_by_status() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 10).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(2));
+    bob.stake(&mut test_info, 1).unwrap();
+
+    let mut dr_ids = Vec::with_capacity(2);
+    for nonce in 1..=2 {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 2);
+        let dr_id = test_info
+            .post_data_request(&mut alice, dr, vec![], vec![], nonce as u64, None)
+            .unwrap();
+        dr_ids.push(dr_id);
+    }
+
+    let alice_reveal = RevealBody {
+        id:                dr_ids[0].clone(),
+        salt:              alice.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    test_info
+        .commit_result(&alice, &dr_ids[0], alice_reveal.try_hash().unwrap())
+        .unwrap();
+
+    let page = test_info.get_data_request_summaries_by_status(DataRequestStatus::Committing, None, 1);
+    assert!(!page.is_paused);
+    assert_eq!(
+        vec![DataRequestSummary {
+            dr_id:              dr_ids[0].clone(),
+            height:             1,
+            status:             DataRequestStatus::Committing,
+            replication_factor: 2,
+            commits:            1,
+            reveals:            0,
+            gas_price:          10u128.into(),
+            expiration_height:  Some(11),
+        }],
+        page.summaries
+    );
+
+    let page = test_info.get_data_request_summaries_by_status(DataRequestStatus::Committing, page.next_cursor, 1);
+    assert_eq!(dr_ids[1], page.summaries[0].dr_id);
+    assert_eq!(2, page.summaries[0].height);
+    assert_eq!(None, page.next_cursor);
+
+    // once revealing the summary counts the reveals
+    test_info
+        .commit_result(&bob, &dr_ids[0], "0xcommitment".hash())
+        .unwrap();
+    test_info.reveal_result(&alice, &dr_ids[0], alice_reveal).unwrap();
+    let page = test_info.get_data_request_summaries_by_status(DataRequestStatus::Revealing, None, 10);
+    assert_eq!(1, page.summaries.len());
+    assert_eq!(DataRequestStatus::Revealing, page.summaries[0].status);
+    assert_eq!((2, 1), (page.summaries[0].commits, page.summaries[0].reveals));
+    assert_eq!(Some(12), page.summaries[0].expiration_height);
+}
+
+#[test]
+fn batch_commit_and_reveal() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 10).unwrap();
+
+    let mut dr_ids = Vec::with_capacity(2);
+    for nonce in 1..=2 {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 1);
+        let dr_id = test_info
+            .post_data_request(&mut alice, dr, vec![], vec![], nonce as u64, None)
+            .unwrap();
+        dr_ids.push(dr_id);
+    }
+    let reveals: Vec<_> = dr_ids
+        .iter()
+        .map(|dr_id| RevealBody {
+            id:                dr_id.clone(),
+            salt:              alice.salt(),
+            reveal:            "10".hash().into(),
+            gas_used:          0,
+            exit_code:         0,
+            proxy_public_keys: vec![],
+        })
+        .collect();
+
+    // the first request is already committed to, the others in the batch still go through
+    test_info
+        .commit_result(&alice, &dr_ids[0], reveals[0].try_hash().unwrap())
+        .unwrap();
+    let missing_dr_id = "0".repeat(64);
+    let results = test_info
+        .batch_commit_results(
+            &alice,
+            vec![
+                (&dr_ids[0], reveals[0].try_hash().unwrap()),
+                (&dr_ids[1], reveals[1].try_hash().unwrap()),
+                (&missing_dr_id, "0xcommitment".hash()),
+            ],
+        )
+        .unwrap();
+    assert_eq!(
+        vec![false, true, false],
+        results.iter().map(|result| result.success).collect::<Vec<_>>()
+    );
+    assert_eq!(Some(ContractError::AlreadyCommitted.code()), results[0].error_code);
+    assert_eq!(None, results[1].error);
+    assert!(results[2].error.is_some());
+    assert!(test_info
+        .get_data_requests_by_status(DataRequestStatus::Revealing, 0, 10)
+        .data_requests
+        .iter()
+        .any(|dr| dr.id == dr_ids[1]));
+
+    // a reveal not matching its commitment fails without undoing the valid one
+    let mut wrong_reveal = reveals[1].clone();
+    wrong_reveal.reveal = "11".hash().into();
+    let results = test_info
+        .batch_reveal_results(&alice, vec![reveals[0].clone(), wrong_reveal])
+        .unwrap();
+    assert!(results[0].success);
+    assert_eq!(Some(ContractError::RevealMismatch.code()), results[1].error_code);
+    assert!(test_info
+        .get_data_requests_by_status(DataRequestStatus::Tallying, 0, 10)
+        .data_requests
+        .iter()
+        .any(|dr| dr.id == dr_ids[0]));
+    assert!(test_info
+        .get_data_requests_by_status(DataRequestStatus::Revealing, 0, 10)
+        .data_requests
+        .iter()
+        .any(|dr| dr.id == dr_ids[1]));
+
+    let results = test_info
+        .batch_reveal_results(&alice, vec![reveals[1].clone()])
+        .unwrap();
+    assert!(results[0].success);
+    assert!(test_info
+        .get_data_requests_by_status(DataRequestStatus::Tallying, 0, 10)
+        .data_requests
+        .iter()
+        .any(|dr| dr.id == dr_ids[1]));
+}
+
+#[test]
+fn failed_batch_items_leave_no_trace() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(42));
+    alice.stake(&mut test_info, 10).unwrap();
+
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut alice, dr, vec![], vec![], 1, None)
+        .unwrap();
+
+    // the commit passes every check but the last one, the committee selection
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                committee_selection: CommitteeSelection::Vrf,
+                ..test_info.get_extended_staking_config()
+            },
+        )
+        .unwrap();
+    let results = test_info
+        .batch_commit_results(&alice, vec![(&dr_id, "0xcommitment".hash())])
+        .unwrap();
+    assert_eq!(Some(ContractError::MissingVrfProof.code()), results[0].error_code);
+    assert!(test_info.get_data_request(&dr_id).unwrap().commits.is_empty());
+
+    // nothing was stored, so the same commit goes through once the executor is selected
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                committee_selection: CommitteeSelection::Window,
+                ..test_info.get_extended_staking_config()
+            },
+        )
+        .unwrap();
+    let results = test_info
+        .batch_commit_results(&alice, vec![(&dr_id, "0xcommitment".hash())])
+        .unwrap();
+    assert!(results[0].success);
+    assert_eq!(1, test_info.get_data_request(&dr_id).unwrap().commits.len());
+}
+
+#[test]
+fn post_data_requests_in_batch() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 10).unwrap();
+
+    let drs: Vec<_> = (1..=3)
+        .map(|nonce| test_helpers::calculate_dr_id_and_args(nonce, 1))
+        .collect();
+
+    // the batch has to post something
+    let res = test_info.post_data_requests(&mut alice, vec![], 1, 20, (None, None));
+    assert!(res.is_err_and(|x| x == ContractError::EmptyDataRequestBatch));
+    assert_eq!(90, test_info.executor_balance("alice"));
+
+    // the payment has to cover every request of the batch
+    let res = test_info.post_data_requests(&mut alice, drs[..2].to_vec(), 1, 30, (None, None));
+    assert!(res.is_err_and(|x| x == ContractError::InsufficientFunds(40u128.into(), 30u128.into())));
+
+    // a duplicate within the batch fails it as a whole
+    let res = test_info.post_data_requests(&mut alice, vec![drs[0].clone(), drs[0].clone()], 1, 40, (None, None));
+    assert!(res.is_err_and(|x| x == ContractError::DataRequestAlreadyExists));
+    assert_eq!(None, test_info.get_data_request(&drs[0].try_hash().unwrap().to_hex()));
+
+    let payloads = test_info
+        .post_data_requests(&mut alice, drs.clone(), 1, 65, (None, None))
+        .unwrap();
+    assert_eq!(
+        drs.iter().map(|dr| dr.try_hash().unwrap().to_hex()).collect::<Vec<_>>(),
+        payloads.iter().map(|payload| payload.dr_id.clone()).collect::<Vec<_>>()
+    );
+    assert!(payloads.iter().all(|payload| payload.height == 1));
+
+    // each request escrows what it requires and the surplus is refunded
+    for payload in &payloads {
+        let escrow = DR_ESCROW
+            .load(
+                &*test_info.app().contract_storage(&test_info.contract_addr()),
+                &Hash::from_hex_str(&payload.dr_id).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(20, escrow.amount.u128());
+        assert_eq!(alice.addr(), escrow.poster);
+    }
+    assert_eq!(30, test_info.executor_balance("alice"));
+    assert_eq!(
+        3,
+        test_info
+            .get_data_requests_by_status(DataRequestStatus::Committing, 0, 10)
+            .data_requests
+            .len()
+    );
+}
+
+#[test]
+fn result_callbacks_are_sent_on_removal() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(10_000));
+    alice.stake(&mut test_info, 10).unwrap();
+    let consumer = test_info.new_result_consumer();
+
+    // the callback gas limit is capped by the data result config
+    let res = test_info.post_data_request_with_callback(
+        &mut alice,
+        test_helpers::calculate_dr_id_and_args(1, 1),
+        1,
+        &consumer,
+        1_000_001,
+        (None, None),
+    );
+    assert!(res.is_err_and(|x| x == ContractError::CallbackGasLimitTooHigh(1_000_001, 1_000_000)));
+
+    // the callback gas is paid for on top of the execution and tally gas
+    let mut dr_ids = Vec::with_capacity(2);
+    for nonce in 1..=2 {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 1);
+        let dr_id = test_info
+            .post_data_request_with_callback(&mut alice, dr, nonce as u64, &consumer, 100, (None, None))
+            .unwrap();
+        dr_ids.push(dr_id);
+    }
+    assert_eq!(7_950, test_info.executor_balance("alice"));
+    let reveals: Vec<_> = dr_ids
+        .iter()
+        .map(|dr_id| RevealBody {
+            id:                dr_id.clone(),
+            salt:              alice.salt(),
+            reveal:            "10".hash().into(),
+            gas_used:          0,
+            exit_code:         0,
+            proxy_public_keys: vec![],
+        })
+        .collect();
+    test_info
+        .batch_commit_results(
+            &alice,
+            reveals
+                .iter()
+                .map(|reveal| (reveal.id.as_str(), reveal.try_hash().unwrap()))
+                .collect(),
+        )
+        .unwrap();
+    test_info.batch_reveal_results(&alice, reveals).unwrap();
+
+    // the consumer rejects the second result, which does not stop its removal
+    let removed = test_info
+        .remove_data_requests_with_results(vec![
+            ResolvedDataRequest {
+                dr_id:    dr_ids[0].clone(),
+                result:   DataRequestResult {
+                    result:    vec![1, 2, 3].into(),
+                    exit_code: 0,
+                    gas_used:  10,
+                    consensus: true,
+                },
+                messages: vec![],
+            },
+            ResolvedDataRequest {
+                dr_id:    dr_ids[1].clone(),
+                result:   DataRequestResult {
+                    result:    vec![].into(),
+                    exit_code: 1,
+                    gas_used:  10,
+                    consensus: false,
+                },
+                messages: vec![],
+            },
+        ])
+        .unwrap();
+    assert_eq!(vec![(dr_ids[0].clone(), 0), (dr_ids[1].clone(), 0)], removed);
+    assert_eq!(None, test_info.get_data_request(&dr_ids[1]));
+    // the callback gas is burned, the rest of the escrow is refunded
+    assert_eq!(7_990, test_info.executor_balance("alice"));
+    assert_eq!(
+        Some(ResultCallbackMsg::SedaDataResult {
+            dr_id:     dr_ids[0].clone(),
+            result:    vec![1, 2, 3].into(),
+            exit_code: 0,
+            consensus: true,
+        }),
+        test_info.last_received_result(&consumer)
+    );
+
+    // cancelled requests drop their callback
+    let dr = test_helpers::calculate_dr_id_and_args(3, 1);
+    let dr_id = test_info
+        .post_data_request_with_callback(&mut alice, dr, 3, &consumer, 100, (None, None))
+        .unwrap();
+    test_info.cancel_data_request(&alice, &dr_id).unwrap();
+    assert_eq!(7_990, test_info.executor_balance("alice"));
+    assert!(!DR_RESULT_CALLBACKS.has(
+        &*test_info.app().contract_storage(&test_info.contract_addr()),
+        &Hash::from_hex_str(&dr_id).unwrap(),
+    ));
+}
+
+#[test]
+fn data_results_are_archived_and_pruned() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 10).unwrap();
+
+    // only the owner can change the retention window
+    let config = DataResultConfig {
+        retention_in_blocks:    5,
+        max_pruned_per_sweep:   1,
+        max_callback_gas_limit: 1_000_000,
+    };
+    let res = test_info.set_data_result_config(&alice, config.clone());
+    assert!(res.is_err_and(|x| x == ContractError::NotOwner));
+    let res = test_info.set_data_result_config(
+        &test_info.creator(),
+        DataResultConfig {
+            max_pruned_per_sweep: 0,
+            ..config.clone()
+        },
+    );
+    assert!(res.is_err_and(|x| x == ContractError::ZeroMaxPrunedPerSweep));
+    test_info.set_data_result_config(&test_info.creator(), config).unwrap();
+
+    let mut resolve = |test_info: &mut TestInfo, nonce: u128, height: u64| {
+        let dr = test_helpers::calculate_dr_id_and_args(nonce, 1);
+        let dr_id = test_info
+            .post_data_request(&mut alice, dr, vec![], vec![], height, None)
+            .unwrap();
+        let reveal = RevealBody {
+            id:                dr_id.clone(),
+            salt:              alice.salt(),
+            reveal:            "10".hash().into(),
+            gas_used:          0,
+            exit_code:         0,
+            proxy_public_keys: vec![],
+        };
+        test_info
+            .commit_result(&alice, &dr_id, reveal.try_hash().unwrap())
+            .unwrap();
+        test_info.reveal_result(&alice, &dr_id, reveal).unwrap();
+        test_info
+            .remove_data_requests_with_results(vec![ResolvedDataRequest {
+                dr_id:    dr_id.clone(),
+                result:   DataRequestResult {
+                    result:    vec![nonce as u8].into(),
+                    exit_code: 0,
+                    gas_used:  7,
+                    consensus: true,
+                },
+                messages: vec![],
+            }])
+            .unwrap();
+        dr_id
+    };
+
+    let first = resolve(&mut test_info, 1, 1);
+    let second = resolve(&mut test_info, 2, 2);
+    assert_eq!(
+        Some(DataResult {
+            dr_id:        first.clone(),
+            result:       vec![1].into(),
+            exit_code:    0,
+            gas_used:     7,
+            consensus:    true,
+            block_height: 1,
+        }),
+        test_info.get_data_result(&first)
+    );
+
+    // both results are past the retention window, but only one is pruned per expiry sweep
+    let third = resolve(&mut test_info, 3, 10);
+    assert!(test_info.get_data_result(&first).is_some());
+    test_info.expire_data_requests().unwrap();
+    assert_eq!(None, test_info.get_data_result(&first));
+    assert!(test_info.get_data_result(&second).is_some());
+    assert!(test_info.get_data_result(&third).is_some());
+
+    let fourth = resolve(&mut test_info, 4, 11);
+    test_info.expire_data_requests().unwrap();
+    assert_eq!(None, test_info.get_data_result(&second));
+    assert!(test_info.get_data_result(&third).is_some());
+    assert!(test_info.get_data_result(&fourth).is_some());
+
+    // a data request that fails to be removed has its result neither archived nor sent to its callback
+    let consumer = test_info.new_result_consumer();
+    let dr = test_helpers::calculate_dr_id_and_args(5, 1);
+    let dr_id = test_info
+        .post_data_request_with_callback(&mut alice, dr, 12, &consumer, 1, (None, None))
+        .unwrap();
+    let removed = test_info
+        .remove_data_requests_with_results(vec![ResolvedDataRequest {
+            dr_id:    dr_id.clone(),
+            result:   DataRequestResult {
+                result:    vec![5].into(),
+                exit_code: 0,
+                gas_used:  7,
+                consensus: true,
+            },
+            messages: vec![],
+        }])
+        .unwrap();
+    assert_eq!(vec![(dr_id.clone(), 0)], removed);
+    assert_eq!(None, test_info.get_data_result(&dr_id));
+    assert_eq!(None, test_info.last_received_result(&consumer));
+    assert!(DR_RESULT_CALLBACKS.has(
+        &*test_info.app().contract_storage(&test_info.contract_addr()),
+        &Hash::from_hex_str(&dr_id).unwrap(),
+    ));
+}
+
+#[test]
+fn cw20_fees_are_escrowed_and_distributed() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 10).unwrap();
+    let proxy = test_info.new_address("proxy");
+    let cw20 = test_info.new_cw20(&[(&alice.addr(), 100)]);
+
+    // only allowlisted tokens are accepted, and only the owner can allowlist them
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let err = test_info
+        .post_data_request_with_cw20(&alice, &cw20, dr.clone(), 1, 30, (None, None))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ContractError::Cw20FeeTokenNotAllowed(cw20.to_string()).to_string()));
+    assert_eq!(
+        ContractError::NotOwner,
+        test_info.add_cw20_fee_token(&alice, &cw20, Decimal::one()).unwrap_err()
+    );
+    test_info
+        .add_cw20_fee_token(&test_info.creator(), &cw20, Decimal::one())
+        .unwrap();
+    assert_eq!(
+        vec![Cw20FeeToken {
+            cw20_contract: cw20.to_string(),
+            rate:          Decimal::one(),
+        }],
+        test_info.get_cw20_fee_tokens()
+    );
+
+    // the whole amount sent is escrowed in the CW20 token
+    let dr_id = test_info
+        .post_data_request_with_cw20(&alice, &cw20, dr, 1, 30, (None, None))
+        .unwrap();
+    let escrow = DR_ESCROW
+        .load(
+            &*test_info.app().contract_storage(&test_info.contract_addr()),
+            &Hash::from_hex_str(&dr_id).unwrap(),
+        )
+        .unwrap();
+    assert_eq!(30, escrow.amount.u128());
+    assert_eq!(Some(cw20.clone()), escrow.cw20_contract);
+    let requests = test_info.get_data_requests_by_poster(&alice, None, 10);
+    assert_eq!(Some(cw20.to_string()), requests[0].cw20_contract);
+    assert_eq!(70, test_info.cw20_balance(&cw20, &alice.addr()));
+
+    // native funds cannot be added to it
+    let posted = test_info.get_data_request(&dr_id).unwrap();
+    assert_eq!(
+        ContractError::Cw20EscrowFeeBump,
+        test_info
+            .bump_data_request_fee(
+                &mut alice,
+                &dr_id,
+                posted.gas_price.u128(),
+                (posted.exec_gas_limit, posted.tally_gas_limit),
+                0,
+            )
+            .unwrap_err()
+    );
+
+    let alice_reveal = RevealBody {
+        id:                dr_id.clone(),
+        salt:              alice.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    test_info
+        .commit_result(&alice, &dr_id, alice_reveal.try_hash().unwrap())
+        .unwrap();
+    test_info.reveal_result(&alice, &dr_id, alice_reveal).unwrap();
+
+    // distributions are paid in the CW20 token, the executor reward is set aside
+    // and the remainder is refunded to the poster
+    test_info
+        .remove_data_request(
+            dr_id,
+            vec![
+                DistributionMessage::Burn(DistributionBurn { amount: 1u128.into() }),
+                DistributionMessage::DataProxyReward(DistributionDataProxyReward {
+                    payout_address: proxy.to_string(),
+                    amount:         5u128.into(),
+                }),
+                DistributionMessage::ExecutorReward(DistributionExecutorReward {
+                    identity: alice.pub_key_hex(),
+                    amount:   10u128.into(),
+                }),
+            ],
+        )
+        .unwrap();
+    assert_eq!(99, test_info.cw20_total_supply(&cw20));
+    assert_eq!(5, test_info.cw20_balance(&cw20, &proxy));
+    assert_eq!(84, test_info.cw20_balance(&cw20, &alice.addr()));
+    assert_eq!(10, test_info.get_cw20_rewards(alice.pub_key(), &cw20));
+    let staker = test_info.get_staker(alice.pub_key()).unwrap();
+    assert_eq!(10, staker.tokens_staked.u128());
+    assert_eq!(0, staker.tokens_pending_withdrawal.u128());
+
+    // the executor withdraws its rewards
+    test_info.withdraw_cw20_rewards(&mut alice, &cw20, 10).unwrap();
+    assert_eq!(94, test_info.cw20_balance(&cw20, &alice.addr()));
+    assert_eq!(0, test_info.get_cw20_rewards(alice.pub_key(), &cw20));
+    assert_eq!(
+        ContractError::InsufficientFunds(0u128.into(), 1u128.into()),
+        test_info.withdraw_cw20_rewards(&mut alice, &cw20, 1).unwrap_err()
+    );
+
+    // cancelled requests are refunded in the CW20 token
+    let dr = test_helpers::calculate_dr_id_and_args(2, 1);
+    let dr_id = test_info
+        .post_data_request_with_cw20(&alice, &cw20, dr, 2, 20, (None, None))
+        .unwrap();
+    assert_eq!(74, test_info.cw20_balance(&cw20, &alice.addr()));
+    test_info.cancel_data_request(&alice, &dr_id).unwrap();
+    assert_eq!(94, test_info.cw20_balance(&cw20, &alice.addr()));
+
+    // removed tokens are no longer accepted
+    test_info.remove_cw20_fee_token(&test_info.creator(), &cw20).unwrap();
+    assert!(test_info.get_cw20_fee_tokens().is_empty());
+    let dr = test_helpers::calculate_dr_id_and_args(3, 1);
+    assert!(test_info
+        .post_data_request_with_cw20(&alice, &cw20, dr, 3, 20, (None, None))
+        .is_err());
+}
+
+#[test]
+fn cw20_fees_are_converted_at_the_token_rate() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 10).unwrap();
+    let proxy = test_info.new_address("proxy");
+    let cw20 = test_info.new_cw20(&[(&alice.addr(), 100)]);
+
+    // two tokens are worth one unit of the native token
+    assert_eq!(
+        ContractError::ZeroCw20FeeTokenRate,
+        test_info
+            .add_cw20_fee_token(&test_info.creator(), &cw20, Decimal::zero())
+            .unwrap_err()
+    );
+    test_info
+        .add_cw20_fee_token(&test_info.creator(), &cw20, Decimal::percent(200))
+        .unwrap();
+    assert_eq!(
+        vec![Cw20FeeToken {
+            cw20_contract: cw20.to_string(),
+            rate:          Decimal::percent(200),
+        }],
+        test_info.get_cw20_fee_tokens()
+    );
+
+    // the data request requires 20 of the native token, so 40 tokens
+    let dr = test_helpers::calculate_dr_id_and_args(1, 1);
+    let err = test_info
+        .post_data_request_with_cw20(&alice, &cw20, dr.clone(), 1, 30, (None, None))
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&ContractError::InsufficientFunds(40u128.into(), 30u128.into()).to_string()));
+    let dr_id = test_info
+        .post_data_request_with_cw20(&alice, &cw20, dr, 1, 50, (None, None))
+        .unwrap();
+
+    let alice_reveal = RevealBody {
+        id:                dr_id.clone(),
+        salt:              alice.salt(),
+        reveal:            "10".hash().into(),
+        gas_used:          0,
+        exit_code:         0,
+        proxy_public_keys: vec![],
+    };
+    test_info
+        .commit_result(&alice, &dr_id, alice_reveal.try_hash().unwrap())
+        .unwrap();
+    test_info.reveal_result(&alice, &dr_id, alice_reveal).unwrap();
+
+    // distributions are in the native token and paid out in twice as many tokens
+    test_info
+        .remove_data_request(
+            dr_id,
+            vec![
+                DistributionMessage::Burn(DistributionBurn { amount: 1u128.into() }),
+                DistributionMessage::DataProxyReward(DistributionDataProxyReward {
+                    payout_address: proxy.to_string(),
+                    amount:         5u128.into(),
+                }),
+                DistributionMessage::ExecutorReward(DistributionExecutorReward {
+                    identity: alice.pub_key_hex(),
+                    amount:   10u128.into(),
+                }),
+            ],
+        )
+        .unwrap();
+    assert_eq!(98, test_info.cw20_total_supply(&cw20));
+    assert_eq!(10, test_info.cw20_balance(&cw20, &proxy));
+    assert_eq!(20, test_info.get_cw20_rewards(alice.pub_key(), &cw20));
+    assert_eq!(68, test_info.cw20_balance(&cw20, &alice.addr()));
+}
+
+#[test]
+fn failed_cw20_payouts_do_not_revert_the_removal() {
+    let mut test_info = TestInfo::init();
+    let mut alice = test_info.new_executor("alice", Some(100));
+    alice.stake(&mut test_info, 10).unwrap();
+    let cw20 = test_info.new_rejecting_cw20(&[(&alice.addr(), 100)]);
+    test_info
+        .add_cw20_fee_token(&test_info.creator(), &cw20, Decimal::one())
+        .unwrap();
+
+    let cw20_dr_id = test_info
+        .post_data_request_with_cw20(
+            &alice,
+            &cw20,
+            test_helpers::calculate_dr_id_and_args(1, 1),
+            1,
+            30,
+            (None, None),
+        )
+        .unwrap();
+    let dr_id = test_info
+        .post_data_request(
+            &mut alice,
+            test_helpers::calculate_dr_id_and_args(2, 1),
+            vec![],
+            vec![],
+            1,
+            None,
+        )
+        .unwrap();
+    let reveals: Vec<_> = [&cw20_dr_id, &dr_id]
+        .into_iter()
+        .map(|dr_id| RevealBody {
+            id:                dr_id.clone(),
+            salt:              alice.salt(),
+            reveal:            "10".hash().into(),
+            gas_used:          0,
+            exit_code:         0,
+            proxy_public_keys: vec![],
+        })
+        .collect();
+    test_info
+        .batch_commit_results(
+            &alice,
+            reveals
+                .iter()
+                .map(|reveal| (reveal.id.as_str(), reveal.try_hash().unwrap()))
+                .collect(),
+        )
+        .unwrap();
+    test_info.batch_reveal_results(&alice, reveals).unwrap();
+
+    // the CW20 contract rejects both the burn and the refund
+    test_info.set_cw20_rejecting(&cw20, true);
+    let burn = DistributionMessage::Burn(DistributionBurn { amount: 1u128.into() });
+    let removed = test_info
+        .remove_data_requests(HashMap::from([
+            (cw20_dr_id.clone(), vec![burn]),
+            (dr_id.clone(), vec![]),
+        ]))
+        .unwrap();
+
+    // both requests are still removed, and the native refund still goes through
+    assert_eq!(2, removed.len());
+    assert!(removed.iter().all(|(_, status)| *status == 0));
+    assert_eq!(None, test_info.get_data_request(&cw20_dr_id));
+    assert_eq!(None, test_info.get_data_request(&dr_id));
+    assert_eq!(90, test_info.executor_balance("alice"));
+    assert_eq!(70, test_info.cw20_balance(&cw20, &alice.addr()));
+
+    // the rejected tokens are recorded, the refund for the poster and the burn for the owner
+    let contract_addr = test_info.contract_addr();
+    let creator = test_info.creator();
+    assert_eq!(30, test_info.cw20_balance(&cw20, &contract_addr));
+    assert_eq!(29, test_info.get_failed_cw20_payouts(&cw20, &alice.addr()));
+    assert_eq!(1, test_info.get_failed_cw20_payouts(&cw20, &creator.addr()));
+
+    // claiming fails as long as the CW20 contract rejects the transfer, and keeps the record
+    assert!(test_info.claim_failed_cw20_payouts(&alice, &cw20).is_err());
+    assert_eq!(29, test_info.get_failed_cw20_payouts(&cw20, &alice.addr()));
+
+    // once it accepts them again the tokens are recovered
+    test_info.set_cw20_rejecting(&cw20, false);
+    test_info.claim_failed_cw20_payouts(&alice, &cw20).unwrap();
+    test_info.claim_failed_cw20_payouts(&creator, &cw20).unwrap();
+    assert_eq!(99, test_info.cw20_balance(&cw20, &alice.addr()));
+    assert_eq!(1, test_info.cw20_balance(&cw20, &creator.addr()));
+    assert_eq!(0, test_info.cw20_balance(&cw20, &contract_addr));
+    assert_eq!(0, test_info.get_failed_cw20_payouts(&cw20, &alice.addr()));
+    assert_eq!(
+        ContractError::NoFailedCw20Payouts(cw20.to_string()),
+        test_info.claim_failed_cw20_payouts(&alice, &cw20).unwrap_err()
+    );
 }
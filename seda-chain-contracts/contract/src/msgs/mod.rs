@@ -1,9 +1,9 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::*;
 use cw_storage_plus::{Item, Map};
 use seda_common::msgs::{
     self,
     staking::{Staker, StakingConfig},
-    SudoMsg,
 };
 
 use crate::{common_types::*, contract::CONTRACT_VERSION, error::ContractError, types::*};
@@ -14,6 +14,99 @@ pub mod owner;
 pub mod staking;
 pub use enumerable_set::EnumerableSet;
 
+/// Execute messages accepted by the contract.
+///
+/// Wraps the `seda-common` messages together with the ones that are specific to this contract.
+#[cw_serde]
+#[serde(untagged)]
+pub enum ExecuteMsg {
+    Common(msgs::ExecuteMsg),
+    Contract(ContractExecuteMsg),
+}
+
+/// Execute messages specific to this contract, flat and untagged like the `seda-common` ones.
+#[cw_serde]
+#[serde(untagged)]
+pub enum ContractExecuteMsg {
+    DataRequest(data_requests::execute::ContractExecuteMsg),
+    Staking(staking::execute::ContractExecuteMsg),
+}
+
+/// Query messages accepted by the contract.
+///
+/// Wraps the `seda-common` messages together with the ones that are specific to this contract.
+#[cw_serde]
+#[serde(untagged)]
+#[derive(QueryResponses)]
+#[query_responses(nested)]
+pub enum QueryMsg {
+    Common(msgs::QueryMsg),
+    Contract(ContractQueryMsg),
+}
+
+/// Query messages specific to this contract, flat and untagged like the `seda-common` ones.
+#[cw_serde]
+#[serde(untagged)]
+#[derive(QueryResponses)]
+#[query_responses(nested)]
+pub enum ContractQueryMsg {
+    DataRequest(data_requests::query::ContractQueryMsg),
+    Staking(staking::query::ContractQueryMsg),
+}
+
+/// Sudo messages accepted by the contract.
+///
+/// Wraps the `seda-common` messages together with the ones that are specific to this contract.
+#[cw_serde]
+#[serde(untagged)]
+pub enum SudoMsg {
+    Common(msgs::SudoMsg),
+    Contract(ContractSudoMsg),
+}
+
+/// Sudo messages specific to this contract, flat and untagged like the `seda-common` ones.
+#[cw_serde]
+#[serde(untagged)]
+pub enum ContractSudoMsg {
+    DataRequest(data_requests::sudo::ContractSudoMsg),
+}
+
+impl From<msgs::ExecuteMsg> for ExecuteMsg {
+    fn from(msg: msgs::ExecuteMsg) -> Self {
+        ExecuteMsg::Common(msg)
+    }
+}
+
+impl From<ContractExecuteMsg> for ExecuteMsg {
+    fn from(msg: ContractExecuteMsg) -> Self {
+        ExecuteMsg::Contract(msg)
+    }
+}
+
+impl From<msgs::QueryMsg> for QueryMsg {
+    fn from(msg: msgs::QueryMsg) -> Self {
+        QueryMsg::Common(msg)
+    }
+}
+
+impl From<ContractQueryMsg> for QueryMsg {
+    fn from(msg: ContractQueryMsg) -> Self {
+        QueryMsg::Contract(msg)
+    }
+}
+
+impl From<msgs::SudoMsg> for SudoMsg {
+    fn from(msg: msgs::SudoMsg) -> Self {
+        SudoMsg::Common(msg)
+    }
+}
+
+impl From<ContractSudoMsg> for SudoMsg {
+    fn from(msg: ContractSudoMsg) -> Self {
+        SudoMsg::Contract(msg)
+    }
+}
+
 pub trait QueryHandler {
     fn query(self, deps: Deps, env: Env) -> Result<Binary, ContractError>;
 }
@@ -36,6 +129,42 @@ impl ExecuteHandler for msgs::ExecuteMsg {
     }
 }
 
+impl ExecuteHandler for ExecuteMsg {
+    fn execute(self, deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        match self {
+            ExecuteMsg::Common(msg) => msg.execute(deps, env, info),
+            ExecuteMsg::Contract(msg) => msg.execute(deps, env, info),
+        }
+    }
+}
+
+impl ExecuteHandler for ContractExecuteMsg {
+    fn execute(self, deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        match self {
+            ContractExecuteMsg::DataRequest(msg) => msg.execute(deps, env, info),
+            ContractExecuteMsg::Staking(msg) => msg.execute(deps, env, info),
+        }
+    }
+}
+
+impl QueryHandler for QueryMsg {
+    fn query(self, deps: Deps, env: Env) -> Result<Binary, ContractError> {
+        match self {
+            QueryMsg::Common(msg) => msg.query(deps, env),
+            QueryMsg::Contract(msg) => msg.query(deps, env),
+        }
+    }
+}
+
+impl QueryHandler for ContractQueryMsg {
+    fn query(self, deps: Deps, env: Env) -> Result<Binary, ContractError> {
+        match self {
+            ContractQueryMsg::DataRequest(msg) => msg.query(deps, env),
+            ContractQueryMsg::Staking(msg) => msg.query(deps, env),
+        }
+    }
+}
+
 impl QueryHandler for msgs::QueryMsg {
     fn query(self, deps: Deps, env: Env) -> Result<Binary, ContractError> {
         match self {
@@ -46,10 +175,27 @@ impl QueryHandler for msgs::QueryMsg {
     }
 }
 
+impl SudoHandler for msgs::SudoMsg {
+    fn sudo(self, deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        match self {
+            msgs::SudoMsg::DataRequest(sudo) => sudo.sudo(deps, env),
+        }
+    }
+}
+
 impl SudoHandler for SudoMsg {
     fn sudo(self, deps: DepsMut, env: Env) -> Result<Response, ContractError> {
         match self {
-            SudoMsg::DataRequest(sudo) => sudo.sudo(deps, env),
+            SudoMsg::Common(sudo) => sudo.sudo(deps, env),
+            SudoMsg::Contract(sudo) => sudo.sudo(deps, env),
+        }
+    }
+}
+
+impl SudoHandler for ContractSudoMsg {
+    fn sudo(self, deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+        match self {
+            ContractSudoMsg::DataRequest(sudo) => sudo.sudo(deps, env),
         }
     }
 }
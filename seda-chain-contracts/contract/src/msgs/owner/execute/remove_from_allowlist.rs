@@ -27,6 +27,7 @@ impl ExecuteHandler for execute::remove_from_allowlist::Execute {
 
         // do this at the end in case we fail above
         ALLOWLIST.remove(deps.storage, &public_key);
+        STAKERS.sync_eligibility(deps.storage, public_key)?;
 
         Ok(Response::new()
             .add_attribute("action", "remove-from-allowlist")
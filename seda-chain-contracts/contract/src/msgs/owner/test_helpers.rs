@@ -7,7 +7,7 @@ use crate::{TestExecutor, TestInfo};
 impl TestInfo {
     #[track_caller]
     pub fn accept_ownership(&mut self, sender: &TestExecutor) -> Result<(), ContractError> {
-        let msg = execute::accept_ownership::Execute {}.into();
+        let msg: msgs::ExecuteMsg = execute::accept_ownership::Execute {}.into();
         self.execute(sender, &msg)
     }
 
@@ -23,7 +23,7 @@ impl TestInfo {
 
     #[track_caller]
     pub fn transfer_ownership(&mut self, sender: &TestExecutor, new_owner: &TestExecutor) -> Result<(), ContractError> {
-        let msg = execute::transfer_ownership::Execute {
+        let msg: msgs::ExecuteMsg = execute::transfer_ownership::Execute {
             new_owner: new_owner.addr().into_string(),
         }
         .into();
@@ -32,7 +32,7 @@ impl TestInfo {
 
     #[track_caller]
     pub fn add_to_allowlist(&mut self, sender: &TestExecutor, public_key: PublicKey) -> Result<(), ContractError> {
-        let msg = execute::add_to_allowlist::Execute {
+        let msg: msgs::ExecuteMsg = execute::add_to_allowlist::Execute {
             public_key: public_key.to_hex(),
         }
         .into();
@@ -42,7 +42,7 @@ impl TestInfo {
 
     #[track_caller]
     pub fn remove_from_allowlist(&mut self, sender: &TestExecutor, public_key: PublicKey) -> Result<(), ContractError> {
-        let msg = execute::remove_from_allowlist::Execute {
+        let msg: msgs::ExecuteMsg = execute::remove_from_allowlist::Execute {
             public_key: public_key.to_hex(),
         }
         .into();
@@ -52,13 +52,13 @@ impl TestInfo {
 
     #[track_caller]
     pub fn pause(&mut self, sender: &TestExecutor) -> Result<(), ContractError> {
-        let msg = execute::pause::Execute {}.into();
+        let msg: msgs::ExecuteMsg = execute::pause::Execute {}.into();
         self.execute(sender, &msg)
     }
 
     #[track_caller]
     pub fn unpause(&mut self, sender: &TestExecutor) -> Result<(), ContractError> {
-        let msg = execute::unpause::Execute {}.into();
+        let msg: msgs::ExecuteMsg = execute::unpause::Execute {}.into();
         self.execute(sender, &msg)
     }
 
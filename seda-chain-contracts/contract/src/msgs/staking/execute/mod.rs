@@ -1,14 +1,19 @@
+use cosmwasm_schema::cw_serde;
+
 use super::{
     msgs::staking::execute::{self, ExecuteMsg},
     *,
 };
 use crate::state::PAUSED;
 
+pub(in crate::msgs::staking) mod set_extended_staking_config;
 pub(in crate::msgs::staking) mod set_staking_config;
+pub(crate) mod slashing;
 pub(in crate::msgs::staking) mod stake;
 pub(crate) mod staking_events;
 pub(in crate::msgs::staking) mod unstake;
 pub(in crate::msgs::staking) mod withdraw;
+pub(in crate::msgs::staking) mod withdraw_cw20_rewards;
 
 impl ExecuteHandler for ExecuteMsg {
     fn execute(self, deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
@@ -25,3 +30,37 @@ impl ExecuteHandler for ExecuteMsg {
         }
     }
 }
+
+/// Staking execute messages specific to this contract.
+#[cw_serde]
+pub enum ContractExecuteMsg {
+    SetExtendedStakingConfig(state::ExtendedStakingConfig),
+    /// Withdraws executor rewards paid in the given CW20 token, proven like a regular withdrawal.
+    WithdrawCw20Rewards {
+        cw20_contract: String,
+        withdraw:      execute::withdraw::Execute,
+    },
+}
+
+impl ExecuteHandler for ContractExecuteMsg {
+    fn execute(self, deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        // setting the extended staking config is an owner operation and should not be paused
+        if PAUSED.load(deps.storage)? && !matches!(self, ContractExecuteMsg::SetExtendedStakingConfig(_)) {
+            return Err(ContractError::ContractPaused("staking execute messages".to_string()));
+        }
+
+        match self {
+            ContractExecuteMsg::SetExtendedStakingConfig(msg) => ExecuteHandler::execute(msg, deps, env, info),
+            ContractExecuteMsg::WithdrawCw20Rewards {
+                cw20_contract,
+                withdraw,
+            } => withdraw_cw20_rewards::withdraw_cw20_rewards(deps, env, info, cw20_contract, withdraw),
+        }
+    }
+}
+
+impl From<ContractExecuteMsg> for crate::msgs::ExecuteMsg {
+    fn from(msg: ContractExecuteMsg) -> Self {
+        crate::msgs::ContractExecuteMsg::Staking(msg).into()
+    }
+}
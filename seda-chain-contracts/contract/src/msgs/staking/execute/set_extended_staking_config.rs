@@ -0,0 +1,46 @@
+use owner::state::OWNER;
+use staking_events::create_extended_staking_config_event;
+
+use super::{
+    state::{CommitteeSelection, ExtendedStakingConfig, SlashDestination, SlashingPenalty, EXTENDED_STAKING_CONFIG},
+    *,
+};
+
+impl ExecuteHandler for ExtendedStakingConfig {
+    /// Set extended staking config
+    fn execute(self, deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        if info.sender != OWNER.load(deps.storage)? {
+            return Err(ContractError::NotOwner);
+        }
+
+        if let Some(slashing) = &self.slashing {
+            if let SlashingPenalty::Fraction(fraction) = slashing.penalty {
+                if fraction > Decimal::one() {
+                    return Err(ContractError::InvalidSlashingFraction(fraction));
+                }
+            }
+
+            if let SlashDestination::Treasury { address } = &slashing.destination {
+                deps.api.addr_validate(address)?;
+            }
+        }
+
+        if let CommitteeSelection::StakeWeighted { stake_cap } = self.committee_selection {
+            if stake_cap.is_zero() {
+                return Err(ContractError::ZeroStakeCap);
+            }
+        }
+
+        EXTENDED_STAKING_CONFIG.save(deps.storage, &self)?;
+
+        Ok(Response::new()
+            .add_attribute("action", "set-extended-staking-config")
+            .add_event(create_extended_staking_config_event(self)))
+    }
+}
+
+impl From<ExtendedStakingConfig> for crate::msgs::ExecuteMsg {
+    fn from(config: ExtendedStakingConfig) -> Self {
+        ContractExecuteMsg::SetExtendedStakingConfig(config).into()
+    }
+}
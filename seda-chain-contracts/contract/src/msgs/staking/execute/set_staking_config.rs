@@ -1,7 +1,10 @@
 use owner::state::OWNER;
 use staking_events::create_staking_config_event;
 
-use super::{state::STAKING_CONFIG, *};
+use super::{
+    state::{STAKERS, STAKING_CONFIG},
+    *,
+};
 
 impl ExecuteHandler for StakingConfig {
     /// Set staking config
@@ -19,6 +22,7 @@ impl ExecuteHandler for StakingConfig {
         }
 
         STAKING_CONFIG.save(deps.storage, &self)?;
+        STAKERS.sync_all_eligibility(deps.storage)?;
 
         Ok(Response::new()
             .add_attribute("action", "set-staking-config")
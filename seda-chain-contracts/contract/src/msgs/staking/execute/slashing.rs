@@ -0,0 +1,72 @@
+use staking_events::create_executor_slashed_event;
+
+use super::{
+    state::{SlashDestination, SlashingConfig, SlashingPenalty, STAKERS},
+    *,
+};
+
+/// Slashes the tokens of an executor according to the slashing config.
+///
+/// The penalty applies to everything the executor still has in the contract, so unstaking
+/// after committing does not escape it. It is taken from the staked tokens first,
+/// then from the tokens still unbonding and last from the pending withdrawals that already matured.
+///
+/// Returns the message moving the slashed funds, the slashing event and the updated staker,
+/// or `None` if the executor is not a staker or has nothing left to slash.
+pub(crate) fn slash_executor(
+    store: &mut dyn Storage,
+    public_key: &PublicKey,
+    config: &SlashingConfig,
+    token: &str,
+    dr_id: &str,
+    height: u64,
+) -> Result<Option<(BankMsg, Event, Staker)>, ContractError> {
+    let Some(mut staker) = STAKERS.may_get_staker(store, public_key)? else {
+        return Ok(None);
+    };
+
+    let slashable = staker.tokens_staked + staker.tokens_pending_withdrawal;
+    let amount = match &config.penalty {
+        SlashingPenalty::Fraction(fraction) => slashable.mul_floor(*fraction),
+        SlashingPenalty::Fixed(amount) => (*amount).min(slashable),
+    };
+    if amount.is_zero() {
+        return Ok(None);
+    }
+
+    let from_staked = amount.min(staker.tokens_staked);
+    staker.tokens_staked -= from_staked;
+
+    // whatever is left comes out of the unbonding entries, and then out of the matured withdrawals
+    let from_pending = amount - from_staked;
+    if !from_pending.is_zero() {
+        STAKERS.slash_unbonding(store, public_key, from_pending, height)?;
+        staker.tokens_pending_withdrawal -= from_pending;
+    }
+
+    // an executor slashed down to nothing is removed, same as after withdrawing everything
+    if staker.tokens_staked.is_zero() && staker.tokens_pending_withdrawal.is_zero() {
+        STAKERS.remove(store, public_key.clone())?;
+    } else {
+        STAKERS.update(store, public_key.clone(), &staker)?;
+    }
+
+    let (bank_msg, destination) = match &config.destination {
+        SlashDestination::Burn => (
+            BankMsg::Burn {
+                amount: coins(amount.u128(), token),
+            },
+            "burn".to_string(),
+        ),
+        SlashDestination::Treasury { address } => (
+            BankMsg::Send {
+                to_address: address.clone(),
+                amount:     coins(amount.u128(), token),
+            },
+            address.clone(),
+        ),
+    };
+
+    let event = create_executor_slashed_event(public_key.to_hex(), dr_id.to_string(), amount, destination);
+    Ok(Some((bank_msg, event, staker)))
+}
@@ -1,7 +1,10 @@
 use cosmwasm_std::{Event, Uint128};
 use seda_common::msgs::staking::{Staker, StakingConfig};
 
-use super::CONTRACT_VERSION;
+use super::{
+    state::{CommitteeSelection, ExtendedStakingConfig, SlashDestination, SlashingPenalty},
+    CONTRACT_VERSION,
+};
 
 pub fn create_executor_event(staker: Staker, public_key: String) -> Event {
     Event::new("seda-executor").add_attributes([
@@ -47,3 +50,52 @@ pub fn create_staking_config_event(config: StakingConfig) -> Event {
         ("allowlist_enabled", config.allowlist_enabled.to_string()),
     ])
 }
+
+pub fn create_extended_staking_config_event(config: ExtendedStakingConfig) -> Event {
+    let (slashing_penalty, slashing_destination) = match config.slashing {
+        Some(slashing) => (
+            match slashing.penalty {
+                SlashingPenalty::Fraction(fraction) => format!("fraction:{fraction}"),
+                SlashingPenalty::Fixed(amount) => format!("fixed:{amount}"),
+            },
+            match slashing.destination {
+                SlashDestination::Burn => "burn".to_string(),
+                SlashDestination::Treasury { address } => address,
+            },
+        ),
+        None => ("none".to_string(), "none".to_string()),
+    };
+
+    Event::new("seda-extended-staking-config").add_attributes([
+        ("version", CONTRACT_VERSION.to_string()),
+        (
+            "unbonding_period_in_blocks",
+            config.unbonding_period_in_blocks.to_string(),
+        ),
+        ("slashing_penalty", slashing_penalty),
+        ("slashing_destination", slashing_destination),
+        (
+            "committee_selection",
+            match config.committee_selection {
+                CommitteeSelection::Window => "window".to_string(),
+                CommitteeSelection::Vrf => "vrf".to_string(),
+                CommitteeSelection::StakeWeighted { stake_cap } => format!("stake_weighted:{stake_cap}"),
+            },
+        ),
+    ])
+}
+
+pub(crate) fn create_executor_slashed_event(
+    public_key: String,
+    dr_id: String,
+    amount: Uint128,
+    destination: String,
+) -> Event {
+    Event::new("seda-executor-slashed").add_attributes([
+        ("version", CONTRACT_VERSION.to_string()),
+        ("identity", public_key),
+        ("dr_id", dr_id),
+        ("amount", amount.to_string()),
+        ("destination", destination),
+    ])
+}
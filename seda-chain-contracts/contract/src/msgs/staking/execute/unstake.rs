@@ -21,9 +21,24 @@ impl ExecuteHandler for execute::unstake::Execute {
         // update the executor
         executor.tokens_staked -= self.amount;
         executor.tokens_pending_withdrawal += self.amount;
-        state::STAKERS.update(deps.storage, public_key, &executor)?;
+        state::STAKERS.update(deps.storage, public_key.clone(), &executor)?;
 
-        // TODO: emit when pending tokens can be withdrawn
+        // lock the unstaked tokens until the unbonding period has passed
+        let unbonding_period = state::EXTENDED_STAKING_CONFIG
+            .load(deps.storage)?
+            .unbonding_period_in_blocks;
+        let withdrawable_at = env.block.height + unbonding_period;
+        if unbonding_period > 0 {
+            state::STAKERS.add_unbonding(
+                deps.storage,
+                &public_key,
+                state::UnbondingEntry {
+                    amount: self.amount,
+                    withdrawable_at,
+                },
+                env.block.height,
+            )?;
+        }
 
         Ok(Response::new().add_attribute("action", "unstake").add_events([
             create_executor_action_event(
@@ -32,7 +47,8 @@ impl ExecuteHandler for execute::unstake::Execute {
                 info.sender.to_string(),
                 self.amount,
                 seq,
-            ),
+            )
+            .add_attribute("withdrawable_at", withdrawable_at.to_string()),
             create_executor_event(executor, self.public_key),
         ]))
     }
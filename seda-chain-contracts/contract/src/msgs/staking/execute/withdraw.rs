@@ -12,16 +12,14 @@ impl ExecuteHandler for execute::withdraw::Execute {
         let seq = inc_get_seq(deps.storage, &public_key)?;
         self.verify(public_key.as_ref(), &chain_id, env.contract.address.as_str(), seq)?;
 
-        // TODO: add delay after calling unstake
         let token = TOKEN.load(deps.storage)?;
 
-        // error if amount is greater than pending tokens
+        // error if amount is greater than the pending tokens that finished unbonding
         let mut executor = state::STAKERS.get_staker(deps.storage, &public_key)?;
-        if self.amount > executor.tokens_pending_withdrawal {
-            return Err(ContractError::InsufficientFunds(
-                executor.tokens_pending_withdrawal,
-                self.amount,
-            ));
+        let unbonding = state::STAKERS.get_unbonding_amount(deps.storage, &public_key, env.block.height)?;
+        let withdrawable = executor.tokens_pending_withdrawal.saturating_sub(unbonding);
+        if self.amount > withdrawable {
+            return Err(ContractError::InsufficientFunds(withdrawable, self.amount));
         }
 
         // update the executor (remove if balances are zero)
@@ -29,6 +27,7 @@ impl ExecuteHandler for execute::withdraw::Execute {
         if executor.tokens_pending_withdrawal.is_zero() && executor.tokens_staked.is_zero() {
             state::STAKERS.remove(deps.storage, public_key)?;
         } else {
+            state::STAKERS.prune_unbonding(deps.storage, &public_key, env.block.height)?;
             state::STAKERS.update(deps.storage, public_key, &executor)?;
         }
 
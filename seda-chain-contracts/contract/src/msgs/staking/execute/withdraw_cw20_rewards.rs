@@ -0,0 +1,57 @@
+use cw20::Cw20ExecuteMsg;
+use staking_events::create_executor_action_event;
+
+use super::*;
+use crate::state::*;
+
+/// Sends the executor rewards paid in a CW20 token to the sender.
+pub(in crate::msgs::staking) fn withdraw_cw20_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_contract: String,
+    withdraw: execute::withdraw::Execute,
+) -> Result<Response, ContractError> {
+    // verify the proof
+    let chain_id = CHAIN_ID.load(deps.storage)?;
+    let public_key = PublicKey::from_hex_str(&withdraw.public_key)?;
+    let seq = inc_get_seq(deps.storage, &public_key)?;
+    withdraw.verify(public_key.as_ref(), &chain_id, env.contract.address.as_str(), seq)?;
+
+    // error if amount is greater than the rewards of the executor
+    let cw20_contract = deps.api.addr_validate(&cw20_contract)?;
+    let key = (&cw20_contract, public_key.as_ref());
+    let rewards = state::CW20_REWARDS.may_load(deps.storage, key)?.unwrap_or_default();
+    if withdraw.amount > rewards {
+        return Err(ContractError::InsufficientFunds(rewards, withdraw.amount));
+    }
+
+    let remaining = rewards - withdraw.amount;
+    if remaining.is_zero() {
+        state::CW20_REWARDS.remove(deps.storage, key);
+    } else {
+        state::CW20_REWARDS.save(deps.storage, key, &remaining)?;
+    }
+
+    // send the tokens to the executor
+    let transfer_msg = WasmMsg::Execute {
+        contract_addr: cw20_contract.to_string(),
+        msg:           to_json_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount:    withdraw.amount,
+        })?,
+        funds:         vec![],
+    };
+
+    Ok(Response::new()
+        .add_message(transfer_msg)
+        .add_attribute("action", "withdraw_cw20_rewards")
+        .add_attribute("cw20_contract", cw20_contract)
+        .add_event(create_executor_action_event(
+            "withdraw_cw20_rewards",
+            withdraw.public_key,
+            info.sender.to_string(),
+            withdraw.amount,
+            seq,
+        )))
+}
@@ -1,6 +1,7 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
 pub use seda_common::msgs::staking::query::{is_executor_eligible, QueryMsg};
 use seda_common::msgs::staking::StakerAndSeq;
-use state::{is_eligible_for_dr::is_eligible_for_dr, STAKERS};
+use state::{is_eligible_for_dr::is_eligible_for_dr, ExtendedStakingConfig, UnbondingEntry, CW20_REWARDS, STAKERS};
 
 use super::*;
 use crate::state::get_seq;
@@ -34,27 +35,120 @@ impl QueryHandler for QueryMsg {
 
 impl QueryHandler for is_executor_eligible::Query {
     fn query(self, deps: Deps, env: Env) -> Result<Binary, ContractError> {
-        let (executor, dr_id, _) = self.parts()?;
-        let executor = PublicKey(executor);
-
-        // Validate signature
-        let chain_id = crate::state::CHAIN_ID.load(deps.storage)?;
-        if self
-            .verify(&executor, &chain_id, env.contract.address.as_str())
-            .is_err()
-        {
-            return Ok(to_json_binary(&false)?);
-        }
-
-        // Check DR is in data_request_pool
-        if data_requests::state::load_request(deps.storage, &dr_id).is_err() {
-            return Ok(to_json_binary(&false)?);
-        }
-
-        if !STAKERS.is_staker_executor(deps.storage, &executor)? {
-            return Ok(to_json_binary(&false)?);
-        }
-
-        Ok(to_json_binary(&is_eligible_for_dr(deps, dr_id, executor)?)?)
+        Ok(to_json_binary(&is_executor_eligible(deps, &env, self, None)?)?)
+    }
+}
+
+/// Checks whether the executor of the query is eligible for the data request.
+///
+/// With VRF committee selection the executor is only eligible with a `vrf_proof` proving their selection.
+fn is_executor_eligible(
+    deps: Deps,
+    env: &Env,
+    query: is_executor_eligible::Query,
+    vrf_proof: Option<&[u8]>,
+) -> Result<bool, ContractError> {
+    let (executor, dr_id, _) = query.parts()?;
+    let executor = PublicKey(executor);
+
+    // Validate signature
+    let chain_id = crate::state::CHAIN_ID.load(deps.storage)?;
+    if query
+        .verify(&executor, &chain_id, env.contract.address.as_str())
+        .is_err()
+    {
+        return Ok(false);
+    }
+
+    // Check DR is in data_request_pool
+    if data_requests::state::load_request(deps.storage, &dr_id).is_err() {
+        return Ok(false);
+    }
+
+    if !STAKERS.is_staker_executor(deps.storage, &executor)? {
+        return Ok(false);
+    }
+
+    match is_eligible_for_dr(deps, env, dr_id, executor, vrf_proof.unwrap_or_default()) {
+        Err(ContractError::InvalidVrfProof) => Ok(false),
+        res => res,
+    }
+}
+
+/// Staking query messages specific to this contract.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum ContractQueryMsg {
+    /// Like `IsExecutorEligible`, along with the executor's VRF proof (hex encoded)
+    /// that they were selected for the committee of the data request.
+    #[returns(bool)]
+    IsExecutorEligibleWithVrfProof {
+        query:     is_executor_eligible::Query,
+        vrf_proof: String,
+    },
+    #[returns(Option<StakerUnbonding>)]
+    GetStakerUnbonding { public_key: String },
+    #[returns(ExtendedStakingConfig)]
+    GetExtendedStakingConfig {},
+    #[returns(Uint128)]
+    GetCw20Rewards {
+        public_key:    String,
+        cw20_contract: String,
+    },
+}
+
+/// The pending withdrawal tokens of a staker, split into what can be withdrawn now
+/// and the entries that are still unbonding.
+#[cw_serde]
+pub struct StakerUnbonding {
+    pub withdrawable: Uint128,
+    pub unbonding:    Vec<UnbondingEntry>,
+}
+
+impl QueryHandler for ContractQueryMsg {
+    fn query(self, deps: Deps, env: Env) -> Result<Binary, ContractError> {
+        let binary = match self {
+            ContractQueryMsg::IsExecutorEligibleWithVrfProof { query, vrf_proof } => {
+                let vrf_proof = hex::decode(vrf_proof)?;
+                to_json_binary(&is_executor_eligible(deps, &env, query, Some(&vrf_proof))?)?
+            }
+            ContractQueryMsg::GetStakerUnbonding { public_key } => {
+                let public_key = PublicKey::from_hex_str(&public_key)?;
+                let unbonding = STAKERS
+                    .may_get_staker(deps.storage, &public_key)?
+                    .map(|staker| {
+                        let unbonding = STAKERS.get_unbonding(deps.storage, &public_key, env.block.height)?;
+                        let unbonding_amount: Uint128 = unbonding.iter().map(|entry| entry.amount).sum();
+                        Ok::<_, ContractError>(StakerUnbonding {
+                            withdrawable: staker.tokens_pending_withdrawal.saturating_sub(unbonding_amount),
+                            unbonding,
+                        })
+                    })
+                    .transpose()?;
+                to_json_binary(&unbonding)?
+            }
+            ContractQueryMsg::GetExtendedStakingConfig {} => {
+                to_json_binary(&state::EXTENDED_STAKING_CONFIG.load(deps.storage)?)?
+            }
+            ContractQueryMsg::GetCw20Rewards {
+                public_key,
+                cw20_contract,
+            } => {
+                let public_key = PublicKey::from_hex_str(&public_key)?;
+                let cw20_contract = deps.api.addr_validate(&cw20_contract)?;
+                let rewards = CW20_REWARDS
+                    .may_load(deps.storage, (&cw20_contract, public_key.as_ref()))?
+                    .unwrap_or_default();
+                to_json_binary(&rewards)?
+            }
+        };
+
+        Ok(binary)
+    }
+}
+
+impl From<ContractQueryMsg> for crate::msgs::QueryMsg {
+    fn from(msg: ContractQueryMsg) -> Self {
+        crate::msgs::ContractQueryMsg::Staking(msg).into()
     }
 }
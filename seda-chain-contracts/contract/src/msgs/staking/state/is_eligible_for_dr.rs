@@ -1,43 +1,117 @@
 use cosmwasm_std::Uint256;
-use data_requests::state::load_request;
+use data_requests::state::{load_request, may_get_dr_expiration_height, DR_COMMITTEES};
+use sha3::{Digest, Keccak256};
+use vrf_rs::Secp256k1Sha256;
 
-use super::{staking::state::STAKERS, *};
+use super::{
+    staking::state::{CommitteeSelection, EXTENDED_STAKING_CONFIG, STAKERS},
+    *,
+};
+use crate::state::CHAIN_ID;
 
-pub fn is_eligible_for_dr(deps: Deps, dr_id: [u8; 32], public_key: PublicKey) -> Result<bool, ContractError> {
+/// Checks whether an executor is part of the committee of a data request.
+///
+/// With [`CommitteeSelection::Vrf`] the `vrf_proof` must prove the executor's
+/// [`committee_selection_alpha`] for the data request, it is ignored otherwise.
+///
+/// A data request posted with [`CommitteeSelection::StakeWeighted`] keeps the committee drawn at posting time.
+///
+/// The VRF selection only yields a committee of `replication_factor` executors on average, so it can come up short.
+/// Once half of the commit stage has passed, the executors of the window selection are eligible as well,
+/// still proving their VRF output, so that an undersized committee does not leave the data request to time out.
+pub fn is_eligible_for_dr(
+    deps: Deps,
+    env: &Env,
+    dr_id: [u8; 32],
+    public_key: PublicKey,
+    vrf_proof: &[u8],
+) -> Result<bool, ContractError> {
     let data_request = load_request(deps.storage, &dr_id)?;
-    let config = STAKING_CONFIG.load(deps.storage)?;
-
-    let stakers = STAKERS.stakers.range_raw(deps.storage, None, None, Order::Ascending);
-    let all_active_stakers = stakers
-        .filter_map(|stakers_info| {
-            if let Ok((public_key, staker)) = stakers_info {
-                if staker.tokens_staked >= config.minimum_stake_for_committee_eligibility {
-                    return Some((public_key, staker));
-                }
-            }
 
-            None
-        })
-        .collect::<Vec<(Vec<u8>, Staker)>>();
+    let Some(eligible_index) = STAKERS.eligible_index(deps.storage, public_key.clone())? else {
+        return Ok(false);
+    };
+    if let Some(committee) = DR_COMMITTEES.may_load(deps.storage, &dr_id)? {
+        return Ok(committee.contains(&public_key));
+    }
+    let eligible_len = STAKERS.eligible_len(deps.storage)?;
+
+    match EXTENDED_STAKING_CONFIG.load(deps.storage)?.committee_selection {
+        // without a drawn committee, stake-weighted requests were posted under the window selection
+        CommitteeSelection::Window | CommitteeSelection::StakeWeighted { .. } => Ok(is_in_window(
+            dr_id,
+            eligible_index,
+            eligible_len,
+            data_request.replication_factor,
+        )),
+        CommitteeSelection::Vrf => {
+            let chain_id = CHAIN_ID.load(deps.storage)?;
+            let alpha = committee_selection_alpha(&dr_id, &chain_id, env.contract.address.as_str());
+            let output = verify_vrf_proof(&public_key, vrf_proof, &alpha)?;
+            if is_selected_by_vrf(output, data_request.replication_factor, eligible_len) {
+                return Ok(true);
+            }
 
-    let (active_staker_index, _) = all_active_stakers
-        .iter()
-        .enumerate()
-        .find(|(_, (pk, _staker))| public_key.as_ref() == pk.as_slice())
-        .expect("Could not find staker");
+            let Some(commit_deadline) = may_get_dr_expiration_height(deps.storage, &dr_id)? else {
+                return Ok(false);
+            };
+            let fallback_height = data_request.height + commit_deadline.saturating_sub(data_request.height) / 2;
+            Ok(env.block.height >= fallback_height
+                && is_in_window(dr_id, eligible_index, eligible_len, data_request.replication_factor))
+        }
+    }
+}
 
-    let executor_index = Uint256::from(active_staker_index as u64);
-    let executor_length = Uint256::from(all_active_stakers.len() as u64);
+fn is_in_window(dr_id: [u8; 32], eligible_index: u32, eligible_len: u32, replication_factor: u16) -> bool {
+    let executor_index = Uint256::from(eligible_index);
+    let executor_length = Uint256::from(eligible_len);
 
     let dr_index = Uint256::from_be_bytes(dr_id) % executor_length;
-    let replication_factor = Uint256::from(data_request.replication_factor);
+    let replication_factor = Uint256::from(replication_factor);
     let end_index = (dr_index + replication_factor) % executor_length;
 
     if dr_index < end_index {
         // No overflow case
-        Ok(executor_index >= dr_index && executor_index < end_index)
+        executor_index >= dr_index && executor_index < end_index
     } else {
         // Overflow case
-        Ok(executor_index >= dr_index || executor_index < end_index)
+        executor_index >= dr_index || executor_index < end_index
+    }
+}
+
+/// The message an executor proves with their key to take part in the VRF committee selection of a data request.
+pub fn committee_selection_alpha(dr_id: &[u8; 32], chain_id: &str, contract_addr: &str) -> Hash {
+    let mut hasher = Keccak256::new();
+    hasher.update(b"committee_selection");
+    hasher.update(dr_id);
+    hasher.update(chain_id.as_bytes());
+    hasher.update(contract_addr.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Verifies a VRF proof against the executor's secp256k1 public key and returns its output.
+pub fn verify_vrf_proof(public_key: &PublicKey, proof: &[u8], alpha: &Hash) -> Result<Hash, ContractError> {
+    let output = Secp256k1Sha256::default()
+        .verify(public_key.as_ref(), proof, alpha)
+        .map_err(|_| ContractError::InvalidVrfProof)?;
+
+    output.try_into().map_err(|_| ContractError::InvalidVrfProof)
+}
+
+/// Whether a VRF output falls below the threshold that selects,
+/// on average, `replication_factor` out of `eligible_len` executors.
+///
+/// Each executor is selected independently, so the committee size is only `replication_factor` in expectation.
+/// This is deliberate: nobody learns the committee before its members reveal their proofs,
+/// and a committee that comes up short is widened by the window fallback of [`is_eligible_for_dr`].
+pub fn is_selected_by_vrf(output: Hash, replication_factor: u16, eligible_len: u32) -> bool {
+    if eligible_len == 0 {
+        return false;
     }
+    if u32::from(replication_factor) >= eligible_len {
+        return true;
+    }
+
+    let threshold = Uint256::MAX / Uint256::from(eligible_len) * Uint256::from(replication_factor);
+    Uint256::from_be_bytes(output) < threshold
 }
@@ -1,6 +1,8 @@
 pub mod is_eligible_for_dr;
+pub mod stake_weighted;
 pub mod stakers_map;
 
+use cosmwasm_schema::cw_serde;
 use seda_common::msgs::staking::{Staker, StakingConfig};
 use stakers_map::{new_stakers_map, StakersMap};
 
@@ -9,5 +11,89 @@ use super::*;
 /// Governance-controlled staking configuration parameters.
 pub const STAKING_CONFIG: Item<StakingConfig> = Item::new("staking_config");
 
+/// Governance-controlled staking parameters that are not part of [`StakingConfig`].
+#[cw_serde]
+pub struct ExtendedStakingConfig {
+    /// Number of blocks unstaked tokens stay locked before they can be withdrawn.
+    pub unbonding_period_in_blocks: u64,
+    /// Penalty for executors that commit but do not reveal before a data request times out.
+    /// Slashing is disabled when unset.
+    pub slashing:                   Option<SlashingConfig>,
+    /// How the committee of a data request is selected among the eligible executors.
+    #[serde(default)]
+    pub committee_selection:        CommitteeSelection,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum CommitteeSelection {
+    /// A contiguous window of eligible executors starting at an index derived from the data request id.
+    #[default]
+    Window,
+    /// Executors prove with their VRF key that they were selected for a data request.
+    /// As the committee size is only right on average, the executors of the window selection
+    /// can commit too once half of the commit stage has passed.
+    Vrf,
+    /// Executors are drawn from the data request id with a probability proportional to their stake.
+    /// Stake above `stake_cap` does not add weight, so large stakers cannot dominate committees.
+    StakeWeighted { stake_cap: Uint128 },
+}
+
+#[cw_serde]
+pub struct SlashingConfig {
+    pub penalty:     SlashingPenalty,
+    pub destination: SlashDestination,
+}
+
+#[cw_serde]
+pub enum SlashingPenalty {
+    /// Slash a fraction of the executor's staked and pending withdrawal tokens.
+    /// The penalty is taken from the staked tokens first, then from the unbonding ones and then from the matured ones.
+    Fraction(Decimal),
+    /// Slash a fixed amount, capped at the executor's staked and pending withdrawal tokens.
+    /// The penalty is taken from the staked tokens first, then from the unbonding ones and then from the matured ones.
+    Fixed(Uint128),
+}
+
+#[cw_serde]
+pub enum SlashDestination {
+    Burn,
+    Treasury { address: String },
+}
+
+/// Governance-controlled staking configuration parameters specific to this contract.
+pub const EXTENDED_STAKING_CONFIG: Item<ExtendedStakingConfig> = Item::new("extended_staking_config");
+
+/// Unstaked tokens that are locked until `withdrawable_at`.
+#[cw_serde]
+pub struct UnbondingEntry {
+    pub amount:          Uint128,
+    /// Block height from which the tokens can be withdrawn.
+    pub withdrawable_at: u64,
+}
+
 /// A map of stakers (of address to info).
 pub const STAKERS: StakersMap = new_stakers_map!("data_request_executors");
+
+/// Executor rewards paid in CW20 tokens, keyed by CW20 contract and executor public key.
+/// Stakes are in the native token, so these are kept apart until the executor withdraws them.
+pub const CW20_REWARDS: Map<(&Addr, &[u8]), Uint128> = Map::new("cw20_executor_rewards");
+
+pub fn add_cw20_reward(
+    store: &mut dyn Storage,
+    cw20_contract: &Addr,
+    public_key: &PublicKey,
+    amount: Uint128,
+) -> StdResult<()> {
+    CW20_REWARDS.update(store, (cw20_contract, public_key.as_ref()), |rewards| {
+        Ok::<_, StdError>(rewards.unwrap_or_default() + amount)
+    })?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = ""]
+mod tests {
+    use super::*;
+    mod stake_weighted_tests;
+}
@@ -0,0 +1,73 @@
+use cosmwasm_std::Uint256;
+use sha3::{Digest, Keccak256};
+
+use super::{staking::state::STAKERS, *};
+
+/// Loads the eligible executors with their selection weight: their current staked tokens capped at `stake_cap`.
+///
+/// The executors come sorted by public key from the weighted eligible set, kept up to date as stakes change,
+/// so the selection only depends on the stakes, not on the order in which executors entered the eligible set.
+pub fn load_weighted_executors(store: &dyn Storage, stake_cap: Uint128) -> StdResult<Vec<(PublicKey, Uint128)>> {
+    Ok(STAKERS
+        .weighted_eligible(store)?
+        .into_iter()
+        .map(|(public_key, stake)| (public_key, stake.min(stake_cap)))
+        .collect())
+}
+
+/// Draws the committee of a data request from the current stakes.
+///
+/// This runs once, when the data request is posted, and the committee is stored along with it:
+/// checking eligibility is then a lookup, and later stake changes do not move the committee.
+pub fn draw_committee(
+    store: &dyn Storage,
+    dr_id: &Hash,
+    replication_factor: u16,
+    stake_cap: Uint128,
+) -> Result<Vec<PublicKey>, ContractError> {
+    let executors = load_weighted_executors(store, stake_cap)?;
+    select_stake_weighted(dr_id, replication_factor, executors)
+}
+
+/// Selects a committee of `replication_factor` executors with a probability proportional to their weight.
+///
+/// Each seat draws a deterministic random number from the data request id and the seat number
+/// and picks the executor it falls on among the cumulative weights of the executors not selected yet.
+pub fn select_stake_weighted(
+    dr_id: &Hash,
+    replication_factor: u16,
+    mut executors: Vec<(PublicKey, Uint128)>,
+) -> Result<Vec<PublicKey>, ContractError> {
+    let mut total_weight: Uint256 = executors.iter().map(|(_, weight)| Uint256::from(*weight)).sum();
+    let mut committee = Vec::with_capacity(replication_factor as usize);
+
+    for seat in 0..replication_factor {
+        if executors.is_empty() || total_weight.is_zero() {
+            break;
+        }
+
+        let mut hasher = Keccak256::new();
+        hasher.update(dr_id);
+        hasher.update(seat.to_be_bytes());
+        let seed: Hash = hasher.finalize().into();
+        let mut target = Uint256::from_be_bytes(seed) % total_weight;
+
+        let position = executors
+            .iter()
+            .position(|(_, weight)| {
+                let weight = Uint256::from(*weight);
+                if target < weight {
+                    return true;
+                }
+                target -= weight;
+                false
+            })
+            .ok_or(ContractError::StakeWeightedTargetOutOfRange)?;
+
+        let (public_key, weight) = executors.remove(position);
+        total_weight -= Uint256::from(weight);
+        committee.push(public_key);
+    }
+
+    Ok(committee)
+}
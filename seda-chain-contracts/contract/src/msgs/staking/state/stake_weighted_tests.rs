@@ -0,0 +1,147 @@
+use sha3::{Digest, Keccak256};
+
+use super::{
+    stake_weighted::{draw_committee, select_stake_weighted},
+    *,
+};
+use crate::new_public_key;
+
+fn executors(weights: &[u128]) -> Vec<(PublicKey, Uint128)> {
+    weights
+        .iter()
+        .map(|weight| (new_public_key().1, Uint128::new(*weight)))
+        .collect()
+}
+
+fn dr_id(nonce: u32) -> Hash {
+    let mut hasher = Keccak256::new();
+    hasher.update(nonce.to_be_bytes());
+    hasher.finalize().into()
+}
+
+/// Counts how many times each executor is selected over `rounds` data requests.
+fn selection_counts(executors: &[(PublicKey, Uint128)], replication_factor: u16, rounds: u32) -> Vec<u32> {
+    let mut counts = vec![0; executors.len()];
+    for nonce in 0..rounds {
+        for selected in select_stake_weighted(&dr_id(nonce), replication_factor, executors.to_vec()).unwrap() {
+            let index = executors.iter().position(|(pk, _)| pk == &selected).unwrap();
+            counts[index] += 1;
+        }
+    }
+    counts
+}
+
+#[test]
+fn selection_is_deterministic() {
+    let executors = executors(&[5, 10, 20, 40]);
+
+    let first = select_stake_weighted(&dr_id(1), 2, executors.clone()).unwrap();
+    let second = select_stake_weighted(&dr_id(1), 2, executors).unwrap();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn selects_distinct_executors_up_to_replication_factor() {
+    let executors = executors(&[1, 1, 1000]);
+
+    let committee = select_stake_weighted(&dr_id(1), 3, executors.clone()).unwrap();
+    assert_eq!(3, committee.len());
+    assert!(executors.iter().all(|(pk, _)| committee.contains(pk)));
+
+    // the committee cannot be bigger than the set of executors
+    let committee = select_stake_weighted(&dr_id(1), 5, executors).unwrap();
+    assert_eq!(3, committee.len());
+}
+
+#[test]
+fn selection_is_proportional_to_stake() {
+    let rounds = 4000;
+    let executors = executors(&[1, 1, 2]);
+
+    let counts = selection_counts(&executors, 1, rounds);
+
+    // expect 25%, 25% and 50% of the seats, within a few percent
+    assert!((900..1100).contains(&counts[0]), "{counts:?}");
+    assert!((900..1100).contains(&counts[1]), "{counts:?}");
+    assert!((1850..2150).contains(&counts[2]), "{counts:?}");
+}
+
+#[test]
+fn capped_stake_limits_selection() {
+    let rounds = 3000;
+    let uncapped = executors(&[10, 10, 1000]);
+    let capped = uncapped
+        .iter()
+        .map(|(pk, weight)| (pk.clone(), (*weight).min(Uint128::new(10))))
+        .collect::<Vec<_>>();
+
+    // without a cap the large staker takes nearly every seat
+    let counts = selection_counts(&uncapped, 1, rounds);
+    assert!(counts[2] > 2850, "{counts:?}");
+
+    // with the cap every executor is selected about a third of the time
+    let counts = selection_counts(&capped, 1, rounds);
+    assert!(counts.iter().all(|count| (900..1100).contains(count)), "{counts:?}");
+}
+
+#[test]
+fn weighted_eligible_set_follows_stake_changes() {
+    let mut store = cosmwasm_std::testing::MockStorage::new();
+    STAKING_CONFIG
+        .save(
+            &mut store,
+            &StakingConfig {
+                minimum_stake_to_register:               Uint128::new(1),
+                minimum_stake_for_committee_eligibility: Uint128::new(10),
+                allowlist_enabled:                       false,
+            },
+        )
+        .unwrap();
+    STAKERS.initialize(&mut store).unwrap();
+
+    let staker = |tokens_staked: u128| Staker {
+        memo:                      None,
+        tokens_staked:             Uint128::new(tokens_staked),
+        tokens_pending_withdrawal: Uint128::zero(),
+    };
+    let executors = executors(&[10; 500]);
+    for (public_key, _) in &executors {
+        STAKERS.insert(&mut store, public_key.clone(), &staker(10)).unwrap();
+    }
+
+    // stakes go up, drop below the minimum and stakers leave
+    for (index, (public_key, _)) in executors.iter().enumerate() {
+        match index % 4 {
+            0 => STAKERS
+                .update(&mut store, public_key.clone(), &staker(10 + index as u128))
+                .unwrap(),
+            1 => STAKERS.update(&mut store, public_key.clone(), &staker(5)).unwrap(),
+            2 => STAKERS.remove(&mut store, public_key.clone()).unwrap(),
+            _ => {}
+        }
+    }
+
+    // the cached set matches the stakes of the eligible executors, sorted by public key
+    let mut expected = (0..STAKERS.eligible_len(&store).unwrap())
+        .map(|index| {
+            let public_key = STAKERS.eligible.index_to_key.load(&store, index).unwrap();
+            let staker = STAKERS.get_staker(&store, &public_key).unwrap();
+            (public_key, staker.tokens_staked)
+        })
+        .collect::<Vec<_>>();
+    expected.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+    assert_eq!(250, expected.len());
+    assert_eq!(expected, STAKERS.weighted_eligible(&store).unwrap());
+
+    // rebuilding it from scratch gives the same set
+    STAKERS.weighted.remove(&mut store);
+    STAKERS.sync_all_eligibility(&mut store).unwrap();
+    assert_eq!(expected, STAKERS.weighted_eligible(&store).unwrap());
+
+    // the committee is drawn from it with the stakes capped
+    let committee = draw_committee(&store, &dr_id(1), 20, Uint128::new(100)).unwrap();
+    assert_eq!(20, committee.len());
+    assert!(committee
+        .iter()
+        .all(|selected| expected.iter().any(|(public_key, _)| public_key == selected)));
+}
@@ -5,31 +5,179 @@ use super::*;
 pub struct StakersMap<'a> {
     pub stakers:     Map<&'a PublicKey, Staker>,
     pub public_keys: EnumerableSet<PublicKey>,
+    pub unbonding:   Map<&'a PublicKey, Vec<UnbondingEntry>>,
+    /// The stakers that are currently eligible for committee selection.
+    pub eligible:    EnumerableSet<PublicKey>,
+    /// The eligible stakers with their staked tokens, sorted by public key,
+    /// kept up to date so that drawing a committee neither loads every staker nor sorts them.
+    pub weighted:    Item<Vec<(PublicKey, Uint128)>>,
 }
 
 impl StakersMap<'_> {
     pub fn initialize(&self, store: &mut dyn Storage) -> StdResult<()> {
         self.public_keys.initialize(store)?;
+        self.eligible.initialize(store)?;
         Ok(())
     }
 
     pub fn insert(&self, store: &mut dyn Storage, key: PublicKey, value: &Staker) -> StdResult<()> {
         self.stakers.save(store, &key, value)?;
-        self.public_keys.add(store, key)?;
+        self.public_keys.add(store, key.clone())?;
+        self.sync_eligibility(store, key)?;
         Ok(())
     }
 
     pub fn update(&self, store: &mut dyn Storage, key: PublicKey, value: &Staker) -> StdResult<()> {
         self.stakers.save(store, &key, value)?;
+        self.sync_eligibility(store, key)?;
         Ok(())
     }
 
     pub fn remove(&self, store: &mut dyn Storage, key: PublicKey) -> StdResult<()> {
         self.stakers.remove(store, &key);
-        self.public_keys.remove(store, key)?;
+        self.unbonding.remove(store, &key);
+        self.public_keys.remove(store, key.clone())?;
+        self.sync_eligibility(store, key)?;
         Ok(())
     }
 
+    /// Adds or removes the executor from the eligible set depending on its current stake,
+    /// the staking config and the allowlist, and updates its stake in the weighted eligible set.
+    pub fn sync_eligibility(&self, store: &mut dyn Storage, key: PublicKey) -> StdResult<()> {
+        let stake = self.sync_eligible_set(store, key.clone())?;
+        let mut weighted = self.weighted.may_load(store)?.unwrap_or_default();
+        match (
+            stake,
+            weighted.binary_search_by(|(public_key, _)| public_key.0.cmp(&key.0)),
+        ) {
+            (Some(stake), Ok(position)) => weighted[position].1 = stake,
+            (Some(stake), Err(position)) => weighted.insert(position, (key, stake)),
+            (None, Ok(position)) => {
+                weighted.remove(position);
+            }
+            (None, Err(_)) => return Ok(()),
+        }
+        self.weighted.save(store, &weighted)
+    }
+
+    /// Re-evaluates the eligibility of every staker, e.g. after the staking config changed,
+    /// and rebuilds the weighted eligible set.
+    pub fn sync_all_eligibility(&self, store: &mut dyn Storage) -> StdResult<()> {
+        let mut weighted = Vec::new();
+        for index in 0..self.len(store)? {
+            let key = self.public_keys.index_to_key.load(store, index)?;
+            if let Some(stake) = self.sync_eligible_set(store, key.clone())? {
+                weighted.push((key, stake));
+            }
+        }
+        weighted.sort_by(|(a, _), (b, _)| a.0.cmp(&b.0));
+        self.weighted.save(store, &weighted)
+    }
+
+    /// Adds or removes the executor from the eligible set, returning its staked tokens if it is eligible.
+    fn sync_eligible_set(&self, store: &mut dyn Storage, key: PublicKey) -> StdResult<Option<Uint128>> {
+        let is_eligible = self.is_staker_executor(store, &key)?;
+        match (is_eligible, self.eligible.has(store, key.clone())) {
+            (true, false) => self.eligible.add(store, key.clone())?,
+            (false, true) => self.eligible.remove(store, key.clone())?,
+            _ => {}
+        }
+
+        if !is_eligible {
+            return Ok(None);
+        }
+        Ok(Some(self.get_staker(store, &key)?.tokens_staked))
+    }
+
+    /// Returns the eligible executors with their staked tokens, sorted by public key.
+    pub fn weighted_eligible(&self, store: &dyn Storage) -> StdResult<Vec<(PublicKey, Uint128)>> {
+        Ok(self.weighted.may_load(store)?.unwrap_or_default())
+    }
+
+    /// Returns the position of the executor in the eligible set, if it is eligible.
+    pub fn eligible_index(&self, store: &dyn Storage, key: PublicKey) -> StdResult<Option<u32>> {
+        self.eligible.key_to_index.may_load(store, key)
+    }
+
+    /// Returns the number of executors eligible for committee selection.
+    pub fn eligible_len(&self, store: &dyn Storage) -> StdResult<u32> {
+        self.eligible.len(store)
+    }
+
+    /// Returns the unbonding entries of a staker that have not matured yet at the given height.
+    pub fn get_unbonding(
+        &self,
+        store: &dyn Storage,
+        pub_key: &PublicKey,
+        height: u64,
+    ) -> StdResult<Vec<UnbondingEntry>> {
+        let entries = self.unbonding.may_load(store, pub_key)?.unwrap_or_default();
+        Ok(entries
+            .into_iter()
+            .filter(|entry| entry.withdrawable_at > height)
+            .collect())
+    }
+
+    /// Returns the amount of a staker's pending withdrawal tokens that are still unbonding at the given height.
+    pub fn get_unbonding_amount(&self, store: &dyn Storage, pub_key: &PublicKey, height: u64) -> StdResult<Uint128> {
+        Ok(self
+            .get_unbonding(store, pub_key, height)?
+            .iter()
+            .map(|entry| entry.amount)
+            .sum())
+    }
+
+    /// Adds an unbonding entry for a staker, dropping the entries that have already matured.
+    pub fn add_unbonding(
+        &self,
+        store: &mut dyn Storage,
+        pub_key: &PublicKey,
+        entry: UnbondingEntry,
+        height: u64,
+    ) -> StdResult<()> {
+        let mut entries = self.get_unbonding(store, pub_key, height)?;
+        entries.push(entry);
+        self.unbonding.save(store, pub_key, &entries)
+    }
+
+    /// Drops the unbonding entries of a staker that have matured at the given height.
+    pub fn prune_unbonding(&self, store: &mut dyn Storage, pub_key: &PublicKey, height: u64) -> StdResult<()> {
+        let entries = self.get_unbonding(store, pub_key, height)?;
+        if entries.is_empty() {
+            self.unbonding.remove(store, pub_key);
+            return Ok(());
+        }
+
+        self.unbonding.save(store, pub_key, &entries)
+    }
+
+    /// Takes up to `amount` out of the unbonding entries of a staker that have not matured yet
+    /// at the given height, most recent entry first. Returns the amount taken.
+    pub fn slash_unbonding(
+        &self,
+        store: &mut dyn Storage,
+        pub_key: &PublicKey,
+        amount: Uint128,
+        height: u64,
+    ) -> StdResult<Uint128> {
+        let mut entries = self.get_unbonding(store, pub_key, height)?;
+        let mut taken = Uint128::zero();
+        for entry in entries.iter_mut().rev() {
+            let slashed = entry.amount.min(amount - taken);
+            entry.amount -= slashed;
+            taken += slashed;
+        }
+
+        entries.retain(|entry| !entry.amount.is_zero());
+        if entries.is_empty() {
+            self.unbonding.remove(store, pub_key);
+        } else {
+            self.unbonding.save(store, pub_key, &entries)?;
+        }
+
+        Ok(taken)
+    }
+
     pub fn may_get_staker(&self, store: &dyn Storage, pub_key: &PublicKey) -> StdResult<Option<Staker>> {
         self.stakers.may_load(store, pub_key)
     }
@@ -66,6 +214,9 @@ macro_rules! new_stakers_map {
         StakersMap {
             stakers:     Map::new(concat!($namespace, "_stakers")),
             public_keys: $crate::enumerable_set!(concat!($namespace, "_public_keys")),
+            unbonding:   Map::new(concat!($namespace, "_unbonding")),
+            eligible:    $crate::enumerable_set!(concat!($namespace, "_eligible")),
+            weighted:    Item::new(concat!($namespace, "_weighted")),
         }
     };
 }
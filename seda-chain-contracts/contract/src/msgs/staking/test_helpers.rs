@@ -2,15 +2,49 @@ use super::{
     msgs::staking::{execute, query},
     *,
 };
-use crate::{types::PublicKey, TestExecutor, TestInfo};
+use crate::{
+    msgs::staking::{
+        execute::ContractExecuteMsg,
+        query::{ContractQueryMsg, StakerUnbonding},
+        state::{is_eligible_for_dr::committee_selection_alpha, ExtendedStakingConfig},
+    },
+    types::PublicKey,
+    TestExecutor,
+    TestInfo,
+};
 
 impl TestInfo {
     #[track_caller]
     pub fn set_staking_config(&mut self, sender: &TestExecutor, config: StakingConfig) -> Result<(), ContractError> {
-        let msg = config.into();
+        let msg: msgs::ExecuteMsg = config.into();
+        self.execute(sender, &msg)
+    }
+
+    #[track_caller]
+    pub fn set_extended_staking_config(
+        &mut self,
+        sender: &TestExecutor,
+        config: ExtendedStakingConfig,
+    ) -> Result<(), ContractError> {
+        let msg: crate::msgs::ExecuteMsg = config.into();
         self.execute(sender, &msg)
     }
 
+    #[track_caller]
+    pub fn get_extended_staking_config(&self) -> ExtendedStakingConfig {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetExtendedStakingConfig {}.into();
+        self.query(msg).unwrap()
+    }
+
+    #[track_caller]
+    pub fn get_staker_unbonding(&self, executor: PublicKey) -> Option<StakerUnbonding> {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetStakerUnbonding {
+            public_key: executor.to_hex(),
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
     #[track_caller]
     pub fn stake(
         &mut self,
@@ -115,6 +149,45 @@ impl TestInfo {
         self.execute(sender, &msg)
     }
 
+    #[track_caller]
+    pub fn withdraw_cw20_rewards(
+        &mut self,
+        sender: &mut TestExecutor,
+        cw20_contract: &Addr,
+        amount: u128,
+    ) -> Result<(), ContractError> {
+        let seq = self.get_account_sequence(sender.pub_key());
+
+        let factory = execute::withdraw::Execute::factory(
+            sender.pub_key_hex(),
+            amount,
+            self.chain_id(),
+            self.contract_addr_str(),
+            seq,
+        );
+        let msg: crate::msgs::ExecuteMsg = ContractExecuteMsg::WithdrawCw20Rewards {
+            cw20_contract: cw20_contract.to_string(),
+            withdraw:      execute::withdraw::Execute {
+                public_key: sender.pub_key_hex(),
+                proof:      sender.prove_hex(factory.get_hash()),
+                amount:     amount.into(),
+            },
+        }
+        .into();
+
+        self.execute(sender, &msg)
+    }
+
+    #[track_caller]
+    pub fn get_cw20_rewards(&self, executor: PublicKey, cw20_contract: &Addr) -> u128 {
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::GetCw20Rewards {
+            public_key:    executor.to_hex(),
+            cw20_contract: cw20_contract.to_string(),
+        }
+        .into();
+        self.query::<_, Uint128>(msg).unwrap().u128()
+    }
+
     #[track_caller]
     pub fn is_staker_executor(&self, executor: &TestExecutor) -> bool {
         self.query(query::QueryMsg::IsStakerExecutor {
@@ -137,6 +210,35 @@ impl TestInfo {
         self.query(query).unwrap()
     }
 
+    /// Proves the executor's committee selection message for a data request.
+    pub fn committee_selection_proof(&self, sender: &TestExecutor, dr_id: &str) -> Vec<u8> {
+        let dr_id = Hash::from_hex_str(dr_id).unwrap();
+        let alpha = committee_selection_alpha(&dr_id, self.chain_id(), self.contract_addr_str());
+        sender.prove(&alpha)
+    }
+
+    #[track_caller]
+    pub fn is_executor_eligible_with_vrf(&self, sender: &TestExecutor, dr_id: String) -> bool {
+        let vrf_proof = self.committee_selection_proof(sender, &dr_id);
+        let factory = query::is_executor_eligible::Query::factory(
+            sender.pub_key_hex(),
+            dr_id,
+            self.chain_id(),
+            self.contract_addr_str(),
+        );
+        let proof = sender.prove(factory.get_hash());
+        let (query, _) = factory.create_message(proof);
+
+        // take the query out of the common message it comes wrapped in
+        let mut query = serde_json::to_value(query).unwrap();
+        let msg: crate::msgs::QueryMsg = ContractQueryMsg::IsExecutorEligibleWithVrfProof {
+            query:     serde_json::from_value(query["is_executor_eligible"].take()).unwrap(),
+            vrf_proof: vrf_proof.to_hex(),
+        }
+        .into();
+        self.query(msg).unwrap()
+    }
+
     #[track_caller]
     pub fn get_account_sequence(&self, public_key: PublicKey) -> Uint128 {
         self.query(query::QueryMsg::GetAccountSeq {
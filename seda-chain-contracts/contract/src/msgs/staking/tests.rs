@@ -5,7 +5,18 @@ use msgs::data_requests::{
 use seda_common::msgs::staking::{Staker, StakingConfig};
 
 use super::*;
-use crate::TestInfo;
+use crate::{
+    msgs::staking::{
+        query::StakerUnbonding,
+        state::{
+            is_eligible_for_dr::{committee_selection_alpha, is_selected_by_vrf, verify_vrf_proof},
+            CommitteeSelection,
+            ExtendedStakingConfig,
+            UnbondingEntry,
+        },
+    },
+    TestInfo,
+};
 
 #[test]
 fn owner_set_staking_config() {
@@ -299,7 +310,7 @@ fn only_allow_active_stakers_to_be_eligible() {
     test_info.stake(&mut val2, Some("memo".to_string()), 10).unwrap();
 
     // post a data request
-    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(1, 2);
+    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(1, 1);
     let dr_id = test_info
         .post_data_request(&mut val1, dr.clone(), vec![], vec![1, 2, 3], 2, None)
         .unwrap();
@@ -312,6 +323,85 @@ fn only_allow_active_stakers_to_be_eligible() {
     assert!(!is_val2_executor_eligible);
 }
 
+#[test]
+fn replication_factor_limited_by_eligible_executors() {
+    let mut test_info = TestInfo::init();
+
+    let new_config = StakingConfig {
+        minimum_stake_to_register:               10u8.into(),
+        minimum_stake_for_committee_eligibility: 20u8.into(),
+        allowlist_enabled:                       false,
+    };
+    test_info.set_staking_config(&test_info.creator(), new_config).unwrap();
+
+    // val1 is eligible, val2 is staked but below the committee minimum
+    let mut val1 = test_info.new_executor("val1", Some(80));
+    test_info.stake(&mut val1, Some("memo".to_string()), 21).unwrap();
+    let mut val2 = test_info.new_executor("val2", Some(20));
+    test_info.stake(&mut val2, Some("memo".to_string()), 10).unwrap();
+
+    // a replication factor of 2 cannot be satisfied
+    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(1, 2);
+    let res = test_info.post_data_request(&mut val1, dr, vec![], vec![1, 2, 3], 1, None);
+    assert!(res.is_err_and(|x| x == ContractError::DataRequestReplicationFactorTooHigh(1)));
+
+    // val2 tops up their stake and becomes eligible
+    test_info.increase_stake(&mut val2, 10).unwrap();
+    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(1, 2);
+    let dr_id = test_info
+        .post_data_request(&mut val1, dr, vec![], vec![1, 2, 3], 1, None)
+        .unwrap();
+    assert!(test_info.is_executor_eligible(&val1, dr_id.clone()));
+    assert!(test_info.is_executor_eligible(&val2, dr_id));
+}
+
+#[test]
+fn eligible_executors_follow_stake_and_config_changes() {
+    let mut test_info = TestInfo::init();
+
+    let mut alice = test_info.new_executor("alice", Some(40));
+    test_info.stake(&mut alice, Some("memo".to_string()), 10).unwrap();
+    let mut bob = test_info.new_executor("bob", Some(40));
+    test_info.stake(&mut bob, Some("memo".to_string()), 10).unwrap();
+
+    // alice unstakes everything and is no longer eligible
+    test_info.unstake(&alice, 10).unwrap();
+    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(1, 2);
+    let res = test_info.post_data_request(&mut bob, dr, vec![], vec![1, 2, 3], 1, None);
+    assert!(res.is_err_and(|x| x == ContractError::DataRequestReplicationFactorTooHigh(1)));
+
+    // only bob can be selected
+    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(1, 1);
+    let dr_id = test_info
+        .post_data_request(&mut bob, dr, vec![], vec![1, 2, 3], 1, None)
+        .unwrap();
+    assert!(!test_info.is_executor_eligible(&alice, dr_id.clone()));
+    assert!(test_info.is_executor_eligible(&bob, dr_id));
+
+    // raising the committee minimum makes bob ineligible as well
+    let new_config = StakingConfig {
+        minimum_stake_to_register:               1u8.into(),
+        minimum_stake_for_committee_eligibility: 20u8.into(),
+        allowlist_enabled:                       false,
+    };
+    test_info.set_staking_config(&test_info.creator(), new_config).unwrap();
+    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(2, 1);
+    let res = test_info.post_data_request(&mut bob, dr, vec![], vec![1, 2, 3], 1, None);
+    assert!(res.is_err_and(|x| x == ContractError::DataRequestReplicationFactorTooHigh(0)));
+
+    // lowering it again restores bob
+    let new_config = StakingConfig {
+        minimum_stake_to_register:               1u8.into(),
+        minimum_stake_for_committee_eligibility: 10u8.into(),
+        allowlist_enabled:                       false,
+    };
+    test_info.set_staking_config(&test_info.creator(), new_config).unwrap();
+    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(2, 1);
+    test_info
+        .post_data_request(&mut bob, dr, vec![], vec![1, 2, 3], 1, None)
+        .unwrap();
+}
+
 const VALIDATORS_AMOUNT: usize = 50;
 
 lazy_static::lazy_static! {
@@ -500,3 +590,295 @@ fn minimum_stake_for_committee_eligibility_cannot_be_zero() {
     let res = test_info.set_staking_config(&test_info.creator(), new_config);
     assert!(res.is_err_and(|x| x == ContractError::ZeroMinimumStakeForCommitteeEligibility));
 }
+
+#[test]
+fn owner_set_extended_staking_config() {
+    let mut test_info = TestInfo::init();
+
+    let new_config = ExtendedStakingConfig {
+        unbonding_period_in_blocks: 5,
+        slashing:                   None,
+        committee_selection:        CommitteeSelection::Window,
+    };
+
+    // non-owner cannot set the extended staking config
+    let non_owner = test_info.new_executor("non-owner", Some(2));
+    let res = test_info.set_extended_staking_config(&non_owner, new_config.clone());
+    assert!(res.is_err_and(|x| x == ContractError::NotOwner));
+
+    // owner sets extended staking config
+    test_info
+        .set_extended_staking_config(&test_info.creator(), new_config.clone())
+        .unwrap();
+    assert_eq!(new_config, test_info.get_extended_staking_config());
+}
+
+#[test]
+fn unstaked_tokens_unbond_before_withdraw() {
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 10,
+                slashing:                   None,
+                committee_selection:        CommitteeSelection::Window,
+            },
+        )
+        .unwrap();
+
+    let mut alice = test_info.new_executor("alice", Some(10));
+    test_info.stake(&mut alice, None, 10).unwrap();
+
+    // alice unstakes at height 5 and 8
+    test_info.set_block_height(5);
+    test_info.unstake(&alice, 3).unwrap();
+    test_info.set_block_height(8);
+    test_info.unstake(&alice, 2).unwrap();
+
+    let unbonding = test_info.get_staker_unbonding(alice.pub_key());
+    assert_eq!(
+        unbonding,
+        Some(StakerUnbonding {
+            withdrawable: 0u8.into(),
+            unbonding:    vec![
+                UnbondingEntry {
+                    amount:          3u8.into(),
+                    withdrawable_at: 15,
+                },
+                UnbondingEntry {
+                    amount:          2u8.into(),
+                    withdrawable_at: 18,
+                },
+            ],
+        })
+    );
+
+    // nothing can be withdrawn yet
+    let res = test_info.withdraw(&mut alice, 1);
+    assert!(res.is_err_and(|x| x == ContractError::InsufficientFunds(0u8.into(), 1u8.into())));
+
+    // the first entry matures
+    test_info.set_block_height(15);
+    let res = test_info.withdraw(&mut alice, 4);
+    assert!(res.is_err_and(|x| x == ContractError::InsufficientFunds(3u8.into(), 4u8.into())));
+    test_info.withdraw(&mut alice, 3).unwrap();
+
+    let unbonding = test_info.get_staker_unbonding(alice.pub_key());
+    assert_eq!(
+        unbonding,
+        Some(StakerUnbonding {
+            withdrawable: 0u8.into(),
+            unbonding:    vec![UnbondingEntry {
+                amount:          2u8.into(),
+                withdrawable_at: 18,
+            }],
+        })
+    );
+
+    // the second entry matures
+    test_info.set_block_height(18);
+    test_info.withdraw(&mut alice, 2).unwrap();
+
+    let staker = test_info.get_staker(alice.pub_key()).unwrap();
+    assert_eq!(staker.tokens_staked, Uint128::new(5));
+    assert_eq!(staker.tokens_pending_withdrawal, Uint128::zero());
+    assert_eq!(
+        test_info.get_staker_unbonding(alice.pub_key()),
+        Some(StakerUnbonding {
+            withdrawable: 0u8.into(),
+            unbonding:    vec![],
+        })
+    );
+}
+
+#[test]
+fn zero_unbonding_period_is_withdrawable_immediately() {
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 10,
+                slashing:                   None,
+                committee_selection:        CommitteeSelection::Window,
+            },
+        )
+        .unwrap();
+
+    let mut alice = test_info.new_executor("alice", Some(10));
+    test_info.stake(&mut alice, None, 10).unwrap();
+    test_info.unstake(&alice, 5).unwrap();
+
+    // unbonding period of 0 means nothing is locked
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   None,
+                committee_selection:        CommitteeSelection::Window,
+            },
+        )
+        .unwrap();
+    test_info.unstake(&alice, 2).unwrap();
+
+    let unbonding = test_info.get_staker_unbonding(alice.pub_key()).unwrap();
+    assert_eq!(unbonding.withdrawable, Uint128::new(2));
+    assert_eq!(unbonding.unbonding.len(), 1);
+
+    test_info.withdraw(&mut alice, 2).unwrap();
+    let res = test_info.withdraw(&mut alice, 1);
+    assert!(res.is_err_and(|x| x == ContractError::InsufficientFunds(0u8.into(), 1u8.into())));
+}
+
+#[test]
+fn unbonding_of_unknown_staker_is_none() {
+    let mut test_info = TestInfo::init();
+    let alice = test_info.new_executor("alice", Some(10));
+
+    assert_eq!(test_info.get_staker_unbonding(alice.pub_key()), None);
+}
+
+#[test]
+fn vrf_threshold_follows_replication_factor() {
+    // nobody can be selected without eligible executors
+    assert!(!is_selected_by_vrf([0; 32], 1, 0));
+
+    // everybody is selected when the replication factor covers all executors
+    assert!(is_selected_by_vrf([u8::MAX; 32], 3, 3));
+    assert!(is_selected_by_vrf([u8::MAX; 32], 4, 3));
+
+    // otherwise only outputs below the threshold are selected
+    assert!(is_selected_by_vrf([0; 32], 1, 4));
+    assert!(!is_selected_by_vrf([u8::MAX; 32], 1, 4));
+
+    // roughly a quarter of the output space selects 1 out of 4
+    let mut below = [0u8; 32];
+    below[0] = 0x3f;
+    assert!(is_selected_by_vrf(below, 1, 4));
+    let mut above = [0u8; 32];
+    above[0] = 0x40;
+    assert!(!is_selected_by_vrf(above, 1, 4));
+    assert!(is_selected_by_vrf(above, 2, 4));
+}
+
+#[test]
+fn vrf_committee_selection() {
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   None,
+                committee_selection:        CommitteeSelection::Vrf,
+            },
+        )
+        .unwrap();
+
+    let mut validators = Vec::with_capacity(VALIDATORS_AMOUNT);
+    for validator_name in LARGE_SET_VALIDATOR_NAMES.iter() {
+        let mut validator = test_info.new_executor(validator_name, Some(20));
+        test_info.stake(&mut validator, Some("memo".to_string()), 2).unwrap();
+        validators.push(validator);
+    }
+
+    // post a data request
+    let mut anyone = test_info.new_executor("anyone", Some(40));
+    let replication_factor = 8;
+    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(1, replication_factor);
+    let dr_id = test_info
+        .post_data_request(&mut anyone, dr.clone(), vec![], vec![1, 2, 3], 1, None)
+        .unwrap();
+
+    let alpha = committee_selection_alpha(
+        &Hash::from_hex_str(&dr_id).unwrap(),
+        test_info.chain_id(),
+        test_info.contract_addr_str(),
+    );
+    for validator in validators {
+        let proof = test_info.committee_selection_proof(&validator, &dr_id);
+        let output = verify_vrf_proof(&validator.pub_key(), &proof, &alpha).unwrap();
+        let expected = is_selected_by_vrf(output, replication_factor, VALIDATORS_AMOUNT as u32);
+
+        assert_eq!(
+            expected,
+            test_info.is_executor_eligible_with_vrf(&validator, dr_id.clone())
+        );
+        // without a VRF proof the executor cannot show it was selected
+        assert!(!test_info.is_executor_eligible(&validator, dr_id.clone()));
+    }
+}
+
+#[test]
+fn stake_weighted_committee_selection() {
+    let mut test_info = TestInfo::init();
+    test_info
+        .set_extended_staking_config(
+            &test_info.creator(),
+            ExtendedStakingConfig {
+                unbonding_period_in_blocks: 0,
+                slashing:                   None,
+                committee_selection:        CommitteeSelection::StakeWeighted {
+                    stake_cap: 100u128.into(),
+                },
+            },
+        )
+        .unwrap();
+
+    let mut validators = Vec::with_capacity(VALIDATORS_AMOUNT);
+    for (i, validator_name) in LARGE_SET_VALIDATOR_NAMES.iter().enumerate() {
+        let mut validator = test_info.new_executor(validator_name, Some(1000));
+        test_info
+            .stake(&mut validator, Some("memo".to_string()), 1 + 10 * i as u128)
+            .unwrap();
+        validators.push(validator);
+    }
+
+    // post a data request
+    let mut anyone = test_info.new_executor("anyone", Some(40));
+    let replication_factor = 8;
+    let dr = data_requests::test::test_helpers::calculate_dr_id_and_args(1, replication_factor);
+    let dr_id = test_info
+        .post_data_request(&mut anyone, dr.clone(), vec![], vec![1, 2, 3], 1, None)
+        .unwrap();
+
+    // exactly a replication factor of executors is selected
+    let (selected, not_selected): (Vec<_>, Vec<_>) = validators
+        .iter()
+        .partition(|validator| test_info.is_executor_eligible(validator, dr_id.clone()));
+    assert_eq!(replication_factor as usize, selected.len());
+
+    // the committee is drawn when posting, later stakes do not move it
+    let mut newcomer = test_info.new_executor("newcomer", Some(1000));
+    test_info.stake(&mut newcomer, Some("memo".to_string()), 100).unwrap();
+    assert!(!test_info.is_executor_eligible(&newcomer, dr_id.clone()));
+    assert!(selected
+        .iter()
+        .all(|validator| test_info.is_executor_eligible(validator, dr_id.clone())));
+
+    // only selected executors can commit
+    let res = test_info.commit_result(not_selected[0], &dr_id, "0xcommitment".hash());
+    assert!(res.is_err_and(|x| x == ContractError::NotInCommittee));
+    test_info
+        .commit_result(selected[0], &dr_id, "0xcommitment".hash())
+        .unwrap();
+}
+
+#[test]
+fn stake_weighted_cap_cannot_be_zero() {
+    let mut test_info = TestInfo::init();
+
+    let res = test_info.set_extended_staking_config(
+        &test_info.creator(),
+        ExtendedStakingConfig {
+            unbonding_period_in_blocks: 0,
+            slashing:                   None,
+            committee_selection:        CommitteeSelection::StakeWeighted {
+                stake_cap: Uint128::zero(),
+            },
+        },
+    );
+    assert!(res.is_err_and(|x| x == ContractError::ZeroStakeCap));
+}
@@ -1,7 +1,25 @@
 use std::collections::HashMap;
 
-use cosmwasm_std::{coins, from_json, testing::MockApi, to_json_binary, Addr, StdError};
-use cw_multi_test::{App, AppBuilder, ContractWrapper, Executor};
+use cosmwasm_std::{
+    coins,
+    from_json,
+    testing::MockApi,
+    to_json_binary,
+    Addr,
+    Binary,
+    Deps,
+    DepsMut,
+    Empty,
+    Env,
+    MessageInfo,
+    Response,
+    StdError,
+    StdResult,
+    Uint128,
+};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
 use k256::{
     ecdsa::{SigningKey, VerifyingKey},
     elliptic_curve::rand_core::OsRng,
@@ -11,7 +29,13 @@ use serde::{de::DeserializeOwned, Serialize};
 use sha3::{Digest, Keccak256};
 use vrf_rs::Secp256k1Sha256;
 
-use crate::{common_types::Hash, contract::*, error::ContractError, types::PublicKey};
+use crate::{
+    common_types::Hash,
+    contract::*,
+    error::{CodedError, ContractError},
+    msgs::data_requests::sudo::ResultCallbackMsg,
+    types::PublicKey,
+};
 
 pub fn new_public_key() -> (SigningKey, PublicKey) {
     let signing_key = SigningKey::random(&mut OsRng);
@@ -23,6 +47,7 @@ pub fn new_public_key() -> (SigningKey, PublicKey) {
 
 pub struct TestInfo {
     app:           App,
+    code_id:       u64,
     contract_addr: Addr,
     executors:     HashMap<&'static str, TestExecutor>,
     chain_id:      String,
@@ -42,7 +67,12 @@ impl TestInfo {
                     .unwrap();
                 executors.insert("creator", creator);
             });
-        let contract = Box::new(ContractWrapper::new(execute, instantiate, query).with_sudo(sudo));
+        let contract = Box::new(
+            ContractWrapper::new(execute, instantiate, query)
+                .with_sudo(sudo)
+                .with_migrate(migrate)
+                .with_reply(reply),
+        );
         let chain_id = "seda_test".to_string();
         let creator = executors.get("creator").unwrap();
 
@@ -56,11 +86,19 @@ impl TestInfo {
         };
 
         let contract_addr = app
-            .instantiate_contract(code_id, creator.addr(), &init_msg, &[], "core", None)
+            .instantiate_contract(
+                code_id,
+                creator.addr(),
+                &init_msg,
+                &[],
+                "core",
+                Some(creator.addr().into_string()),
+            )
             .unwrap();
 
         let mut info = Self {
             app,
+            code_id,
             contract_addr,
             executors,
             chain_id,
@@ -151,10 +189,25 @@ impl TestInfo {
     }
 
     #[track_caller]
-    pub fn sudo<R: DeserializeOwned>(&mut self, msg: &SudoMsg) -> Result<R, ContractError> {
+    pub fn migrate(&mut self) -> Result<(), ContractError> {
+        let creator = self.creator();
+        self.app
+            .migrate_contract(creator.addr(), self.contract_addr.clone(), &MigrateMsg {}, self.code_id)
+            .map_err(|e| {
+                if e.downcast_ref::<CodedError>().is_some() {
+                    e.downcast::<CodedError>().unwrap().0
+                } else {
+                    ContractError::Dbg(e.to_string())
+                }
+            })?;
+        Ok(())
+    }
+
+    #[track_caller]
+    pub fn sudo<M: Serialize, R: DeserializeOwned>(&mut self, msg: &M) -> Result<R, ContractError> {
         let res = self.app.wasm_sudo(self.contract_addr.clone(), msg).map_err(|e| {
-            if e.downcast_ref::<ContractError>().is_some() {
-                e.downcast().unwrap()
+            if e.downcast_ref::<CodedError>().is_some() {
+                e.downcast::<CodedError>().unwrap().0
             } else if let Some(s_err) = e.downcast_ref::<StdError>() {
                 return ContractError::Std(s_err.to_string());
             } else {
@@ -169,17 +222,17 @@ impl TestInfo {
     }
 
     #[track_caller]
-    pub fn execute<R: DeserializeOwned>(
+    pub fn execute<M: Serialize, R: DeserializeOwned>(
         &mut self,
         sender: &TestExecutor,
-        msg: &ExecuteMsg,
+        msg: &M,
     ) -> Result<R, ContractError> {
         let res = self
             .app
             .execute_contract(sender.addr(), self.contract_addr.clone(), msg, &[])
             .map_err(|e| {
-                if e.downcast_ref::<ContractError>().is_some() {
-                    e.downcast().unwrap()
+                if e.downcast_ref::<CodedError>().is_some() {
+                    e.downcast::<CodedError>().unwrap().0
                 } else if let Some(s_err) = e.downcast_ref::<StdError>() {
                     return ContractError::Std(s_err.to_string());
                 } else {
@@ -194,18 +247,18 @@ impl TestInfo {
     }
 
     #[track_caller]
-    pub fn execute_with_funds<R: DeserializeOwned>(
+    pub fn execute_with_funds<M: Serialize, R: DeserializeOwned>(
         &mut self,
         sender: &mut TestExecutor,
-        msg: &ExecuteMsg,
+        msg: &M,
         amount: u128,
     ) -> Result<R, ContractError> {
         let res = self
             .app
             .execute_contract(sender.addr(), self.contract_addr.clone(), msg, &coins(amount, "aseda"))
             .map_err(|e| {
-                if e.downcast_ref::<ContractError>().is_some() {
-                    e.downcast().unwrap()
+                if e.downcast_ref::<CodedError>().is_some() {
+                    e.downcast::<CodedError>().unwrap().0
                 } else if let Some(s_err) = e.downcast_ref::<StdError>() {
                     return ContractError::Std(s_err.to_string());
                 } else {
@@ -220,6 +273,174 @@ impl TestInfo {
     }
 }
 
+/// Last result received by a result consumer contract.
+const LAST_RESULT: Item<ResultCallbackMsg> = Item::new("last_result");
+
+/// Stores the results it receives, rejecting the ones without consensus.
+fn result_consumer_execute(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: ResultCallbackMsg,
+) -> StdResult<Response> {
+    let ResultCallbackMsg::SedaDataResult { consensus, .. } = &msg;
+    if !consensus {
+        return Err(StdError::generic_err("result without consensus"));
+    }
+    LAST_RESULT.save(deps.storage, &msg)?;
+    Ok(Response::new())
+}
+
+fn result_consumer_instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn result_consumer_query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_json_binary(&LAST_RESULT.may_load(deps.storage)?)
+}
+
+/// Whether a rejecting CW20 token currently rejects transfers and burns.
+const REJECTING: Item<bool> = Item::new("rejecting");
+
+/// Executes like a CW20 token, but fails transfers and burns while set to reject.
+fn rejecting_cw20_execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: cw20_base::msg::ExecuteMsg,
+) -> Result<Response, cw20_base::ContractError> {
+    let rejecting = REJECTING.may_load(deps.storage)?.unwrap_or_default();
+    if rejecting
+        && matches!(
+            msg,
+            cw20_base::msg::ExecuteMsg::Transfer { .. } | cw20_base::msg::ExecuteMsg::Burn { .. }
+        )
+    {
+        return Err(StdError::generic_err("rejected").into());
+    }
+    cw20_base::contract::execute(deps, env, info, msg)
+}
+
+impl TestInfo {
+    /// Instantiates a contract that accepts result callbacks.
+    pub fn new_result_consumer(&mut self) -> Addr {
+        let creator = self.creator();
+        let contract = Box::new(ContractWrapper::new(
+            result_consumer_execute,
+            result_consumer_instantiate,
+            result_consumer_query,
+        ));
+        let code_id = self.app.store_code(contract);
+        self.app
+            .instantiate_contract(code_id, creator.addr(), &Empty {}, &[], "consumer", None)
+            .unwrap()
+    }
+
+    /// The last result a result consumer contract received.
+    pub fn last_received_result(&self, consumer: &Addr) -> Option<ResultCallbackMsg> {
+        self.app.wrap().query_wasm_smart(consumer, &Empty {}).unwrap()
+    }
+
+    /// Instantiates a CW20 token with the given initial balances.
+    pub fn new_cw20(&mut self, balances: &[(&Addr, u128)]) -> Addr {
+        let contract = Box::new(ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ));
+        self.instantiate_cw20(contract, balances)
+    }
+
+    /// Instantiates a CW20 token like `new_cw20` that rejects transfers and burns
+    /// while set to reject with `set_cw20_rejecting`.
+    pub fn new_rejecting_cw20(&mut self, balances: &[(&Addr, u128)]) -> Addr {
+        let contract = Box::new(ContractWrapper::new(
+            rejecting_cw20_execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        ));
+        self.instantiate_cw20(contract, balances)
+    }
+
+    /// Makes a CW20 token created with `new_rejecting_cw20` reject transfers and burns, or accept them again.
+    pub fn set_cw20_rejecting(&mut self, cw20_contract: &Addr, rejecting: bool) {
+        let mut storage = self.app.contract_storage_mut(cw20_contract);
+        REJECTING.save(&mut *storage, &rejecting).unwrap();
+    }
+
+    fn instantiate_cw20(&mut self, contract: Box<dyn Contract<Empty>>, balances: &[(&Addr, u128)]) -> Addr {
+        let creator = self.creator();
+        let code_id = self.app.store_code(contract);
+        let msg = cw20_base::msg::InstantiateMsg {
+            name:             "Test Token".to_string(),
+            symbol:           "TEST".to_string(),
+            decimals:         6,
+            initial_balances: balances
+                .iter()
+                .map(|(address, amount)| Cw20Coin {
+                    address: address.to_string(),
+                    amount:  (*amount).into(),
+                })
+                .collect(),
+            mint:             None,
+            marketing:        None,
+        };
+        self.app
+            .instantiate_contract(code_id, creator.addr(), &msg, &[], "cw20", None)
+            .unwrap()
+    }
+
+    pub fn cw20_balance(&self, cw20_contract: &Addr, address: &Addr) -> u128 {
+        let res: BalanceResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(
+                cw20_contract,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        res.balance.u128()
+    }
+
+    pub fn cw20_total_supply(&self, cw20_contract: &Addr) -> u128 {
+        let res: cw20::TokenInfoResponse = self
+            .app
+            .wrap()
+            .query_wasm_smart(cw20_contract, &Cw20QueryMsg::TokenInfo {})
+            .unwrap();
+        res.total_supply.u128()
+    }
+
+    /// Sends CW20 tokens to this contract, calling its `Receive` hook with the given message.
+    /// Errors of this contract are nested in the error of the CW20 contract,
+    /// so they are returned as their message.
+    #[track_caller]
+    pub fn send_cw20<M: Serialize, R: DeserializeOwned>(
+        &mut self,
+        sender: &TestExecutor,
+        cw20_contract: &Addr,
+        amount: u128,
+        msg: &M,
+    ) -> Result<R, ContractError> {
+        let send = Cw20ExecuteMsg::Send {
+            contract: self.contract_addr.to_string(),
+            amount:   Uint128::new(amount),
+            msg:      to_json_binary(msg).unwrap(),
+        };
+        let res = self
+            .app
+            .execute_contract(sender.addr(), cw20_contract.clone(), &send, &[])
+            .map_err(|e| ContractError::Dbg(e.root_cause().to_string()))?;
+
+        Ok(match res.data {
+            Some(data) => from_json(data).unwrap(),
+            None => from_json(to_json_binary(&serde_json::Value::Null).unwrap()).unwrap(),
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TestExecutor {
     pub name:    &'static str,
@@ -79,15 +79,51 @@ type CommitDataResult struct {
 	Proof      string `json:"proof"`
 }
 
+// CommitDataResultWithVrfProof is the commit sent instead of CommitDataResult when the
+// core contract selects committees by VRF, carrying the executor's VRF proof.
+type CommitDataResultWithVrfProof struct {
+	CommitDataResult
+	VrfProof string `json:"vrf_proof"`
+}
+
 type RevealDataResult struct {
 	DrID      string `json:"dr_id"`
 	PublicKey string `json:"public_key"`
 }
 
+// BatchCommitDataResults commits to several data requests as the same
+// executor. It only qualifies for free gas if every commit does.
+type BatchCommitDataResults struct {
+	PublicKey string        `json:"public_key"`
+	Commits   []BatchCommit `json:"commits"`
+}
+
+type BatchCommit struct {
+	DrID       string  `json:"dr_id"`
+	Commitment string  `json:"commitment"`
+	Proof      string  `json:"proof"`
+	VrfProof   *string `json:"vrf_proof"`
+}
+
+// BatchRevealDataResults reveals for several data requests as the same
+// executor. It only qualifies for free gas if every reveal does.
+type BatchRevealDataResults struct {
+	PublicKey string        `json:"public_key"`
+	Reveals   []BatchReveal `json:"reveals"`
+}
+
+type BatchReveal struct {
+	DrID string `json:"dr_id"`
+}
+
 type CanExecutorCommitQuery struct {
 	CanExecutorCommit CommitDataResult `json:"can_executor_commit"`
 }
 
+type CanExecutorCommitWithVrfProofQuery struct {
+	CanExecutorCommitWithVrfProof CommitDataResultWithVrfProof `json:"can_executor_commit_with_vrf_proof"`
+}
+
 type CanExecutorRevealQuery struct {
 	CanExecutorReveal RevealDataResult `json:"can_executor_reveal"`
 }
@@ -112,6 +148,13 @@ func (d CommitRevealDecorator) checkFreeGas(ctx sdk.Context, coreContract sdk.Ac
 				return false
 			}
 
+			return result
+		case CommitDataResultWithVrfProof:
+			result, err := d.queryContract(ctx, coreContract, CanExecutorCommitWithVrfProofQuery{CanExecutorCommitWithVrfProof: contractMsg})
+			if err != nil {
+				return false
+			}
+
 			return result
 		case RevealDataResult:
 			result, err := d.queryContract(ctx, coreContract, CanExecutorRevealQuery{CanExecutorReveal: contractMsg})
@@ -120,6 +163,10 @@ func (d CommitRevealDecorator) checkFreeGas(ctx sdk.Context, coreContract sdk.Ac
 			}
 
 			return result
+		case BatchCommitDataResults:
+			return d.checkFreeGasBatchCommit(ctx, coreContract, contractMsg)
+		case BatchRevealDataResults:
+			return d.checkFreeGasBatchReveal(ctx, coreContract, contractMsg)
 		// Not a commit or reveal message, so we don't need to check for free gas
 		default:
 			return false
@@ -130,6 +177,73 @@ func (d CommitRevealDecorator) checkFreeGas(ctx sdk.Context, coreContract sdk.Ac
 	}
 }
 
+// checkFreeGasBatchCommit checks that every commit of the batch is one the
+// executor can make. Empty batches and batches committing to the same data
+// request twice do not qualify, as the duplicates would fail for free.
+func (d CommitRevealDecorator) checkFreeGasBatchCommit(ctx sdk.Context, coreContract sdk.AccAddress, batch BatchCommitDataResults) bool {
+	if len(batch.Commits) == 0 {
+		return false
+	}
+
+	seen := make(map[string]bool, len(batch.Commits))
+	for _, item := range batch.Commits {
+		if seen[item.DrID] {
+			return false
+		}
+		seen[item.DrID] = true
+
+		commit := CommitDataResult{
+			DrID:       item.DrID,
+			PublicKey:  batch.PublicKey,
+			Commitment: item.Commitment,
+			Proof:      item.Proof,
+		}
+		var query interface{} = CanExecutorCommitQuery{CanExecutorCommit: commit}
+		if item.VrfProof != nil {
+			query = CanExecutorCommitWithVrfProofQuery{
+				CanExecutorCommitWithVrfProof: CommitDataResultWithVrfProof{
+					CommitDataResult: commit,
+					VrfProof:         *item.VrfProof,
+				},
+			}
+		}
+
+		result, err := d.queryContract(ctx, coreContract, query)
+		if err != nil || !result {
+			return false
+		}
+	}
+
+	return true
+}
+
+// checkFreeGasBatchReveal checks that every reveal of the batch is one the
+// executor can make, with the same restrictions as for batch commits.
+func (d CommitRevealDecorator) checkFreeGasBatchReveal(ctx sdk.Context, coreContract sdk.AccAddress, batch BatchRevealDataResults) bool {
+	if len(batch.Reveals) == 0 {
+		return false
+	}
+
+	seen := make(map[string]bool, len(batch.Reveals))
+	for _, item := range batch.Reveals {
+		if seen[item.DrID] {
+			return false
+		}
+		seen[item.DrID] = true
+
+		reveal := RevealDataResult{
+			DrID:      item.DrID,
+			PublicKey: batch.PublicKey,
+		}
+		result, err := d.queryContract(ctx, coreContract, CanExecutorRevealQuery{CanExecutorReveal: reveal})
+		if err != nil || !result {
+			return false
+		}
+	}
+
+	return true
+}
+
 func (d CommitRevealDecorator) queryContract(ctx sdk.Context, coreContract sdk.AccAddress, query interface{}) (bool, error) {
 	queryBytes, err := json.Marshal(query)
 	if err != nil {
@@ -151,8 +265,11 @@ func (d CommitRevealDecorator) queryContract(ctx sdk.Context, coreContract sdk.A
 func unmarshalMsg(msg wasmtypes.RawContractMessage) (interface{}, error) {
 	// We're only interested in the commit or reveal messages
 	var msgData struct {
-		CommitDataResult *CommitDataResult `json:"commit_data_result"`
-		RevealDataResult *RevealDataResult `json:"reveal_data_result"`
+		CommitDataResult             *CommitDataResult             `json:"commit_data_result"`
+		CommitDataResultWithVrfProof *CommitDataResultWithVrfProof `json:"commit_data_result_with_vrf_proof"`
+		RevealDataResult             *RevealDataResult             `json:"reveal_data_result"`
+		BatchCommitDataResults       *BatchCommitDataResults       `json:"batch_commit_data_results"`
+		BatchRevealDataResults       *BatchRevealDataResults       `json:"batch_reveal_data_results"`
 	}
 	if err := json.Unmarshal(msg, &msgData); err != nil {
 		return nil, err
@@ -162,9 +279,21 @@ func unmarshalMsg(msg wasmtypes.RawContractMessage) (interface{}, error) {
 		return *msgData.CommitDataResult, nil
 	}
 
+	if msgData.CommitDataResultWithVrfProof != nil {
+		return *msgData.CommitDataResultWithVrfProof, nil
+	}
+
 	if msgData.RevealDataResult != nil {
 		return *msgData.RevealDataResult, nil
 	}
 
+	if msgData.BatchCommitDataResults != nil {
+		return *msgData.BatchCommitDataResults, nil
+	}
+
+	if msgData.BatchRevealDataResults != nil {
+		return *msgData.BatchRevealDataResults, nil
+	}
+
 	return nil, nil
 }
@@ -124,13 +124,15 @@ func (k Keeper) ProcessTallies(ctx sdk.Context, coreContract sdk.AccAddress) err
 		}
 	}
 
-	// Notify the Core Contract of tally completion.
-	msg, err := types.MarshalSudoRemoveDataRequests(processedReqs)
-	if err != nil {
-		k.Logger(ctx).Error("[HALTS_DR_FLOW] failed to marshal sudo remove data requests", "err", err)
-		return nil
+	// Notify the Core Contract of tally completion, handing it the final
+	// results so that it can archive them and run the result callbacks.
+	resolvedReqs := make([]types.ResolvedDataRequest, 0, len(processedReqs))
+	for i, req := range tallyList {
+		if distributions, ok := processedReqs[req.ID]; ok {
+			resolvedReqs = append(resolvedReqs, types.NewResolvedDataRequest(dataResults[i], distributions))
+		}
 	}
-	_, err = k.wasmKeeper.Sudo(ctx, coreContract, msg)
+	err = k.removeDataRequests(ctx, coreContract, processedReqs, resolvedReqs)
 	if err != nil {
 		k.Logger(ctx).Error("[HALTS_DR_FLOW] failed to notify core contract of tally completion", "err", err)
 		return nil
@@ -167,6 +169,46 @@ func (k Keeper) ProcessTallies(ctx sdk.Context, coreContract sdk.AccAddress) err
 	return nil
 }
 
+// removeDataRequests removes the tallied data requests from the Core
+// Contract along with their final results. Core Contracts that predate
+// the results reject them, in which case the data requests are removed
+// with their distributions only.
+func (k Keeper) removeDataRequests(ctx sdk.Context, coreContract sdk.AccAddress, processedReqs map[string][]types.Distribution, resolvedReqs []types.ResolvedDataRequest) error {
+	msg, err := types.MarshalSudoRemoveDataRequestsWithResults(resolvedReqs)
+	if err != nil {
+		return err
+	}
+	cacheCtx, writeCache := ctx.CacheContext()
+	_, err = k.wasmKeeper.Sudo(cacheCtx, coreContract, msg)
+	if err == nil {
+		writeCache()
+		return nil
+	}
+	// Only a core contract that does not know the message yet is
+	// expected to reject it, any other failure is returned.
+	if !isUnknownSudoMsgErr(err) {
+		return err
+	}
+	k.Logger(ctx).Error("[HALTS_DR_FLOW] core contract does not accept data request results - removing data requests without them", "err", err)
+
+	msg, err = types.MarshalSudoRemoveDataRequests(processedReqs)
+	if err != nil {
+		return err
+	}
+	_, err = k.wasmKeeper.Sudo(ctx, coreContract, msg)
+	return err
+}
+
+// isUnknownSudoMsgErr returns true if the error is the core contract
+// failing to deserialize a sudo message, as it does for a variant it
+// does not know.
+func isUnknownSudoMsgErr(err error) bool {
+	msg := err.Error()
+	return strings.Contains(msg, "unknown variant") ||
+		strings.Contains(msg, "did not match any variant") ||
+		strings.Contains(msg, "Error parsing into type")
+}
+
 type TallyResult struct {
 	Consensus    bool
 	StdOut       []string
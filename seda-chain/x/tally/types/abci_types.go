@@ -205,3 +205,64 @@ func MarshalSudoRemoveDataRequests(processedReqs map[string][]Distribution) ([]b
 		},
 	})
 }
+
+// ResolvedDataRequest is a tallied data request to be removed from the
+// Core Contract, along with its final result and the distributions of
+// its escrow.
+type ResolvedDataRequest struct {
+	DrID     string         `json:"dr_id"`
+	Result   ResolvedResult `json:"result"`
+	Messages []Distribution `json:"messages"`
+}
+
+// ResolvedResult is the final result of a data request, which the Core
+// Contract archives and sends to the result callback of the request.
+type ResolvedResult struct {
+	Result    []byte `json:"result"`
+	ExitCode  uint8  `json:"exit_code"`
+	GasUsed   uint64 `json:"gas_used"`
+	Consensus bool   `json:"consensus"`
+}
+
+func NewResolvedDataRequest(dataResult batchingtypes.DataResult, distributions []Distribution) ResolvedDataRequest {
+	// The Core Contract rejects null in place of an empty result or an
+	// empty list of distributions.
+	result := dataResult.Result
+	if result == nil {
+		result = []byte{}
+	}
+	if distributions == nil {
+		distributions = []Distribution{}
+	}
+
+	// Gas used never exceeds the gas limits, which are uint64.
+	var gasUsed uint64
+	if dataResult.GasUsed != nil && dataResult.GasUsed.IsUint64() {
+		gasUsed = dataResult.GasUsed.Uint64()
+	}
+
+	return ResolvedDataRequest{
+		DrID: dataResult.DrId,
+		Result: ResolvedResult{
+			Result:    result,
+			ExitCode:  uint8(dataResult.ExitCode), //nolint:gosec // G115: Tally exit codes fit in a byte.
+			GasUsed:   gasUsed,
+			Consensus: dataResult.Consensus,
+		},
+		Messages: distributions,
+	}
+}
+
+func MarshalSudoRemoveDataRequestsWithResults(resolvedReqs []ResolvedDataRequest) ([]byte, error) {
+	return json.Marshal(struct {
+		SudoRemoveDataRequestsWithResults struct {
+			Requests []ResolvedDataRequest `json:"requests"`
+		} `json:"remove_data_requests_with_results"`
+	}{
+		SudoRemoveDataRequestsWithResults: struct {
+			Requests []ResolvedDataRequest `json:"requests"`
+		}{
+			Requests: resolvedReqs,
+		},
+	})
+}